@@ -0,0 +1,5 @@
+#!/bin/sh
+# Regenerates the C header of the `capi` feature, requires `cargo install cbindgen`.
+set -e
+cd "$(dirname "$0")"
+cbindgen --config cbindgen.toml --crate i_overlay --output include/i_overlay.h
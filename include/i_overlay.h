@@ -0,0 +1,280 @@
+/*
+ * C interface of i_overlay, enabled by the `capi` feature.
+ *
+ * Geometry is passed in flat buffers: the points of all paths are stored one after another as x, y pairs,
+ * and the path i consists of the points offsets[i]..offsets[i + 1], so offsets has count + 1 items.
+ * Results are grouped the same way, shapes additionally store offsets into their contours.
+ *
+ * Every function returns a status code and writes its result into an output buffer.
+ * A result buffer is owned by the caller and must be released by the matching free function,
+ * releasing an empty or already released buffer does nothing.
+ */
+
+#ifndef I_OVERLAY_H
+#define I_OVERLAY_H
+
+/* Generated by cbindgen from src/capi, do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * The call succeeded.
+ */
+#define I_OVERLAY_OK 0
+
+/**
+ * A required pointer is null.
+ */
+#define I_OVERLAY_ERROR_NULL 1
+
+/**
+ * An unknown rule or style, decreasing offsets or a number which is not finite.
+ */
+#define I_OVERLAY_ERROR_ARGUMENT 2
+
+/**
+ * The operation failed unexpectedly.
+ */
+#define I_OVERLAY_ERROR_INTERNAL 3
+
+/**
+ * `OverlayRule` values.
+ */
+#define I_OVERLAY_RULE_SUBJECT 0
+
+#define I_OVERLAY_RULE_CLIP 1
+
+#define I_OVERLAY_RULE_INTERSECT 2
+
+#define I_OVERLAY_RULE_UNION 3
+
+#define I_OVERLAY_RULE_DIFFERENCE 4
+
+#define I_OVERLAY_RULE_INVERSE_DIFFERENCE 5
+
+#define I_OVERLAY_RULE_XOR 6
+
+/**
+ * `FillRule` values.
+ */
+#define I_OVERLAY_FILL_EVEN_ODD 0
+
+#define I_OVERLAY_FILL_NON_ZERO 1
+
+#define I_OVERLAY_FILL_POSITIVE 2
+
+#define I_OVERLAY_FILL_NEGATIVE 3
+
+/**
+ * `LineJoin` values.
+ */
+#define I_OVERLAY_JOIN_BEVEL 0
+
+#define I_OVERLAY_JOIN_MITER 1
+
+#define I_OVERLAY_JOIN_ROUND 2
+
+/**
+ * `LineCap` values.
+ */
+#define I_OVERLAY_CAP_BUTT 0
+
+#define I_OVERLAY_CAP_SQUARE 1
+
+#define I_OVERLAY_CAP_ROUND 2
+
+/**
+ * Paths with `f64` coordinates.
+ */
+typedef struct {
+    /**
+     * `x, y` pairs of all points.
+     */
+    const double *points;
+    /**
+     * `count + 1` point offsets of the paths.
+     */
+    const size_t *offsets;
+    size_t count;
+} IOverlayPaths;
+
+/**
+ * Paths with `i32` coordinates.
+ */
+typedef struct {
+    /**
+     * `x, y` pairs of all points.
+     */
+    const int32_t *points;
+    /**
+     * `count + 1` point offsets of the paths.
+     */
+    const size_t *offsets;
+    size_t count;
+} IOverlayIntPaths;
+
+/**
+ * Result paths with `f64` coordinates, released by `i_overlay_free_paths`.
+ */
+typedef struct {
+    /**
+     * `x, y` pairs of all points.
+     */
+    double *points;
+    size_t points_count;
+    /**
+     * `paths_count + 1` point offsets of the paths.
+     */
+    size_t *paths;
+    size_t paths_count;
+} IOverlayPathBuffer;
+
+/**
+ * Result shapes with `f64` coordinates, released by `i_overlay_free_shapes`.
+ *
+ * The first contour of a shape is its outer boundary, the others are holes.
+ */
+typedef struct {
+    /**
+     * `x, y` pairs of all points.
+     */
+    double *points;
+    size_t points_count;
+    /**
+     * `contours_count + 1` point offsets of the contours.
+     */
+    size_t *contours;
+    size_t contours_count;
+    /**
+     * `shapes_count + 1` contour offsets of the shapes.
+     */
+    size_t *shapes;
+    size_t shapes_count;
+} IOverlayShapeBuffer;
+
+/**
+ * Result shapes with `i32` coordinates, released by `i_overlay_free_int_shapes`.
+ */
+typedef struct {
+    /**
+     * `x, y` pairs of all points.
+     */
+    int32_t *points;
+    size_t points_count;
+    /**
+     * `contours_count + 1` point offsets of the contours.
+     */
+    size_t *contours;
+    size_t contours_count;
+    /**
+     * `shapes_count + 1` contour offsets of the shapes.
+     */
+    size_t *shapes;
+    size_t shapes_count;
+} IOverlayIntShapeBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif  /* __cplusplus */
+
+/**
+ * Clips lines by shapes.
+ * - `lines`: Open paths to clip.
+ * - `contours`: Contours of the shapes.
+ * - `fill_rule`: `I_OVERLAY_FILL_*`.
+ * - `invert`: Keeps the parts outside of the shapes if not zero.
+ * - `boundary_included`: Keeps the parts on the boundary of the shapes if not zero.
+ *
+ * # Safety
+ * `lines` and `contours` must describe readable buffers and `output` must be writable.
+ */
+int32_t i_overlay_clip(IOverlayPaths lines, IOverlayPaths contours, uint32_t fill_rule, bool invert, bool boundary_included, IOverlayPathBuffer *output);
+
+/**
+ * Executes a boolean operation on float contours with `FloatOverlay`.
+ * - `overlay_rule`: `I_OVERLAY_RULE_*`.
+ * - `fill_rule`: `I_OVERLAY_FILL_*`.
+ * - `min_area`: Result contours with a smaller area are dropped.
+ *
+ * # Safety
+ * `subj` and `clip` must describe readable buffers and `output` must be writable.
+ */
+int32_t i_overlay_float_overlay(IOverlayPaths subj, IOverlayPaths clip, uint32_t overlay_rule, uint32_t fill_rule, double min_area, IOverlayShapeBuffer *output);
+
+/**
+ * Releases a buffer returned by the library, the buffer is emptied.
+ *
+ * # Safety
+ * `buffer` must be null or point to a buffer written by the library which is not released yet.
+ */
+void i_overlay_free_int_shapes(IOverlayIntShapeBuffer *buffer);
+
+/**
+ * Releases a buffer returned by the library, the buffer is emptied.
+ *
+ * # Safety
+ * `buffer` must be null or point to a buffer written by the library which is not released yet.
+ */
+void i_overlay_free_paths(IOverlayPathBuffer *buffer);
+
+/**
+ * Releases a buffer returned by the library, the buffer is emptied.
+ *
+ * # Safety
+ * `buffer` must be null or point to a buffer written by the library which is not released yet.
+ */
+void i_overlay_free_shapes(IOverlayShapeBuffer *buffer);
+
+/**
+ * Executes a boolean operation on integer contours with `Overlay`.
+ * - `overlay_rule`: `I_OVERLAY_RULE_*`.
+ * - `fill_rule`: `I_OVERLAY_FILL_*`.
+ *
+ * # Safety
+ * `subj` and `clip` must describe readable buffers and `output` must be writable.
+ */
+int32_t i_overlay_int_overlay(IOverlayIntPaths subj, IOverlayIntPaths clip, uint32_t overlay_rule, uint32_t fill_rule, IOverlayIntShapeBuffer *output);
+
+/**
+ * Offsets shapes, outer contours must be clockwise and holes counterclockwise.
+ * - `contours`: Contours of the shapes.
+ * - `offset`: Offset distance, negative to shrink.
+ * - `join`, `join_parameter`: `I_OVERLAY_JOIN_*` and its angle or ratio, ignored by bevel.
+ *
+ * # Safety
+ * `contours` must describe readable buffers and `output` must be writable.
+ */
+int32_t i_overlay_outline(IOverlayPaths contours, double offset, uint32_t join, double join_parameter, IOverlayShapeBuffer *output);
+
+/**
+ * Slices shapes by lines.
+ * - `contours`: Contours of the shapes.
+ * - `lines`: Open paths which cut the shapes.
+ * - `fill_rule`: `I_OVERLAY_FILL_*`.
+ * - `min_area`: Result contours with a smaller area are dropped.
+ *
+ * # Safety
+ * `contours` and `lines` must describe readable buffers and `output` must be writable.
+ */
+int32_t i_overlay_slice(IOverlayPaths contours, IOverlayPaths lines, uint32_t fill_rule, double min_area, IOverlayShapeBuffer *output);
+
+/**
+ * Strokes paths.
+ * - `paths`: Paths to stroke.
+ * - `width`: Stroke width.
+ * - `cap`, `cap_parameter`: `I_OVERLAY_CAP_*` and its angle, ignored by butt and square.
+ * - `join`, `join_parameter`: `I_OVERLAY_JOIN_*` and its angle or ratio, ignored by bevel.
+ * - `is_closed`: Paths are closed if not zero.
+ *
+ * # Safety
+ * `paths` must describe readable buffers and `output` must be writable.
+ */
+int32_t i_overlay_stroke(IOverlayPaths paths, double width, uint32_t cap, double cap_parameter, uint32_t join, double join_parameter, bool is_closed, IOverlayShapeBuffer *output);
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif  /* __cplusplus */
+
+#endif  /* I_OVERLAY_H */
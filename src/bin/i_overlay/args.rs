@@ -0,0 +1,292 @@
+use std::path::PathBuf;
+use i_overlay::core::fill_rule::FillRule;
+use i_overlay::core::overlay_rule::OverlayRule;
+use i_overlay::core::solver::{Precision, Solver, Strategy};
+use i_overlay::mesh::style::{LineCap, LineJoin};
+use crate::geometry::Format;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Operation {
+    Boolean(OverlayRule),
+    Slice,
+    Clip,
+    Stroke,
+    Outline,
+}
+
+#[derive(Debug)]
+pub(crate) struct Args {
+    pub(crate) operation: Operation,
+    pub(crate) subject: PathBuf,
+    pub(crate) clip: Option<PathBuf>,
+    pub(crate) output: Option<PathBuf>,
+    pub(crate) format: Option<Format>,
+    pub(crate) fill_rule: FillRule,
+    pub(crate) solver: Solver,
+    pub(crate) min_area: f64,
+    pub(crate) invert: bool,
+    pub(crate) boundary_included: bool,
+    pub(crate) width: f64,
+    pub(crate) offset: f64,
+    pub(crate) start_cap: LineCap<[f64; 2], f64>,
+    pub(crate) end_cap: LineCap<[f64; 2], f64>,
+    pub(crate) join: LineJoin<f64>,
+    pub(crate) is_closed: bool,
+    pub(crate) tolerance: f64,
+}
+
+pub(crate) const USAGE: &str = "\
+usage: i_overlay <operation> [options] <subject> [<clip>]
+
+operations:
+  union, intersect, difference, inverse-difference, xor
+                      boolean operation of subject and clip shapes
+  slice               slice subject shapes by clip lines
+  clip                clip subject lines by clip shapes
+  stroke              stroke subject lines
+  outline             offset subject shapes
+
+A json test file of tests/boolean or tests/string holds both the subject and the clip.
+
+options:
+  -o, --output <file>       write the result into a file instead of stdout
+  -f, --format <format>     output format: wkt, geojson, svg or json, the subject format by default
+  --fill <rule>             evenodd, nonzero, positive or negative, default nonzero
+  --strategy <strategy>     auto, list, tree or frag, default auto
+  --precision <precision>   absolute, high, medium-high, medium, medium-low or low, default high
+  --single-thread           do not use multithreading
+  --min-area <area>         drop result contours with a smaller area
+  --invert                  clip: keep the lines outside of the shapes
+  --boundary                clip: keep the lines on the boundary of the shapes
+  --width <width>           stroke: line width, default 1
+  --offset <offset>         outline: offset, negative to shrink, default 1
+  --cap <cap>               stroke: butt, square or round[:angle], default butt
+  --join <join>             stroke, outline: bevel, miter[:angle] or round[:ratio], default bevel
+  --closed                  stroke: lines are closed
+  --tolerance <tolerance>   svg: flattening tolerance of curves, default 0.1
+  -h, --help                print this help";
+
+impl Args {
+    /// Parses arguments without the program name.
+    pub(crate) fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut iter = args.into_iter();
+        let operation = match iter.next() {
+            Some(name) => parse_operation(&name)?,
+            None => return Err("an operation is expected".to_string()),
+        };
+
+        let mut files = Vec::new();
+        let mut output = None;
+        let mut format = None;
+        let mut fill_rule = FillRule::NonZero;
+        let mut solver = Solver::AUTO;
+        let mut min_area = 0.0;
+        let mut invert = false;
+        let mut boundary_included = false;
+        let mut width = 1.0;
+        let mut offset = 1.0;
+        let mut start_cap = LineCap::Butt;
+        let mut end_cap = LineCap::Butt;
+        let mut join = LineJoin::Bevel;
+        let mut is_closed = false;
+        let mut tolerance = 0.1;
+
+        while let Some(arg) = iter.next() {
+            let mut value = |name: &str| iter.next().ok_or(format!("{} requires a value", name));
+            match arg.as_str() {
+                "-o" | "--output" => output = Some(PathBuf::from(value(&arg)?)),
+                "-f" | "--format" => format = Some(parse_format(&value(&arg)?)?),
+                "--fill" => fill_rule = parse_fill_rule(&value(&arg)?)?,
+                "--strategy" => solver.strategy = parse_strategy(&value(&arg)?)?,
+                "--precision" => solver.precision = parse_precision(&value(&arg)?)?,
+                "--single-thread" => solver.multithreading = None,
+                "--min-area" => min_area = parse_number(&arg, &value(&arg)?)?,
+                "--invert" => invert = true,
+                "--boundary" => boundary_included = true,
+                "--width" => width = parse_number(&arg, &value(&arg)?)?,
+                "--offset" => offset = parse_number(&arg, &value(&arg)?)?,
+                "--cap" => {
+                    // caps are not cloneable
+                    let text = value(&arg)?;
+                    start_cap = parse_cap(&text)?;
+                    end_cap = parse_cap(&text)?;
+                }
+                "--join" => join = parse_join(&value(&arg)?)?,
+                "--closed" => is_closed = true,
+                "--tolerance" => tolerance = parse_number(&arg, &value(&arg)?)?,
+                _ if arg.starts_with('-') && arg.len() > 1 => return Err(format!("unknown option {}", arg)),
+                _ => files.push(PathBuf::from(arg)),
+            }
+        }
+
+        let mut files = files.into_iter();
+        let subject = files.next().ok_or("a subject file is expected")?;
+        let clip = files.next();
+        if files.next().is_some() {
+            return Err("too many files".to_string());
+        }
+
+        Ok(Self {
+            operation,
+            subject,
+            clip,
+            output,
+            format,
+            fill_rule,
+            solver,
+            min_area,
+            invert,
+            boundary_included,
+            width,
+            offset,
+            start_cap,
+            end_cap,
+            join,
+            is_closed,
+            tolerance,
+        })
+    }
+}
+
+fn parse_operation(name: &str) -> Result<Operation, String> {
+    let operation = match name {
+        "union" => Operation::Boolean(OverlayRule::Union),
+        "intersect" => Operation::Boolean(OverlayRule::Intersect),
+        "difference" => Operation::Boolean(OverlayRule::Difference),
+        "inverse-difference" => Operation::Boolean(OverlayRule::InverseDifference),
+        "xor" => Operation::Boolean(OverlayRule::Xor),
+        "slice" => Operation::Slice,
+        "clip" => Operation::Clip,
+        "stroke" => Operation::Stroke,
+        "outline" => Operation::Outline,
+        _ => return Err(format!("unknown operation {}", name)),
+    };
+    Ok(operation)
+}
+
+fn parse_format(name: &str) -> Result<Format, String> {
+    Format::with_name(name).ok_or(format!("unknown format {}", name))
+}
+
+fn parse_fill_rule(name: &str) -> Result<FillRule, String> {
+    let fill_rule = match name {
+        "evenodd" => FillRule::EvenOdd,
+        "nonzero" => FillRule::NonZero,
+        "positive" => FillRule::Positive,
+        "negative" => FillRule::Negative,
+        _ => return Err(format!("unknown fill rule {}", name)),
+    };
+    Ok(fill_rule)
+}
+
+fn parse_strategy(name: &str) -> Result<Strategy, String> {
+    let strategy = match name {
+        "auto" => Strategy::Auto,
+        "list" => Strategy::List,
+        "tree" => Strategy::Tree,
+        "frag" => Strategy::Frag,
+        _ => return Err(format!("unknown strategy {}", name)),
+    };
+    Ok(strategy)
+}
+
+fn parse_precision(name: &str) -> Result<Precision, String> {
+    let precision = match name {
+        "absolute" => Precision::ABSOLUTE,
+        "high" => Precision::HIGH,
+        "medium-high" => Precision::MEDIUM_HIGH,
+        "medium" => Precision::MEDIUM,
+        "medium-low" => Precision::MEDIUM_LOW,
+        "low" => Precision::LOW,
+        _ => return Err(format!("unknown precision {}", name)),
+    };
+    Ok(precision)
+}
+
+fn parse_cap(text: &str) -> Result<LineCap<[f64; 2], f64>, String> {
+    let (name, value) = split_value(text);
+    let cap = match (name, value) {
+        ("butt", None) => LineCap::Butt,
+        ("square", None) => LineCap::Square,
+        ("round", None) => LineCap::Round(0.1),
+        ("round", Some(value)) => LineCap::Round(parse_number("round", value)?),
+        _ => return Err(format!("unknown cap {}", text)),
+    };
+    Ok(cap)
+}
+
+fn parse_join(text: &str) -> Result<LineJoin<f64>, String> {
+    let (name, value) = split_value(text);
+    let join = match (name, value) {
+        ("bevel", None) => LineJoin::Bevel,
+        ("miter", None) => LineJoin::Miter(0.1),
+        ("miter", Some(value)) => LineJoin::Miter(parse_number("miter", value)?),
+        ("round", None) => LineJoin::Round(0.1),
+        ("round", Some(value)) => LineJoin::Round(parse_number("round", value)?),
+        _ => return Err(format!("unknown join {}", text)),
+    };
+    Ok(join)
+}
+
+#[inline]
+fn split_value(text: &str) -> (&str, Option<&str>) {
+    match text.split_once(':') {
+        Some((name, value)) => (name, Some(value)),
+        None => (text, None),
+    }
+}
+
+fn parse_number(name: &str, text: &str) -> Result<f64, String> {
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(format!("{} expects a number, found {}", name, text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::solver::Strategy;
+    use i_overlay::mesh::style::LineJoin;
+    use crate::args::{Args, Operation};
+    use crate::geometry::Format;
+
+    fn parse(line: &str) -> Result<Args, String> {
+        Args::parse(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_boolean() {
+        let args = parse("difference a.wkt b.wkt --fill evenodd --strategy tree --single-thread -f svg").unwrap();
+
+        assert_eq!(args.operation, Operation::Boolean(OverlayRule::Difference));
+        assert_eq!(args.subject.to_str(), Some("a.wkt"));
+        assert_eq!(args.clip.unwrap().to_str(), Some("b.wkt"));
+        assert_eq!(args.fill_rule, FillRule::EvenOdd);
+        assert_eq!(args.solver.strategy, Strategy::Tree);
+        assert!(args.solver.multithreading.is_none());
+        assert_eq!(args.format, Some(Format::Svg));
+    }
+
+    #[test]
+    fn test_stroke() {
+        let args = parse("stroke --width 2.5 --join miter:0.3 --closed lines.geojson").unwrap();
+
+        assert_eq!(args.operation, Operation::Stroke);
+        assert_eq!(args.width, 2.5);
+        assert!(matches!(args.join, LineJoin::Miter(angle) if angle == 0.3));
+        assert!(args.is_closed);
+        assert!(args.clip.is_none());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse("").unwrap_err(), "an operation is expected");
+        assert_eq!(parse("merge a.wkt").unwrap_err(), "unknown operation merge");
+        assert_eq!(parse("union").unwrap_err(), "a subject file is expected");
+        assert_eq!(parse("union a.wkt --fill").unwrap_err(), "--fill requires a value");
+        assert_eq!(parse("outline a.wkt --offset x").unwrap_err(), "--offset expects a number, found x");
+        assert_eq!(parse("union a b c").unwrap_err(), "too many files");
+    }
+}
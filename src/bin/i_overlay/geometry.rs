@@ -0,0 +1,266 @@
+use std::path::Path;
+use geojson::GeoJson;
+use i_overlay::core::fill_rule::FillRule;
+use i_overlay::format::{geojson as geo, svg, wkt, Winding};
+use i_overlay::i_shape::base::data::{Contour, Paths, Shapes};
+use i_overlay::i_shape::int::shape::IntContour;
+use i_overlay::record::Record;
+
+pub(crate) type Point = [f64; 2];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Wkt,
+    GeoJson,
+    Svg,
+    Json,
+}
+
+impl Format {
+    pub(crate) fn with_name(name: &str) -> Option<Self> {
+        match name {
+            "wkt" => Some(Format::Wkt),
+            "geojson" => Some(Format::GeoJson),
+            "svg" => Some(Format::Svg),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn with_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        Self::with_name(&extension)
+    }
+}
+
+/// The geometry of a file: filled shapes and open lines.
+/// A json test file also holds the geometry of the clip.
+#[derive(Debug, Default)]
+pub(crate) struct Geometry {
+    pub(crate) format: Option<Format>,
+    pub(crate) shapes: Shapes<Point>,
+    pub(crate) paths: Paths<Point>,
+    pub(crate) clip: Option<Box<Geometry>>,
+}
+
+impl Geometry {
+    pub(crate) fn load(path: &Path, tolerance: f64) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        let format = Format::with_path(path).unwrap_or(Format::Wkt);
+        let geometry = match format {
+            Format::Wkt => Self::with_wkt(&text),
+            Format::GeoJson => Self::with_geojson(&text),
+            Format::Svg => Self::with_svg(&text, tolerance),
+            // json is either a test case or GeoJSON
+            Format::Json => Self::with_record(&text).or_else(|_| Self::with_geojson(&text)),
+        };
+
+        geometry.map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    fn with_wkt(text: &str) -> Result<Self, String> {
+        let format = Some(Format::Wkt);
+        if let Ok(shapes) = wkt::read_shapes(text) {
+            return Ok(Self { format, shapes, ..Default::default() });
+        }
+        let paths = wkt::read_paths(text).map_err(|error| error.to_string())?;
+        Ok(Self { format, paths, ..Default::default() })
+    }
+
+    fn with_geojson(text: &str) -> Result<Self, String> {
+        let geojson = text.parse::<GeoJson>().map_err(|error| error.to_string())?;
+        let mut geometries = Vec::new();
+        match geojson {
+            GeoJson::Geometry(geometry) => geometries.push(geometry),
+            GeoJson::Feature(feature) => geometries.extend(feature.geometry),
+            GeoJson::FeatureCollection(collection) => {
+                geometries.extend(collection.features.into_iter().filter_map(|feature| feature.geometry))
+            }
+        }
+
+        let mut result = Self { format: Some(Format::GeoJson), ..Default::default() };
+        while let Some(geometry) = geometries.pop() {
+            match geometry.value {
+                geojson::Value::GeometryCollection(collection) => geometries.extend(collection),
+                geojson::Value::Polygon(_) | geojson::Value::MultiPolygon(_) => {
+                    result.shapes.extend(geo::read_shapes(&geometry).map_err(|error| error.to_string())?)
+                }
+                geojson::Value::LineString(_) | geojson::Value::MultiLineString(_) => {
+                    result.paths.extend(geo::read_paths(&geometry).map_err(|error| error.to_string())?)
+                }
+                // points have no edges
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+
+    // Path data is taken from all `d` attributes, closed subpaths are shapes and open ones are lines.
+    fn with_svg(text: &str, tolerance: f64) -> Result<Self, String> {
+        let mut result = Self { format: Some(Format::Svg), ..Default::default() };
+        let mut rest = text;
+        while let Some(start) = find_attribute(rest, "d") {
+            let quote = rest[start..].chars().next().unwrap_or('"');
+            let data_start = start + 1;
+            let data_end = rest[data_start..].find(quote).ok_or("unclosed attribute")? + data_start;
+            for mut path in svg::read_paths::<Point, f64>(&rest[data_start..data_end], tolerance).map_err(|error| error.to_string())? {
+                if path.len() > 3 && path.first() == path.last() {
+                    path.pop();
+                    result.shapes.push(vec![path]);
+                } else {
+                    result.paths.push(path);
+                }
+            }
+            rest = &rest[data_end..];
+        }
+
+        Ok(result)
+    }
+
+    fn with_record(text: &str) -> Result<Self, String> {
+        let format = Some(Format::Json);
+        let geometry = match Record::from_json(text).map_err(|error| error.to_string())? {
+            Record::Overlay(record) => Self {
+                format,
+                shapes: vec![float_contours(&record.subj_paths)],
+                clip: Some(Box::new(Self { format, shapes: vec![float_contours(&record.clip_paths)], ..Default::default() })),
+                ..Default::default()
+            },
+            Record::String(record) => Self {
+                format,
+                shapes: vec![float_contours(&record.body)],
+                clip: Some(Box::new(Self { format, paths: float_contours(&record.string), ..Default::default() })),
+                ..Default::default()
+            },
+        };
+
+        Ok(geometry)
+    }
+
+    /// All shape contours, lines are taken as contours too.
+    pub(crate) fn contours(&self) -> Vec<Contour<Point>> {
+        self.shapes.iter().flatten().chain(self.paths.iter()).cloned().collect()
+    }
+
+    /// All lines, shape contours are taken as closed lines.
+    pub(crate) fn lines(&self) -> Paths<Point> {
+        let mut lines: Paths<Point> = self.paths.clone();
+        for contour in self.shapes.iter().flatten() {
+            let mut line = contour.clone();
+            if let Some(&first) = contour.first() {
+                line.push(first);
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    pub(crate) fn edges_count(&self) -> usize {
+        shape_edges_count(&self.shapes) + path_edges_count(&self.paths)
+    }
+}
+
+pub(crate) fn write_shapes(shapes: &Shapes<Point>, format: Format, fill_rule: FillRule) -> String {
+    match format {
+        Format::Wkt => wkt::write_shapes(shapes, Winding::default()),
+        Format::GeoJson => geo::write_shapes(shapes).to_string(),
+        Format::Svg => svg_document(&svg::write_shapes(shapes, fill_rule), shapes.iter().flatten().flatten()),
+        Format::Json => serde_json::to_string(shapes).unwrap_or_default(),
+    }
+}
+
+pub(crate) fn write_paths(paths: &Paths<Point>, format: Format) -> String {
+    match format {
+        Format::Wkt => wkt::write_paths(paths),
+        Format::GeoJson => geo::write_paths(paths).to_string(),
+        Format::Svg => svg_document(&svg::write_paths(paths), paths.iter().flatten()),
+        Format::Json => serde_json::to_string(paths).unwrap_or_default(),
+    }
+}
+
+#[inline]
+pub(crate) fn shape_edges_count(shapes: &Shapes<Point>) -> usize {
+    shapes.iter().flatten().map(|contour| contour.len()).sum()
+}
+
+#[inline]
+pub(crate) fn path_edges_count(paths: &Paths<Point>) -> usize {
+    paths.iter().map(|path| path.len().saturating_sub(1)).sum()
+}
+
+fn svg_document<'a, I: Iterator<Item = &'a Point>>(elements: &str, points: I) -> String {
+    let mut min = [f64::MAX, f64::MAX];
+    let mut max = [f64::MIN, f64::MIN];
+    for p in points {
+        min = [min[0].min(p[0]), min[1].min(p[1])];
+        max = [max[0].max(p[0]), max[1].max(p[1])];
+    }
+    if min[0] > max[0] {
+        min = [0.0, 0.0];
+        max = [0.0, 0.0];
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n{}</svg>\n",
+        min[0], min[1], max[0] - min[0], max[1] - min[1], elements
+    )
+}
+
+// Returns the position of the opening quote of the attribute value.
+fn find_attribute(text: &str, name: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut from = 0;
+    while let Some(index) = text[from..].find(name).map(|index| index + from) {
+        from = index + name.len();
+        let is_separated = index > 0 && bytes[index - 1].is_ascii_whitespace();
+        let rest = text[from..].trim_start();
+        if is_separated && rest.starts_with('=') {
+            let value = rest[1..].trim_start();
+            if value.starts_with('"') || value.starts_with('\'') {
+                return Some(text.len() - value.len());
+            }
+        }
+    }
+    None
+}
+
+#[inline]
+fn float_contours(contours: &[IntContour]) -> Vec<Contour<Point>> {
+    contours
+        .iter()
+        .map(|contour| contour.iter().map(|p| [p.x as f64, p.y as f64]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use i_overlay::core::fill_rule::FillRule;
+    use crate::geometry::{write_shapes, Format, Geometry};
+
+    #[test]
+    fn test_svg() {
+        let text = "<svg><path fill='red' d='M0 0 L0 4 L4 4 L4 0 Z'/><path id=\"d\" d=\"M-1 2 L5 2\"/></svg>";
+        let geometry = Geometry::with_svg(text, 0.1).unwrap();
+
+        assert_eq!(geometry.shapes, vec![vec![vec![[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [4.0, 0.0]]]]);
+        assert_eq!(geometry.paths, vec![vec![[-1.0, 2.0], [5.0, 2.0]]]);
+        assert_eq!(geometry.edges_count(), 5);
+
+        let document = write_shapes(&geometry.shapes, Format::Svg, FillRule::NonZero);
+        assert!(document.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 4 4\">"));
+    }
+
+    #[test]
+    fn test_geojson() {
+        let text = r#"{"type":"FeatureCollection","features":[
+            {"type":"Feature","properties":{},"geometry":{"type":"LineString","coordinates":[[0,0],[1,1]]}},
+            {"type":"Feature","properties":{},"geometry":{"type":"Polygon","coordinates":[[[0,0],[1,0],[1,1],[0,0]]]}}
+        ]}"#;
+        let geometry = Geometry::with_geojson(text).unwrap();
+
+        assert_eq!(geometry.shapes.len(), 1);
+        assert_eq!(geometry.paths.len(), 1);
+        assert_eq!(geometry.lines().len(), 2);
+    }
+}
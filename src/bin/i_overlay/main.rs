@@ -0,0 +1,113 @@
+//! A command-line tool to run overlay operations on WKT, GeoJSON, SVG and json test files.
+//! Timing and edge counts are printed to stderr, run `i_overlay --help` for the options.
+
+mod args;
+mod geometry;
+
+use std::io::Write;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use i_overlay::float::clip::FloatClip;
+use i_overlay::float::filter::ContourFilter;
+use i_overlay::float::overlay::FloatOverlay;
+use i_overlay::float::slice::FloatSlice;
+use i_overlay::mesh::outline::offset::OutlineOffset;
+use i_overlay::mesh::stroke::offset::StrokeOffset;
+use i_overlay::mesh::style::{OutlineStyle, StrokeStyle};
+use i_overlay::i_shape::base::data::{Paths, Shapes};
+use i_overlay::string::clip::ClipRule;
+use crate::args::{Args, Operation, USAGE};
+use crate::geometry::{path_edges_count, shape_edges_count, write_paths, write_shapes, Format, Geometry, Point};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let result = Args::parse(args).and_then(run);
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let start = Instant::now();
+    let mut subject = Geometry::load(&args.subject, args.tolerance)?;
+    let clip = match &args.clip {
+        Some(path) => Geometry::load(path, args.tolerance)?,
+        None => subject.clip.take().map(|clip| *clip).unwrap_or_default(),
+    };
+    let read_time = start.elapsed();
+
+    let format = args.format.or(subject.format).unwrap_or(Format::Wkt);
+    let filter = ContourFilter { min_area: args.min_area, simplify: true };
+
+    let start = Instant::now();
+    let (output, edges_count) = match args.operation {
+        Operation::Boolean(overlay_rule) => {
+            let shapes = FloatOverlay::with_subj_and_clip(&subject.contours(), &clip.contours())
+                .overlay_with_filter_and_solver(overlay_rule, args.fill_rule, filter, args.solver);
+            (Output::Shapes(shapes), None)
+        }
+        Operation::Slice => {
+            let shapes = subject
+                .contours()
+                .slice_by_with_filter_and_solver(&clip.lines(), args.fill_rule, filter, args.solver);
+            (Output::Shapes(shapes), None)
+        }
+        Operation::Clip => {
+            let clip_rule = ClipRule { invert: args.invert, boundary_included: args.boundary_included };
+            let paths = subject
+                .lines()
+                .clip_by_with_solver(&clip.contours(), args.fill_rule, clip_rule, args.solver);
+            (Output::Paths(paths), None)
+        }
+        Operation::Stroke => {
+            let style = StrokeStyle::new(args.width)
+                .start_cap(args.start_cap)
+                .end_cap(args.end_cap)
+                .line_join(args.join);
+            let shapes = subject.lines().stroke_with_filter(style, args.is_closed, filter);
+            (Output::Shapes(shapes), Some(path_edges_count(&subject.lines())))
+        }
+        Operation::Outline => {
+            let style = OutlineStyle::new(args.offset).line_join(args.join);
+            let shapes = subject.shapes.outline_with_filter(style, filter);
+            (Output::Shapes(shapes), Some(shape_edges_count(&subject.shapes)))
+        }
+    };
+    let operation_time = start.elapsed();
+
+    let start = Instant::now();
+    let (text, output_edges_count) = match &output {
+        Output::Shapes(shapes) => (write_shapes(shapes, format, args.fill_rule), shape_edges_count(shapes)),
+        Output::Paths(paths) => (write_paths(paths, format), path_edges_count(paths)),
+    };
+    match &args.output {
+        Some(path) => std::fs::write(path, text).map_err(|error| format!("{}: {}", path.display(), error))?,
+        None => writeln!(std::io::stdout(), "{}", text).map_err(|error| error.to_string())?,
+    }
+    let write_time = start.elapsed();
+
+    let input_edges_count = edges_count.unwrap_or(subject.edges_count() + clip.edges_count());
+    eprintln!("edges: {} in, {} out", input_edges_count, output_edges_count);
+    eprintln!("time: read {}, operation {}, write {}", millis(read_time), millis(operation_time), millis(write_time));
+
+    Ok(())
+}
+
+enum Output {
+    Shapes(Shapes<Point>),
+    Paths(Paths<Point>),
+}
+
+#[inline]
+fn millis(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
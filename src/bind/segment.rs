@@ -1,7 +1,6 @@
 use std::cmp::Ordering;
 use i_float::int::point::IntPoint;
 use i_key_sort::index::{BinKey, BinLayout};
-use i_shape::int::path::IntPath;
 use crate::geom::x_segment::XSegment;
 use crate::vector::edge::VectorPath;
 
@@ -54,7 +53,7 @@ pub(crate) trait IdSegments {
     fn append_id_segments(&self, buffer: &mut Vec<IdSegment>, id: usize, x_min: i32, x_max: i32);
 }
 
-impl IdSegments for IntPath {
+impl IdSegments for [IntPoint] {
     fn append_hole_segments(&self, buffer: &mut Vec<IdSegment>, index: usize, x_min: i32, x_max: i32) {
         let id= (index << 1) | 1;
         self.append_id_segments(buffer, id, x_min, x_max);
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use i_float::int::point::IntPoint;
 use i_shape::int::path::IntPath;
 use i_shape::int::shape::{IntContour, IntShape};
 use crate::bind::segment::{IdSegment, IdSegments};
@@ -22,7 +23,7 @@ pub(crate) trait ScanHoleStore {
 
 impl ShapeBinder {
     #[inline]
-    pub(crate) fn bind(shape_count: usize, hole_segments: Vec<IdSegment>, segments: Vec<IdSegment>) -> BindSolution {
+    pub(crate) fn bind(shape_count: usize, hole_segments: &[IdSegment], segments: Vec<IdSegment>) -> BindSolution {
         if shape_count < 128 {
             let scan_list = ScanHoleList::new(segments.len());
             Self::private_solve(scan_list, shape_count, hole_segments, segments)
@@ -32,7 +33,7 @@ impl ShapeBinder {
         }
     }
 
-    fn private_solve<S: ScanHoleStore>(scan_store: S, shape_count: usize, anchors: Vec<IdSegment>, segments: Vec<IdSegment>) -> BindSolution {
+    fn private_solve<S: ScanHoleStore>(scan_store: S, shape_count: usize, anchors: &[IdSegment], segments: Vec<IdSegment>) -> BindSolution {
         let children_count = anchors.len();
         let mut scan_store = scan_store;
 
@@ -129,31 +130,49 @@ impl JoinHoles for Vec<IntShape> {
     }
 
     fn scan_join(&mut self, solver: &Solver, holes: Vec<IntPath>, hole_segments: Vec<IdSegment>) {
+        let solution = ShapeBinder::bind_contours(
+            solver,
+            self.iter().map(|shape| shape[0].as_slice()),
+            holes.iter().map(|hole| hole.as_slice()),
+            &hole_segments,
+        );
+
+        for (shape_index, &capacity) in solution.children_count_for_parent.iter().enumerate() {
+            self[shape_index].reserve(capacity);
+        }
+
+        for (hole_index, hole) in holes.into_iter().enumerate() {
+            let shape_index = solution.parent_for_child[hole_index];
+            self[shape_index].push(hole);
+        }
+    }
+}
+
+impl ShapeBinder {
+    /// Finds the parent shape of every hole, `hole_segments` must be sorted by `a` and then by angle.
+    pub(crate) fn bind_contours<'a, S, H>(solver: &Solver, shapes: S, holes: H, hole_segments: &[IdSegment]) -> BindSolution
+    where
+        S: Iterator<Item = &'a [IntPoint]> + Clone,
+        H: Iterator<Item = &'a [IntPoint]>,
+    {
         let x_min = hole_segments[0].x_segment.a.x;
         let x_max = hole_segments[hole_segments.len() - 1].x_segment.a.x;
 
-        let capacity = self.iter().fold(0, |s, it| s + it[0].len()) / 2;
+        let capacity = shapes.clone().fold(0, |s, it| s + it.len()) / 2;
         let mut segments = Vec::with_capacity(capacity);
-        for (i, shape) in self.iter().enumerate() {
-            shape[0].append_hull_segments(&mut segments, i, x_min, x_max);
+        let mut shape_count = 0;
+        for (i, contour) in shapes.enumerate() {
+            contour.append_hull_segments(&mut segments, i, x_min, x_max);
+            shape_count += 1;
         }
 
-        for (i, hole) in holes.iter().enumerate() {
+        for (i, hole) in holes.enumerate() {
             hole.append_hole_segments(&mut segments, i, x_min, x_max);
         }
 
         segments.sort_by_a_then_by_angle(solver);
 
-        let solution = ShapeBinder::bind(self.len(), hole_segments, segments);
-
-        for (shape_index, &capacity) in solution.children_count_for_parent.iter().enumerate() {
-            self[shape_index].reserve(capacity);
-        }
-
-        for (hole_index, hole) in holes.into_iter().enumerate() {
-            let shape_index = solution.parent_for_child[hole_index];
-            self[shape_index].push(hole);
-        }
+        Self::bind(shape_count, hole_segments, segments)
     }
 }
 
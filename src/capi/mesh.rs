@@ -0,0 +1,99 @@
+use crate::capi;
+use crate::capi::{execute, finite, IOverlayPaths, IOverlayShapeBuffer};
+use crate::mesh::outline::offset::OutlineOffset;
+use crate::mesh::stroke::offset::StrokeOffset;
+use crate::mesh::style::{OutlineStyle, StrokeStyle};
+
+/// Offsets shapes, outer contours must be clockwise and holes counterclockwise.
+/// - `contours`: Contours of the shapes.
+/// - `offset`: Offset distance, negative to shrink.
+/// - `join`, `join_parameter`: `I_OVERLAY_JOIN_*` and its angle or ratio, ignored by bevel.
+///
+/// # Safety
+/// `contours` must describe readable buffers and `output` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_outline(
+    contours: IOverlayPaths,
+    offset: f64,
+    join: u32,
+    join_parameter: f64,
+    output: *mut IOverlayShapeBuffer,
+) -> i32 {
+    unsafe {
+        execute(output, || {
+            let style = OutlineStyle::new(finite(offset)?).line_join(capi::line_join(join, join_parameter)?);
+            let contours = contours.to_paths()?;
+
+            let shapes = contours.outline(style);
+            Ok(IOverlayShapeBuffer::new(&shapes))
+        })
+    }
+}
+
+/// Strokes paths.
+/// - `paths`: Paths to stroke.
+/// - `width`: Stroke width.
+/// - `cap`, `cap_parameter`: `I_OVERLAY_CAP_*` and its angle, ignored by butt and square.
+/// - `join`, `join_parameter`: `I_OVERLAY_JOIN_*` and its angle or ratio, ignored by bevel.
+/// - `is_closed`: Paths are closed if not zero.
+///
+/// # Safety
+/// `paths` must describe readable buffers and `output` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_stroke(
+    paths: IOverlayPaths,
+    width: f64,
+    cap: u32,
+    cap_parameter: f64,
+    join: u32,
+    join_parameter: f64,
+    is_closed: bool,
+    output: *mut IOverlayShapeBuffer,
+) -> i32 {
+    unsafe {
+        execute(output, || {
+            let style = StrokeStyle::new(finite(width)?)
+                .start_cap(capi::line_cap(cap, cap_parameter)?)
+                .end_cap(capi::line_cap(cap, cap_parameter)?)
+                .line_join(capi::line_join(join, join_parameter)?);
+            let paths = paths.to_paths()?;
+
+            let shapes = paths.stroke(style, is_closed);
+            Ok(IOverlayShapeBuffer::new(&shapes))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::capi::{i_overlay_free_shapes, IOverlayPaths, IOverlayShapeBuffer, I_OVERLAY_ERROR_ARGUMENT, I_OVERLAY_OK};
+    use crate::capi::mesh::{i_overlay_outline, i_overlay_stroke};
+
+    #[test]
+    fn test_outline() {
+        let points = [0.0, 0.0, 0.0, 4.0, 4.0, 4.0, 4.0, 0.0];
+        let contours = IOverlayPaths { points: points.as_ptr(), offsets: [0, 4].as_ptr(), count: 1 };
+
+        let mut buffer = IOverlayShapeBuffer::default();
+        assert_eq!(unsafe { i_overlay_outline(contours, 1.0, 1, 0.1, &mut buffer) }, I_OVERLAY_OK);
+        assert_eq!(buffer.shapes_count, 1);
+        assert_eq!(buffer.points_count, 4);
+        let points = unsafe { std::slice::from_raw_parts(buffer.points, 8) };
+        assert!(points.iter().all(|&v| v == -1.0 || v == 5.0));
+        unsafe { i_overlay_free_shapes(&mut buffer) };
+
+        assert_eq!(unsafe { i_overlay_outline(contours, 1.0, 3, 0.1, &mut buffer) }, I_OVERLAY_ERROR_ARGUMENT);
+    }
+
+    #[test]
+    fn test_stroke() {
+        let points = [0.0, 0.0, 10.0, 0.0];
+        let paths = IOverlayPaths { points: points.as_ptr(), offsets: [0, 2].as_ptr(), count: 1 };
+
+        let mut buffer = IOverlayShapeBuffer::default();
+        assert_eq!(unsafe { i_overlay_stroke(paths, 2.0, 0, 0.0, 0, 0.0, false, &mut buffer) }, I_OVERLAY_OK);
+        assert_eq!(buffer.shapes_count, 1);
+        assert_eq!(buffer.points_count, 4);
+        unsafe { i_overlay_free_shapes(&mut buffer) };
+    }
+}
@@ -0,0 +1,464 @@
+//! C ABI of the overlay, declared in `include/i_overlay.h`.
+//!
+//! Geometry is passed in flat buffers: the points of all paths are stored one after another as `x, y` pairs,
+//! and the path `i` consists of the points `offsets[i]..offsets[i + 1]`, so `offsets` has `count + 1` items.
+//! Results are grouped the same way, shapes additionally store offsets into their contours.
+//!
+//! Every function returns a status code and writes its result into an output buffer.
+//! A result buffer is owned by the caller and must be released by the matching `i_overlay_free_*` function.
+//!
+//! The library is built for C with `cargo rustc --release --features capi --crate-type cdylib`
+//! or `--crate-type staticlib`. The header is generated by cbindgen with `./generate_header.sh`,
+//! which uses `cbindgen.toml`, so it must be regenerated after the interface changes.
+
+pub mod overlay;
+pub mod string;
+pub mod mesh;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::null_mut;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::{Paths, Shapes};
+use i_shape::int::path::IntPaths;
+use i_shape::int::shape::IntShapes;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::mesh::style::{LineCap, LineJoin};
+
+/// The call succeeded.
+pub const I_OVERLAY_OK: i32 = 0;
+/// A required pointer is null.
+pub const I_OVERLAY_ERROR_NULL: i32 = 1;
+/// An unknown rule or style, decreasing offsets or a number which is not finite.
+pub const I_OVERLAY_ERROR_ARGUMENT: i32 = 2;
+/// The operation failed unexpectedly.
+pub const I_OVERLAY_ERROR_INTERNAL: i32 = 3;
+
+/// `OverlayRule` values.
+pub const I_OVERLAY_RULE_SUBJECT: u32 = 0;
+pub const I_OVERLAY_RULE_CLIP: u32 = 1;
+pub const I_OVERLAY_RULE_INTERSECT: u32 = 2;
+pub const I_OVERLAY_RULE_UNION: u32 = 3;
+pub const I_OVERLAY_RULE_DIFFERENCE: u32 = 4;
+pub const I_OVERLAY_RULE_INVERSE_DIFFERENCE: u32 = 5;
+pub const I_OVERLAY_RULE_XOR: u32 = 6;
+
+/// `FillRule` values.
+pub const I_OVERLAY_FILL_EVEN_ODD: u32 = 0;
+pub const I_OVERLAY_FILL_NON_ZERO: u32 = 1;
+pub const I_OVERLAY_FILL_POSITIVE: u32 = 2;
+pub const I_OVERLAY_FILL_NEGATIVE: u32 = 3;
+
+/// `LineJoin` values.
+pub const I_OVERLAY_JOIN_BEVEL: u32 = 0;
+pub const I_OVERLAY_JOIN_MITER: u32 = 1;
+pub const I_OVERLAY_JOIN_ROUND: u32 = 2;
+
+/// `LineCap` values.
+pub const I_OVERLAY_CAP_BUTT: u32 = 0;
+pub const I_OVERLAY_CAP_SQUARE: u32 = 1;
+pub const I_OVERLAY_CAP_ROUND: u32 = 2;
+
+/// Paths with `f64` coordinates.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct IOverlayPaths {
+    /// `x, y` pairs of all points.
+    pub points: *const f64,
+    /// `count + 1` point offsets of the paths.
+    pub offsets: *const usize,
+    pub count: usize,
+}
+
+/// Paths with `i32` coordinates.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct IOverlayIntPaths {
+    /// `x, y` pairs of all points.
+    pub points: *const i32,
+    /// `count + 1` point offsets of the paths.
+    pub offsets: *const usize,
+    pub count: usize,
+}
+
+/// Result paths with `f64` coordinates, released by `i_overlay_free_paths`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct IOverlayPathBuffer {
+    /// `x, y` pairs of all points.
+    pub points: *mut f64,
+    pub points_count: usize,
+    /// `paths_count + 1` point offsets of the paths.
+    pub paths: *mut usize,
+    pub paths_count: usize,
+}
+
+/// Result shapes with `f64` coordinates, released by `i_overlay_free_shapes`.
+///
+/// The first contour of a shape is its outer boundary, the others are holes.
+#[repr(C)]
+#[derive(Debug)]
+pub struct IOverlayShapeBuffer {
+    /// `x, y` pairs of all points.
+    pub points: *mut f64,
+    pub points_count: usize,
+    /// `contours_count + 1` point offsets of the contours.
+    pub contours: *mut usize,
+    pub contours_count: usize,
+    /// `shapes_count + 1` contour offsets of the shapes.
+    pub shapes: *mut usize,
+    pub shapes_count: usize,
+}
+
+/// Result shapes with `i32` coordinates, released by `i_overlay_free_int_shapes`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct IOverlayIntShapeBuffer {
+    /// `x, y` pairs of all points.
+    pub points: *mut i32,
+    pub points_count: usize,
+    /// `contours_count + 1` point offsets of the contours.
+    pub contours: *mut usize,
+    pub contours_count: usize,
+    /// `shapes_count + 1` contour offsets of the shapes.
+    pub shapes: *mut usize,
+    pub shapes_count: usize,
+}
+
+/// Releases a buffer returned by the library, the buffer is emptied.
+///
+/// # Safety
+/// `buffer` must be null or point to a buffer written by the library which is not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_free_paths(buffer: *mut IOverlayPathBuffer) {
+    // SAFETY: the caller passes a buffer written by the library
+    let Some(buffer) = (unsafe { buffer.as_mut() }) else { return; };
+    unsafe {
+        free(buffer.points, 2 * buffer.points_count);
+        free(buffer.paths, buffer.paths_count + 1);
+    }
+    *buffer = IOverlayPathBuffer::default();
+}
+
+/// Releases a buffer returned by the library, the buffer is emptied.
+///
+/// # Safety
+/// `buffer` must be null or point to a buffer written by the library which is not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_free_shapes(buffer: *mut IOverlayShapeBuffer) {
+    // SAFETY: the caller passes a buffer written by the library
+    let Some(buffer) = (unsafe { buffer.as_mut() }) else { return; };
+    unsafe {
+        free(buffer.points, 2 * buffer.points_count);
+        free(buffer.contours, buffer.contours_count + 1);
+        free(buffer.shapes, buffer.shapes_count + 1);
+    }
+    *buffer = IOverlayShapeBuffer::default();
+}
+
+/// Releases a buffer returned by the library, the buffer is emptied.
+///
+/// # Safety
+/// `buffer` must be null or point to a buffer written by the library which is not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_free_int_shapes(buffer: *mut IOverlayIntShapeBuffer) {
+    // SAFETY: the caller passes a buffer written by the library
+    let Some(buffer) = (unsafe { buffer.as_mut() }) else { return; };
+    unsafe {
+        free(buffer.points, 2 * buffer.points_count);
+        free(buffer.contours, buffer.contours_count + 1);
+        free(buffer.shapes, buffer.shapes_count + 1);
+    }
+    *buffer = IOverlayIntShapeBuffer::default();
+}
+
+impl Default for IOverlayPathBuffer {
+    fn default() -> Self {
+        Self { points: null_mut(), points_count: 0, paths: null_mut(), paths_count: 0 }
+    }
+}
+
+impl Default for IOverlayShapeBuffer {
+    fn default() -> Self {
+        Self { points: null_mut(), points_count: 0, contours: null_mut(), contours_count: 0, shapes: null_mut(), shapes_count: 0 }
+    }
+}
+
+impl Default for IOverlayIntShapeBuffer {
+    fn default() -> Self {
+        Self { points: null_mut(), points_count: 0, contours: null_mut(), contours_count: 0, shapes: null_mut(), shapes_count: 0 }
+    }
+}
+
+impl IOverlayPaths {
+    pub(crate) unsafe fn to_paths(self) -> Result<Paths<[f64; 2]>, i32> {
+        let (coords, offsets) = unsafe { flat_slices(self.points, self.offsets, self.count)? };
+        if coords.iter().any(|value| !value.is_finite()) {
+            return Err(I_OVERLAY_ERROR_ARGUMENT);
+        }
+        Ok(split(offsets, |i| [coords[2 * i], coords[2 * i + 1]]))
+    }
+}
+
+impl IOverlayIntPaths {
+    pub(crate) unsafe fn to_paths(self) -> Result<IntPaths, i32> {
+        let (coords, offsets) = unsafe { flat_slices(self.points, self.offsets, self.count)? };
+        Ok(split(offsets, |i| IntPoint::new(coords[2 * i], coords[2 * i + 1])))
+    }
+}
+
+impl IOverlayPathBuffer {
+    pub(crate) fn new(paths: &Paths<[f64; 2]>) -> Self {
+        let mut points = Vec::new();
+        let mut offsets = vec![0];
+        for path in paths.iter() {
+            points.extend(path.iter().flatten());
+            offsets.push(points.len() / 2);
+        }
+
+        Self {
+            points_count: points.len() / 2,
+            points: into_raw(points),
+            paths_count: paths.len(),
+            paths: into_raw(offsets),
+        }
+    }
+}
+
+impl IOverlayShapeBuffer {
+    pub(crate) fn new(shapes: &Shapes<[f64; 2]>) -> Self {
+        let (points, contours, offsets) = flatten_shapes(shapes, |p| *p);
+
+        Self {
+            points_count: points.len() / 2,
+            points: into_raw(points),
+            contours_count: contours.len() - 1,
+            contours: into_raw(contours),
+            shapes_count: shapes.len(),
+            shapes: into_raw(offsets),
+        }
+    }
+}
+
+impl IOverlayIntShapeBuffer {
+    pub(crate) fn new(shapes: &IntShapes) -> Self {
+        let (points, contours, offsets) = flatten_shapes(shapes, |p| [p.x, p.y]);
+
+        Self {
+            points_count: points.len() / 2,
+            points: into_raw(points),
+            contours_count: contours.len() - 1,
+            contours: into_raw(contours),
+            shapes_count: shapes.len(),
+            shapes: into_raw(offsets),
+        }
+    }
+}
+
+/// Runs an operation and writes its result, a panic is reported as an internal error.
+pub(crate) unsafe fn execute<B, F: FnOnce() -> Result<B, i32>>(output: *mut B, operation: F) -> i32 {
+    if output.is_null() {
+        return I_OVERLAY_ERROR_NULL;
+    }
+    match catch_unwind(AssertUnwindSafe(operation)) {
+        Ok(Ok(buffer)) => {
+            // SAFETY: the output is not null and the caller guarantees it is writable,
+            // the previous content is not read, so it is not dropped
+            unsafe { output.write(buffer) };
+            I_OVERLAY_OK
+        }
+        Ok(Err(status)) => status,
+        Err(_) => I_OVERLAY_ERROR_INTERNAL,
+    }
+}
+
+pub(crate) fn overlay_rule(value: u32) -> Result<OverlayRule, i32> {
+    let rule = match value {
+        I_OVERLAY_RULE_SUBJECT => OverlayRule::Subject,
+        I_OVERLAY_RULE_CLIP => OverlayRule::Clip,
+        I_OVERLAY_RULE_INTERSECT => OverlayRule::Intersect,
+        I_OVERLAY_RULE_UNION => OverlayRule::Union,
+        I_OVERLAY_RULE_DIFFERENCE => OverlayRule::Difference,
+        I_OVERLAY_RULE_INVERSE_DIFFERENCE => OverlayRule::InverseDifference,
+        I_OVERLAY_RULE_XOR => OverlayRule::Xor,
+        _ => return Err(I_OVERLAY_ERROR_ARGUMENT),
+    };
+    Ok(rule)
+}
+
+pub(crate) fn fill_rule(value: u32) -> Result<FillRule, i32> {
+    let rule = match value {
+        I_OVERLAY_FILL_EVEN_ODD => FillRule::EvenOdd,
+        I_OVERLAY_FILL_NON_ZERO => FillRule::NonZero,
+        I_OVERLAY_FILL_POSITIVE => FillRule::Positive,
+        I_OVERLAY_FILL_NEGATIVE => FillRule::Negative,
+        _ => return Err(I_OVERLAY_ERROR_ARGUMENT),
+    };
+    Ok(rule)
+}
+
+pub(crate) fn line_join(value: u32, parameter: f64) -> Result<LineJoin<f64>, i32> {
+    let join = match value {
+        I_OVERLAY_JOIN_BEVEL => LineJoin::Bevel,
+        I_OVERLAY_JOIN_MITER => LineJoin::Miter(finite(parameter)?),
+        I_OVERLAY_JOIN_ROUND => LineJoin::Round(finite(parameter)?),
+        _ => return Err(I_OVERLAY_ERROR_ARGUMENT),
+    };
+    Ok(join)
+}
+
+pub(crate) fn line_cap(value: u32, parameter: f64) -> Result<LineCap<[f64; 2], f64>, i32> {
+    let cap = match value {
+        I_OVERLAY_CAP_BUTT => LineCap::Butt,
+        I_OVERLAY_CAP_SQUARE => LineCap::Square,
+        I_OVERLAY_CAP_ROUND => LineCap::Round(finite(parameter)?),
+        _ => return Err(I_OVERLAY_ERROR_ARGUMENT),
+    };
+    Ok(cap)
+}
+
+#[inline]
+pub(crate) fn finite(value: f64) -> Result<f64, i32> {
+    if value.is_finite() { Ok(value) } else { Err(I_OVERLAY_ERROR_ARGUMENT) }
+}
+
+unsafe fn flat_slices<'a, T>(points: *const T, offsets: *const usize, count: usize) -> Result<(&'a [T], &'a [usize]), i32> {
+    if count == 0 {
+        return Ok((&[], &[0]));
+    }
+    if offsets.is_null() {
+        return Err(I_OVERLAY_ERROR_NULL);
+    }
+    // SAFETY: the caller guarantees `count + 1` offsets
+    let offsets = unsafe { std::slice::from_raw_parts(offsets, count + 1) };
+    if offsets.windows(2).any(|w| w[0] > w[1]) {
+        return Err(I_OVERLAY_ERROR_ARGUMENT);
+    }
+    let points_count = offsets[count];
+    if points_count == 0 {
+        return Ok((&[], offsets));
+    }
+    if points.is_null() {
+        return Err(I_OVERLAY_ERROR_NULL);
+    }
+    let len = points_count.checked_mul(2).ok_or(I_OVERLAY_ERROR_ARGUMENT)?;
+    // SAFETY: the caller guarantees a pair of coordinates for every point
+    let points = unsafe { std::slice::from_raw_parts(points, len) };
+
+    Ok((points, offsets))
+}
+
+#[inline]
+fn split<P, F: Fn(usize) -> P>(offsets: &[usize], point: F) -> Vec<Vec<P>> {
+    offsets.windows(2).map(|w| (w[0]..w[1]).map(&point).collect()).collect()
+}
+
+fn flatten_shapes<P, C: Copy, F: Fn(&P) -> [C; 2]>(shapes: &[Vec<Vec<P>>], xy: F) -> (Vec<C>, Vec<usize>, Vec<usize>) {
+    let mut points = Vec::new();
+    let mut contours = vec![0];
+    let mut offsets = vec![0];
+    for shape in shapes.iter() {
+        for contour in shape.iter() {
+            points.extend(contour.iter().flat_map(&xy));
+            contours.push(points.len() / 2);
+        }
+        offsets.push(contours.len() - 1);
+    }
+    (points, contours, offsets)
+}
+
+#[inline]
+fn into_raw<T>(values: Vec<T>) -> *mut T {
+    Box::into_raw(values.into_boxed_slice()) as *mut T
+}
+
+#[inline]
+unsafe fn free<T>(values: *mut T, len: usize) {
+    if !values.is_null() {
+        // SAFETY: the pointer comes from `into_raw` of a boxed slice with `len` items
+        drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(values, len)) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::null;
+    use crate::capi::{IOverlayPathBuffer, IOverlayPaths, i_overlay_free_paths, I_OVERLAY_ERROR_ARGUMENT, I_OVERLAY_ERROR_NULL};
+
+    #[test]
+    fn test_read_paths() {
+        let points = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 5.0, 5.0];
+        let offsets = [0, 3, 3, 4];
+        let paths = IOverlayPaths { points: points.as_ptr(), offsets: offsets.as_ptr(), count: 3 };
+
+        let paths = unsafe { paths.to_paths() }.unwrap();
+        assert_eq!(paths, vec![vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]], vec![], vec![[5.0, 5.0]]]);
+
+        let empty = IOverlayPaths { points: null(), offsets: null(), count: 0 };
+        assert!(unsafe { empty.to_paths() }.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        let points = [0.0, f64::NAN];
+        let offsets = [0, 1];
+        let paths = IOverlayPaths { points: points.as_ptr(), offsets: offsets.as_ptr(), count: 1 };
+        assert_eq!(unsafe { paths.to_paths() }, Err(I_OVERLAY_ERROR_ARGUMENT));
+
+        let decreasing = [1, 0];
+        let paths = IOverlayPaths { points: points.as_ptr(), offsets: decreasing.as_ptr(), count: 1 };
+        assert_eq!(unsafe { paths.to_paths() }, Err(I_OVERLAY_ERROR_ARGUMENT));
+
+        let paths = IOverlayPaths { points: null(), offsets: offsets.as_ptr(), count: 1 };
+        assert_eq!(unsafe { paths.to_paths() }, Err(I_OVERLAY_ERROR_NULL));
+    }
+
+    #[test]
+    fn test_path_buffer() {
+        let mut buffer = IOverlayPathBuffer::new(&vec![vec![[0.0, 1.0], [2.0, 3.0]], vec![[4.0, 5.0]]]);
+        assert_eq!(buffer.points_count, 3);
+        assert_eq!(buffer.paths_count, 2);
+        let points = unsafe { std::slice::from_raw_parts(buffer.points, 6) };
+        assert_eq!(points, &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        let offsets = unsafe { std::slice::from_raw_parts(buffer.paths, 3) };
+        assert_eq!(offsets, &[0, 2, 3]);
+
+        unsafe { i_overlay_free_paths(&mut buffer) };
+        assert!(buffer.points.is_null());
+        // a released buffer can be released again
+        unsafe { i_overlay_free_paths(&mut buffer) };
+    }
+
+    #[test]
+    fn test_header() {
+        // the header is generated, this checks that it is not stale
+        let header = include_str!("../../include/i_overlay.h");
+        assert!(header.contains("/* Generated by cbindgen from src/capi, do not edit. */"));
+        let sources = [
+            include_str!("mod.rs"),
+            include_str!("overlay.rs"),
+            include_str!("string.rs"),
+            include_str!("mesh.rs"),
+        ];
+
+        let mut count = 0;
+        for source in sources {
+            for (_, rest) in source.match_indices("extern \"C\" fn ").map(|(i, _)| source.split_at(i + 14)) {
+                let name = &rest[..rest.find('(').unwrap()];
+                assert!(header.contains(&format!(" {}(", name)), "{} is not declared in the header", name);
+                count += 1;
+            }
+            for line in source.lines() {
+                if let Some(rest) = line.strip_prefix("pub const ") {
+                    let (name, rest) = rest.split_once(':').unwrap();
+                    let value = rest.split_once("= ").unwrap().1.trim_end_matches(';');
+                    assert!(header.contains(&format!("#define {} {}\n", name, value)), "{} is not defined in the header", name);
+                } else if let Some(rest) = line.strip_prefix("pub struct ") {
+                    let name = rest.trim_end_matches(" {");
+                    assert!(header.contains(&format!("}} {};", name)), "{} is not declared in the header", name);
+                }
+            }
+        }
+        assert_eq!(header.matches(" i_overlay_").count(), count);
+    }
+}
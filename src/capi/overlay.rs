@@ -0,0 +1,127 @@
+use crate::capi;
+use crate::capi::{execute, finite, IOverlayIntPaths, IOverlayIntShapeBuffer, IOverlayPaths, IOverlayShapeBuffer};
+use crate::core::overlay::Overlay;
+use crate::float::filter::ContourFilter;
+use crate::float::overlay::FloatOverlay;
+
+/// Executes a boolean operation on integer contours with `Overlay`.
+/// - `overlay_rule`: `I_OVERLAY_RULE_*`.
+/// - `fill_rule`: `I_OVERLAY_FILL_*`.
+///
+/// # Safety
+/// `subj` and `clip` must describe readable buffers and `output` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_int_overlay(
+    subj: IOverlayIntPaths,
+    clip: IOverlayIntPaths,
+    overlay_rule: u32,
+    fill_rule: u32,
+    output: *mut IOverlayIntShapeBuffer,
+) -> i32 {
+    unsafe {
+        execute(output, || {
+            let overlay_rule = capi::overlay_rule(overlay_rule)?;
+            let fill_rule = capi::fill_rule(fill_rule)?;
+            let subj = subj.to_paths()?;
+            let clip = clip.to_paths()?;
+
+            let shapes = Overlay::with_contours(&subj, &clip).overlay(overlay_rule, fill_rule);
+            Ok(IOverlayIntShapeBuffer::new(&shapes))
+        })
+    }
+}
+
+/// Executes a boolean operation on float contours with `FloatOverlay`.
+/// - `overlay_rule`: `I_OVERLAY_RULE_*`.
+/// - `fill_rule`: `I_OVERLAY_FILL_*`.
+/// - `min_area`: Result contours with a smaller area are dropped.
+///
+/// # Safety
+/// `subj` and `clip` must describe readable buffers and `output` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_float_overlay(
+    subj: IOverlayPaths,
+    clip: IOverlayPaths,
+    overlay_rule: u32,
+    fill_rule: u32,
+    min_area: f64,
+    output: *mut IOverlayShapeBuffer,
+) -> i32 {
+    unsafe {
+        execute(output, || {
+            let overlay_rule = capi::overlay_rule(overlay_rule)?;
+            let fill_rule = capi::fill_rule(fill_rule)?;
+            let filter = ContourFilter { min_area: finite(min_area)?, ..Default::default() };
+            let subj = subj.to_paths()?;
+            let clip = clip.to_paths()?;
+
+            let shapes = FloatOverlay::with_subj_and_clip(&subj, &clip)
+                .overlay_with_filter_and_solver(overlay_rule, fill_rule, filter, Default::default());
+            Ok(IOverlayShapeBuffer::new(&shapes))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::{null, null_mut};
+    use crate::capi::{i_overlay_free_int_shapes, i_overlay_free_shapes, IOverlayIntPaths, IOverlayIntShapeBuffer, IOverlayPaths, IOverlayShapeBuffer, I_OVERLAY_ERROR_ARGUMENT, I_OVERLAY_ERROR_NULL, I_OVERLAY_OK};
+    use crate::capi::overlay::{i_overlay_float_overlay, i_overlay_int_overlay};
+
+    #[test]
+    fn test_int_union() {
+        let subj = [0, 0, 0, 10, 10, 10, 10, 0];
+        let clip = [10, 0, 10, 10, 20, 10, 20, 0];
+        let offsets = [0, 4];
+        let subj = IOverlayIntPaths { points: subj.as_ptr(), offsets: offsets.as_ptr(), count: 1 };
+        let clip = IOverlayIntPaths { points: clip.as_ptr(), offsets: offsets.as_ptr(), count: 1 };
+
+        let mut buffer = IOverlayIntShapeBuffer::default();
+        let status = unsafe { i_overlay_int_overlay(subj, clip, 3, 0, &mut buffer) };
+
+        assert_eq!(status, I_OVERLAY_OK);
+        assert_eq!(buffer.shapes_count, 1);
+        assert_eq!(buffer.contours_count, 1);
+        assert_eq!(buffer.points_count, 4);
+        let shapes = unsafe { std::slice::from_raw_parts(buffer.shapes, 2) };
+        assert_eq!(shapes, &[0, 1]);
+
+        unsafe { i_overlay_free_int_shapes(&mut buffer) };
+        assert!(buffer.shapes.is_null());
+    }
+
+    #[test]
+    fn test_float_difference() {
+        let subj = [0.0, 0.0, 0.0, 4.0, 4.0, 4.0, 4.0, 0.0];
+        let clip = [1.0, 1.0, 1.0, 3.0, 3.0, 3.0, 3.0, 1.0];
+        let offsets = [0, 4];
+        let subj = IOverlayPaths { points: subj.as_ptr(), offsets: offsets.as_ptr(), count: 1 };
+        let clip = IOverlayPaths { points: clip.as_ptr(), offsets: offsets.as_ptr(), count: 1 };
+
+        let mut buffer = IOverlayShapeBuffer::default();
+        let status = unsafe { i_overlay_float_overlay(subj, clip, 4, 1, 0.0, &mut buffer) };
+
+        assert_eq!(status, I_OVERLAY_OK);
+        assert_eq!(buffer.shapes_count, 1);
+        assert_eq!(buffer.contours_count, 2);
+        let contours = unsafe { std::slice::from_raw_parts(buffer.contours, 3) };
+        assert_eq!(contours, &[0, 4, 8]);
+
+        unsafe { i_overlay_free_shapes(&mut buffer) };
+    }
+
+    #[test]
+    fn test_errors() {
+        let empty = IOverlayPaths { points: null(), offsets: null(), count: 0 };
+        let mut buffer = IOverlayShapeBuffer::default();
+
+        assert_eq!(unsafe { i_overlay_float_overlay(empty, empty, 7, 0, 0.0, &mut buffer) }, I_OVERLAY_ERROR_ARGUMENT);
+        assert_eq!(unsafe { i_overlay_float_overlay(empty, empty, 0, 0, f64::NAN, &mut buffer) }, I_OVERLAY_ERROR_ARGUMENT);
+        assert_eq!(unsafe { i_overlay_float_overlay(empty, empty, 0, 0, 0.0, null_mut()) }, I_OVERLAY_ERROR_NULL);
+        assert!(buffer.points.is_null());
+
+        assert_eq!(unsafe { i_overlay_float_overlay(empty, empty, 3, 0, 0.0, &mut buffer) }, I_OVERLAY_OK);
+        assert_eq!(buffer.shapes_count, 0);
+        unsafe { i_overlay_free_shapes(&mut buffer) };
+    }
+}
@@ -0,0 +1,98 @@
+use crate::capi;
+use crate::capi::{execute, finite, IOverlayPathBuffer, IOverlayPaths, IOverlayShapeBuffer};
+use crate::float::clip::FloatClip;
+use crate::float::filter::ContourFilter;
+use crate::float::slice::FloatSlice;
+use crate::string::clip::ClipRule;
+
+/// Slices shapes by lines.
+/// - `contours`: Contours of the shapes.
+/// - `lines`: Open paths which cut the shapes.
+/// - `fill_rule`: `I_OVERLAY_FILL_*`.
+/// - `min_area`: Result contours with a smaller area are dropped.
+///
+/// # Safety
+/// `contours` and `lines` must describe readable buffers and `output` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_slice(
+    contours: IOverlayPaths,
+    lines: IOverlayPaths,
+    fill_rule: u32,
+    min_area: f64,
+    output: *mut IOverlayShapeBuffer,
+) -> i32 {
+    unsafe {
+        execute(output, || {
+            let fill_rule = capi::fill_rule(fill_rule)?;
+            let filter = ContourFilter { min_area: finite(min_area)?, ..Default::default() };
+            let contours = contours.to_paths()?;
+            let lines = lines.to_paths()?;
+
+            let shapes = contours.slice_by_with_filter_and_solver(&lines, fill_rule, filter, Default::default());
+            Ok(IOverlayShapeBuffer::new(&shapes))
+        })
+    }
+}
+
+/// Clips lines by shapes.
+/// - `lines`: Open paths to clip.
+/// - `contours`: Contours of the shapes.
+/// - `fill_rule`: `I_OVERLAY_FILL_*`.
+/// - `invert`: Keeps the parts outside of the shapes if not zero.
+/// - `boundary_included`: Keeps the parts on the boundary of the shapes if not zero.
+///
+/// # Safety
+/// `lines` and `contours` must describe readable buffers and `output` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn i_overlay_clip(
+    lines: IOverlayPaths,
+    contours: IOverlayPaths,
+    fill_rule: u32,
+    invert: bool,
+    boundary_included: bool,
+    output: *mut IOverlayPathBuffer,
+) -> i32 {
+    unsafe {
+        execute(output, || {
+            let fill_rule = capi::fill_rule(fill_rule)?;
+            let lines = lines.to_paths()?;
+            let contours = contours.to_paths()?;
+
+            let clip_rule = ClipRule { invert, boundary_included };
+            let paths = lines.clip_by(&contours, fill_rule, clip_rule);
+            Ok(IOverlayPathBuffer::new(&paths))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::capi::{i_overlay_free_paths, i_overlay_free_shapes, IOverlayPathBuffer, IOverlayPaths, IOverlayShapeBuffer, I_OVERLAY_OK};
+    use crate::capi::string::{i_overlay_clip, i_overlay_slice};
+
+    const SQUARE: [f64; 8] = [0.0, 0.0, 0.0, 4.0, 4.0, 4.0, 4.0, 0.0];
+    const LINE: [f64; 4] = [-1.0, 2.0, 5.0, 2.0];
+
+    #[test]
+    fn test_slice() {
+        let contours = IOverlayPaths { points: SQUARE.as_ptr(), offsets: [0, 4].as_ptr(), count: 1 };
+        let lines = IOverlayPaths { points: LINE.as_ptr(), offsets: [0, 2].as_ptr(), count: 1 };
+
+        let mut buffer = IOverlayShapeBuffer::default();
+        assert_eq!(unsafe { i_overlay_slice(contours, lines, 1, 0.0, &mut buffer) }, I_OVERLAY_OK);
+        assert_eq!(buffer.shapes_count, 2);
+        unsafe { i_overlay_free_shapes(&mut buffer) };
+    }
+
+    #[test]
+    fn test_clip() {
+        let contours = IOverlayPaths { points: SQUARE.as_ptr(), offsets: [0, 4].as_ptr(), count: 1 };
+        let lines = IOverlayPaths { points: LINE.as_ptr(), offsets: [0, 2].as_ptr(), count: 1 };
+
+        let mut buffer = IOverlayPathBuffer::default();
+        assert_eq!(unsafe { i_overlay_clip(lines, contours, 1, true, false, &mut buffer) }, I_OVERLAY_OK);
+        assert_eq!(buffer.paths_count, 2);
+        assert_eq!(buffer.points_count, 4);
+        unsafe { i_overlay_free_paths(&mut buffer) };
+    }
+}
@@ -0,0 +1,147 @@
+//! Reusable buffers for running many overlay operations in a row.
+
+use i_float::int::point::IntPoint;
+use crate::core::extract::ExtractBuffer;
+use crate::core::fill_rule::FillRule;
+use crate::core::flat::FlatShapes;
+use crate::core::graph::OverlayGraph;
+use crate::core::link::{LinkBuffer, OverlayLinkBuilder};
+use crate::core::node::OverlayNode;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::geom::end::End;
+use crate::segm::segment::Segment;
+use crate::segm::winding_count::ShapeCountBoolean;
+
+/// A workspace that keeps the intermediate buffers of boolean operations between calls.
+///
+/// Every operation allocates split, fill, link, node and visited-mask buffers, which becomes noticeable when
+/// thousands of small operations run per frame. Pass the same context to `Overlay::overlay_with_context`
+/// or `FloatOverlay::overlay_with_context` to reuse that memory instead. The buffers only grow,
+/// drop the context to release them.
+///
+/// The context keeps the split marks and segment buffer, the fill scan store and the graph buffers.
+/// Some allocations are still made per call: the initial segment sort, the segment tree or the
+/// fragment grid of the split when the input is too large for the list solver, and the growth
+/// of the fill scan tree when a larger input comes.
+///
+/// ```rust
+/// use i_float::int_pnt;
+/// use i_float::int::point::IntPoint;
+/// use i_overlay::core::context::OverlayContext;
+/// use i_overlay::core::fill_rule::FillRule;
+/// use i_overlay::core::flat::FlatShapes;
+/// use i_overlay::core::overlay::{Overlay, ShapeType};
+/// use i_overlay::core::overlay_rule::OverlayRule;
+///
+/// let mut context = OverlayContext::new();
+/// let mut overlay = Overlay::new(8);
+/// let mut result = FlatShapes::new();
+///
+/// for i in 0..10 {
+///     let subj = [int_pnt!(0, 0), int_pnt!(0, 10), int_pnt!(10, 10), int_pnt!(10, 0)];
+///     let clip = [int_pnt!(i, i), int_pnt!(i, i + 10), int_pnt!(i + 10, i + 10), int_pnt!(i + 10, i)];
+///     overlay.add_contour(&subj, ShapeType::Subject);
+///     overlay.add_contour(&clip, ShapeType::Clip);
+///
+///     overlay.overlay_with_context(OverlayRule::Union, FillRule::NonZero, 0, Default::default(), &mut context, &mut result);
+///     assert_eq!(result.len(), 1);
+/// }
+/// ```
+#[derive(Default)]
+pub struct OverlayContext {
+    link: LinkBuffer,
+    nodes: Vec<OverlayNode>,
+    end_bs: Vec<End>,
+    extract: ExtractBuffer,
+    pub(crate) int_shapes: FlatShapes<IntPoint>,
+}
+
+impl OverlayContext {
+    /// Creates an empty context, its buffers are allocated by the first operation.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a boolean operation on `segments` and writes the result into `output`.
+    /// Returns the emptied segment buffer, so the caller can keep its memory.
+    pub(crate) fn overlay(
+        &mut self,
+        segments: Vec<Segment<ShapeCountBoolean>>,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        min_area: usize,
+        solver: Solver,
+        output: &mut FlatShapes<IntPoint>,
+    ) -> Vec<Segment<ShapeCountBoolean>> {
+        let mut segments = OverlayLinkBuilder::build_with_overlay_filter_into(
+            segments,
+            fill_rule,
+            overlay_rule,
+            solver,
+            &mut self.link,
+        );
+        segments.clear();
+
+        let mut links = std::mem::take(&mut self.link.links);
+        let mut nodes = std::mem::take(&mut self.nodes);
+        OverlayGraph::build_nodes_and_connect_links_into(&solver, &mut links, &mut self.end_bs, &mut nodes);
+        let graph = OverlayGraph { solver, nodes, links };
+
+        self.extract.visited.clear();
+        self.extract.visited.resize(graph.links.len(), false);
+        graph.extract_into(overlay_rule, min_area, &mut self.extract, output);
+
+        self.link.links = graph.links;
+        self.nodes = graph.nodes;
+
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::context::OverlayContext;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::flat::FlatShapes;
+    use crate::core::overlay::{Overlay, ShapeType};
+    use crate::core::overlay_rule::OverlayRule;
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x, y + size),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x + size, y),
+        ]
+    }
+
+    #[test]
+    fn test_reuse_matches_overlay() {
+        let mut context = OverlayContext::new();
+        let mut overlay = Overlay::new(0);
+        let mut result = FlatShapes::new();
+
+        let rules = [OverlayRule::Union, OverlayRule::Difference, OverlayRule::Xor, OverlayRule::Intersect];
+        for i in 0..20 {
+            let subj = [square(0, 0, 20), square(30, 0, 10 + i)];
+            let clip = [square(i, i, 5), square(25 + i, 2, 8)];
+            let overlay_rule = rules[i as usize % rules.len()];
+
+            overlay.add_contours(&subj, ShapeType::Subject);
+            overlay.add_contours(&clip, ShapeType::Clip);
+            overlay.overlay_with_context(overlay_rule, FillRule::NonZero, 0, Default::default(), &mut context, &mut result);
+
+            let expected = Overlay::with_contours(&subj, &clip).overlay(overlay_rule, FillRule::NonZero);
+            assert_eq!(result.to_shapes(), expected);
+            assert!(overlay.segments.is_empty());
+        }
+
+        assert!(overlay.segments.capacity() > 0);
+        assert!(context.link.links.capacity() > 0);
+        assert!(context.link.split.spare.capacity() > 0);
+        assert!(context.link.fill.ends.capacity() > 0);
+    }
+}
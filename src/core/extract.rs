@@ -1,7 +1,9 @@
+use std::ops::Range;
 use super::filter::MaskFilter;
 use super::overlay_rule::OverlayRule;
 use crate::bind::segment::IdSegment;
-use crate::bind::solver::{JoinHoles, LeftBottomSegment};
+use crate::bind::solver::{JoinHoles, LeftBottomSegment, ShapeBinder, SortByAngle};
+use crate::core::flat::FlatShapes;
 use crate::core::graph::OverlayGraph;
 use crate::core::link::OverlayLink;
 use crate::core::nearest_vector::NearestVector;
@@ -10,7 +12,7 @@ use crate::geom::x_segment::XSegment;
 use i_float::int::point::IntPoint;
 use i_float::triangle::Triangle;
 use i_shape::int::path::{IntPath, PointPathExtension};
-use i_shape::int::shape::IntShapes;
+use i_shape::int::shape::{IntShape, IntShapes};
 use i_shape::int::simple::Simplify;
 
 impl OverlayGraph {
@@ -45,6 +47,41 @@ impl OverlayGraph {
         self.extract(visited, overlay_rule, min_area)
     }
 
+    /// Extracts shapes one by one instead of returning them all at once, which lets the caller consume each shape and release it right away.
+    /// All contours are traced into flat buffers and holes are attached to their shapes before the first shape is yielded,
+    /// since a hole may belong to any outer contour. Each `IntShape` is built only when it is requested.
+    /// - `overlay_rule`: The boolean operation rule to apply when extracting shapes from the graph.
+    /// - `min_area`: The minimum area threshold for contours to be included in the result.
+    /// - Returns: An iterator over `IntShape`, yielding the same shapes as `extract_shapes_into` in the same order.
+    #[inline]
+    pub fn extract_shapes_iter(&self, overlay_rule: OverlayRule, min_area: usize) -> impl Iterator<Item = IntShape> + use<> {
+        let mut flat = FlatShapes::new();
+        self.extract_shapes_into(overlay_rule, min_area, &mut flat);
+        flat.into_shapes_iter()
+    }
+
+    /// Passes each extracted shape with its holes to `visitor`, see `extract_shapes_iter`.
+    /// - `overlay_rule`: The boolean operation rule to apply when extracting shapes from the graph.
+    /// - `min_area`: The minimum area threshold for contours to be included in the result.
+    /// - `visitor`: Receives the shapes in the order of `extract_shapes_into`.
+    #[inline]
+    pub fn visit_shapes<F: FnMut(IntShape)>(&self, overlay_rule: OverlayRule, min_area: usize, visitor: F) {
+        self.extract_shapes_iter(overlay_rule, min_area).for_each(visitor);
+    }
+
+    /// Extracts shapes like `extract_shapes_min_area`, but writes them into flat buffers.
+    /// The `output` is cleared first and its memory is reused, so repeated calls do not allocate once the buffers are large enough.
+    /// - `overlay_rule`: The boolean operation rule to apply when extracting shapes from the graph.
+    /// - `min_area`: The minimum area threshold for contours to be included in the result.
+    /// - `output`: Receives the same shapes as `extract_shapes_min_area` in the same order.
+    pub fn extract_shapes_into(&self, overlay_rule: OverlayRule, min_area: usize, output: &mut FlatShapes<IntPoint>) {
+        let mut buffer = ExtractBuffer {
+            visited: self.links.filter_by_rule(overlay_rule),
+            ..Default::default()
+        };
+        self.extract_into(overlay_rule, min_area, &mut buffer, output);
+    }
+
     pub(crate) fn extract(
         &self,
         filter: Vec<bool>,
@@ -110,19 +147,149 @@ impl OverlayGraph {
         shapes
     }
 
+    /// Extracts shapes into `output`, `buffer.visited` must hold the filter of links.
+    pub(crate) fn extract_into(
+        &self,
+        overlay_rule: OverlayRule,
+        min_area: usize,
+        buffer: &mut ExtractBuffer,
+        output: &mut FlatShapes<IntPoint>,
+    ) {
+        output.clear();
+        buffer.shapes.clear();
+        buffer.holes.clear();
+        buffer.anchors.clear();
+
+        let visited = buffer.visited.as_mut_slice();
+        let path = &mut buffer.path;
+        let shapes = &mut buffer.shapes;
+        let holes = &mut buffer.holes;
+        let anchors = &mut buffer.anchors;
+
+        let mut link_index = 0;
+        let mut is_all_anchors_sorted = true;
+        while link_index < visited.len() {
+            if visited.is_visited(link_index) {
+                link_index += 1;
+                continue;
+            }
+
+            let left_top_link = self.find_left_top_link(link_index, visited);
+            let link = self.link(left_top_link);
+            let is_hole = overlay_rule.is_fill_top(link.fill);
+
+            let start_data = StartPathData::new(is_hole, link, left_top_link);
+
+            self.fill_path(&start_data, is_hole, visited, path);
+            let (is_valid, is_modified) = path.validate(min_area);
+
+            if !is_valid {
+                link_index += 1;
+                continue;
+            }
+
+            if is_hole {
+                let mut x_segment = XSegment {
+                    a: path[1],
+                    b: path[2],
+                };
+                if is_modified {
+                    let most_left = path.left_bottom_segment();
+                    if most_left != x_segment {
+                        x_segment = most_left;
+                        is_all_anchors_sorted = false;
+                    }
+                };
+
+                debug_assert_eq!(x_segment, path.left_bottom_segment());
+                let id = holes.len();
+                anchors.push(IdSegment { id, x_segment });
+                holes.push(path);
+            } else {
+                shapes.push(path);
+            }
+        }
+
+        if shapes.is_empty() {
+            return;
+        }
+
+        if holes.is_empty() || shapes.len() == 1 {
+            for shape in shapes.iter() {
+                output.push_contour(shape);
+            }
+            for hole in holes.iter() {
+                output.push_contour(hole);
+            }
+            if shapes.len() == 1 {
+                output.end_shape();
+            } else {
+                for i in 0..shapes.len() {
+                    output.shape_ranges.push(i..i + 1);
+                }
+            }
+            return;
+        }
+
+        if !is_all_anchors_sorted {
+            anchors.sort_by_key(|s| s.x_segment.a);
+        }
+        anchors.add_sort_by_angle();
+
+        let solution = ShapeBinder::bind_contours(&self.solver, shapes.iter(), holes.iter(), anchors);
+
+        // order holes by their parent shape, keeping the extraction order inside a shape
+        let mut offsets = Vec::with_capacity(shapes.len());
+        let mut offset = 0;
+        for &count in solution.children_count_for_parent.iter() {
+            offsets.push(offset);
+            offset += count;
+        }
+        let mut order = vec![0; holes.len()];
+        for (hole_index, &shape_index) in solution.parent_for_child.iter().enumerate() {
+            order[offsets[shape_index]] = hole_index;
+            offsets[shape_index] += 1;
+        }
+
+        output.points.reserve(shapes.points.len() + holes.points.len());
+        output.contour_ranges.reserve(shapes.len() + holes.len());
+        output.shape_ranges.reserve(shapes.len());
+
+        let mut hole_orders = order.iter();
+        for (shape, &count) in shapes.iter().zip(solution.children_count_for_parent.iter()) {
+            output.push_contour(shape);
+            for &hole_index in hole_orders.by_ref().take(count) {
+                output.push_contour(holes.get(hole_index));
+            }
+            output.end_shape();
+        }
+    }
+
     fn get_path(
         &self,
         start_data: &StartPathData,
         clockwise: bool,
         visited: &mut [bool],
     ) -> IntPath {
+        let mut path = IntPath::new();
+        self.fill_path(start_data, clockwise, visited, &mut path);
+        path
+    }
+
+    fn fill_path(
+        &self,
+        start_data: &StartPathData,
+        clockwise: bool,
+        visited: &mut [bool],
+        path: &mut IntPath,
+    ) {
         let mut link_id = start_data.link_id;
         let mut node_id = start_data.node_id;
         let last_node_id = start_data.last_node_id;
 
         visited.visit(link_id);
 
-        let mut path = IntPath::new();
+        path.clear();
         path.push(start_data.begin);
 
         // Find a closed tour
@@ -152,8 +319,6 @@ impl OverlayGraph {
 
             visited.visit(link_id);
         }
-
-        path
     }
 
     #[inline]
@@ -357,3 +522,54 @@ impl Visit for [bool] {
         unsafe { *self.get_unchecked_mut(index) = true }
     }
 }
+
+/// Reusable buffers of `extract_into`.
+#[derive(Default)]
+pub(crate) struct ExtractBuffer {
+    pub(crate) visited: Vec<bool>,
+    path: IntPath,
+    shapes: FlatContours,
+    holes: FlatContours,
+    anchors: Vec<IdSegment>,
+}
+
+#[derive(Default)]
+struct FlatContours {
+    points: Vec<IntPoint>,
+    ranges: Vec<Range<usize>>,
+}
+
+impl FlatContours {
+    #[inline]
+    fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.points.clear();
+        self.ranges.clear();
+    }
+
+    #[inline]
+    fn push(&mut self, contour: &[IntPoint]) {
+        let start = self.points.len();
+        self.points.extend_from_slice(contour);
+        self.ranges.push(start..self.points.len());
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> &[IntPoint] {
+        &self.points[self.ranges[index].clone()]
+    }
+
+    #[inline]
+    fn iter(&self) -> impl Iterator<Item = &[IntPoint]> + Clone {
+        self.ranges.iter().map(|range| &self.points[range.clone()])
+    }
+}
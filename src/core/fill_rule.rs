@@ -6,6 +6,7 @@ use std::fmt;
 /// - `Positive`: Fills regions where the winding number is positive.
 /// - `Negative`: Fills regions where the winding number is negative.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillRule {
     EvenOdd,
     NonZero,
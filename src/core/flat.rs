@@ -0,0 +1,287 @@
+//! A flat representation of shapes, where the points of all contours are stored in a single buffer.
+//! It is convenient for consumers that upload geometry to a GPU or pass it over FFI,
+//! and it can be reused between operations to avoid allocations.
+
+use std::ops::Range;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::{Shape, Shapes};
+use i_shape::int::shape::IntShape;
+use i_shape::int::simple::SimpleContour;
+
+/// Shapes stored in flat buffers.
+/// - `points`: The points of all contours one after another.
+/// - `contour_ranges`: The range of points of each contour.
+/// - `shape_ranges`: The range of contours of each shape, the first contour of a shape is its outer boundary, and all subsequent contours are holes.
+///
+/// Note: Outer boundary contours have a clockwise order, and holes have a counterclockwise order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatShapes<P> {
+    pub points: Vec<P>,
+    pub contour_ranges: Vec<Range<usize>>,
+    pub shape_ranges: Vec<Range<usize>>,
+}
+
+impl<P> Default for FlatShapes<P> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> FlatShapes<P> {
+    /// Creates an empty `FlatShapes`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { points: Vec::new(), contour_ranges: Vec::new(), shape_ranges: Vec::new() }
+    }
+
+    /// Creates an empty `FlatShapes` with preallocated buffers.
+    /// - `points`: The capacity of the points buffer.
+    /// - `contours`: The capacity of the contour ranges buffer.
+    /// - `shapes`: The capacity of the shape ranges buffer.
+    #[inline]
+    pub fn with_capacity(points: usize, contours: usize, shapes: usize) -> Self {
+        Self {
+            points: Vec::with_capacity(points),
+            contour_ranges: Vec::with_capacity(contours),
+            shape_ranges: Vec::with_capacity(shapes),
+        }
+    }
+
+    /// Removes all shapes and keeps the allocated memory for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.contour_ranges.clear();
+        self.shape_ranges.clear();
+    }
+
+    /// Returns the number of shapes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shape_ranges.len()
+    }
+
+    /// Returns `true` if there are no shapes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.shape_ranges.is_empty()
+    }
+
+    /// Returns the points of the contour at `index`.
+    #[inline]
+    pub fn contour(&self, index: usize) -> &[P] {
+        &self.points[self.contour_ranges[index].clone()]
+    }
+
+    /// Returns the contours of the shape at `index`, starting with the outer boundary.
+    #[inline]
+    pub fn shape(&self, index: usize) -> impl Iterator<Item = &[P]> {
+        self.contour_ranges[self.shape_ranges[index].clone()]
+            .iter()
+            .map(|range| &self.points[range.clone()])
+    }
+
+    /// Returns an iterator over all shapes, see `shape`.
+    #[inline]
+    pub fn shapes(&self) -> impl Iterator<Item = impl Iterator<Item = &[P]>> {
+        (0..self.len()).map(|index| self.shape(index))
+    }
+
+    /// Adds a contour to the shape that is currently being built, see `end_shape`.
+    #[inline]
+    pub fn push_contour(&mut self, contour: &[P])
+    where
+        P: Copy,
+    {
+        let start = self.points.len();
+        self.points.extend_from_slice(contour);
+        self.contour_ranges.push(start..self.points.len());
+    }
+
+    /// Finishes a shape from all contours added after the previous shape.
+    #[inline]
+    pub fn end_shape(&mut self) {
+        let start = self.shape_ranges.last().map_or(0, |range| range.end);
+        self.shape_ranges.push(start..self.contour_ranges.len());
+    }
+
+    /// Adds a shape, the first contour is the outer boundary and the rest are holes.
+    #[inline]
+    pub fn push_shape<C: AsRef<[P]>>(&mut self, shape: &[C])
+    where
+        P: Copy,
+    {
+        for contour in shape.iter() {
+            self.push_contour(contour.as_ref());
+        }
+        self.end_shape();
+    }
+
+    /// Converts the flat buffers into an iterator, which builds the nested shapes one by one.
+    pub fn into_shapes_iter(self) -> impl Iterator<Item = Shape<P>>
+    where
+        P: Clone,
+    {
+        (0..self.len()).map(move |index| self.shape(index).map(|contour| contour.to_vec()).collect())
+    }
+
+    /// Converts the flat buffers into nested `Shapes`.
+    pub fn to_shapes(&self) -> Shapes<P>
+    where
+        P: Clone,
+    {
+        self.shapes()
+            .map(|shape| shape.map(|contour| contour.to_vec()).collect())
+            .collect()
+    }
+}
+
+impl<P> FlatShapes<P> {
+    /// Replaces the content with int shapes converted by `adapter`.
+    pub(crate) fn set_int_flat<T: FloatNumber>(&mut self, flat: &FlatShapes<IntPoint>, adapter: &FloatPointAdapter<P, T>)
+    where
+        P: FloatPointCompatible<T>,
+    {
+        self.clear();
+        self.points.extend(flat.points.iter().map(|p| adapter.int_to_float(p)));
+        self.contour_ranges.extend_from_slice(&flat.contour_ranges);
+        self.shape_ranges.extend_from_slice(&flat.shape_ranges);
+    }
+
+    /// Replaces the content with nested int shapes converted by `adapter`.
+    pub(crate) fn set_int_shapes<T: FloatNumber>(&mut self, shapes: &[IntShape], adapter: &FloatPointAdapter<P, T>)
+    where
+        P: FloatPointCompatible<T>,
+    {
+        self.clear();
+        for shape in shapes.iter() {
+            for contour in shape.iter() {
+                let start = self.points.len();
+                self.points.extend(contour.iter().map(|p| adapter.int_to_float(p)));
+                self.contour_ranges.push(start..self.points.len());
+            }
+            self.end_shape();
+        }
+    }
+}
+
+impl<P> FlatShapes<P> {
+    /// Simplifies the contours in float space like `SimplifyContour` does for nested shapes:
+    /// every contour is rounded by `adapter`, degenerate edges are removed,
+    /// a shape is dropped if its outer contour collapses and a hole is dropped if it collapses.
+    /// The buffers are compacted in place.
+    ///
+    /// Returns `true` if any contour was modified.
+    pub fn simplify_contour<T: FloatNumber>(&mut self, adapter: &FloatPointAdapter<P, T>) -> bool
+    where
+        P: FloatPointCompatible<T>,
+    {
+        let mut int_contour = Vec::new();
+        let mut any_simplified = false;
+
+        let mut points_end = 0;
+        let mut contours_end = 0;
+        let mut shapes_end = 0;
+        for shape_index in 0..self.shape_ranges.len() {
+            let shape_range = self.shape_ranges[shape_index].clone();
+            let shape_start = contours_end;
+            let shape_points_start = points_end;
+            for contour_index in shape_range.clone() {
+                let range = self.contour_ranges[contour_index].clone();
+                int_contour.clear();
+                int_contour.extend(self.points[range.clone()].iter().map(|p| adapter.float_to_int(p)));
+
+                let start = points_end;
+                if int_contour.is_simple() {
+                    self.points.copy_within(range, start);
+                    points_end += int_contour.len();
+                } else {
+                    any_simplified = true;
+                    if let Some(simple) = int_contour.simplified() {
+                        for (i, p) in simple.iter().enumerate() {
+                            self.points[start + i] = adapter.int_to_float(p);
+                        }
+                        points_end += simple.len();
+                    } else if contour_index == shape_range.start {
+                        // the outer contour is collapsed, so the whole shape is dropped
+                        contours_end = shape_start;
+                        points_end = shape_points_start;
+                        break;
+                    } else {
+                        continue;
+                    }
+                }
+
+                self.contour_ranges[contours_end] = start..points_end;
+                contours_end += 1;
+            }
+
+            if contours_end > shape_start {
+                self.shape_ranges[shapes_end] = shape_start..contours_end;
+                shapes_end += 1;
+            }
+        }
+
+        self.points.truncate(points_end);
+        self.contour_ranges.truncate(contours_end);
+        self.shape_ranges.truncate(shapes_end);
+
+        any_simplified
+    }
+}
+
+impl<P: Copy> From<&[Shape<P>]> for FlatShapes<P> {
+    fn from(shapes: &[Shape<P>]) -> Self {
+        let contours_count = shapes.iter().map(|shape| shape.len()).sum();
+        let points_count = shapes.iter().flatten().map(|contour| contour.len()).sum();
+        let mut flat = Self::with_capacity(points_count, contours_count, shapes.len());
+        for shape in shapes.iter() {
+            flat.push_shape(shape);
+        }
+        flat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::flat::FlatShapes;
+
+    #[test]
+    fn test_push_and_read() {
+        let shapes = vec![
+            vec![
+                vec![[0, 0], [0, 10], [10, 10], [10, 0]],
+                vec![[2, 2], [8, 2], [8, 8], [2, 8]],
+            ],
+            vec![vec![[20, 0], [20, 10], [30, 10]]],
+        ];
+
+        let flat = FlatShapes::from(shapes.as_slice());
+
+        assert_eq!(flat.len(), 2);
+        assert_eq!(flat.points.len(), 11);
+        assert_eq!(flat.contour_ranges, [0..4, 4..8, 8..11]);
+        assert_eq!(flat.shape_ranges, [0..2, 2..3]);
+        assert_eq!(flat.contour(2), &[[20, 0], [20, 10], [30, 10]]);
+        assert_eq!(flat.shape(0).count(), 2);
+        assert_eq!(flat.to_shapes(), shapes);
+        assert_eq!(flat.into_shapes_iter().collect::<Vec<_>>(), shapes);
+    }
+
+    #[test]
+    fn test_reuse() {
+        let mut flat = FlatShapes::new();
+        flat.push_shape(&[[[0, 0], [0, 1], [1, 1]]]);
+        flat.clear();
+        assert!(flat.is_empty());
+        assert!(flat.points.capacity() >= 3);
+
+        flat.push_contour(&[[1, 1], [1, 2], [2, 2]]);
+        flat.end_shape();
+        assert_eq!(flat.shape_ranges[0], 0..1);
+    }
+}
@@ -31,18 +31,31 @@ impl OverlayGraph {
     }
 
     pub(crate) fn build_nodes_and_connect_links(solver: &Solver, links: &mut [OverlayLink]) -> Vec<OverlayNode> {
+        let mut nodes = Vec::new();
+        Self::build_nodes_and_connect_links_into(solver, links, &mut Vec::new(), &mut nodes);
+        nodes
+    }
+
+    /// Builds nodes like `build_nodes_and_connect_links`, reusing `end_bs` and the memory of the previous `nodes`.
+    pub(crate) fn build_nodes_and_connect_links_into(
+        solver: &Solver,
+        links: &mut [OverlayLink],
+        end_bs: &mut Vec<End>,
+        nodes: &mut Vec<OverlayNode>,
+    ) {
         let n = links.len();
         if n == 0 {
-            return vec![];
+            nodes.clear();
+            return;
         }
 
-        let mut end_bs: Vec<End> = links.iter().enumerate()
-            .map(|(i, link)| End { index: i, point: link.b.point })
-            .collect();
+        end_bs.clear();
+        end_bs.extend(links.iter().enumerate().map(|(i, link)| End { index: i, point: link.b.point }));
 
         end_bs.smart_bin_sort_by(solver, |a, b| a.point.cmp(&b.point));
 
-        let mut nodes: Vec<OverlayNode> = Vec::with_capacity(n);
+        nodes.reserve(n.saturating_sub(nodes.len()));
+        let mut nodes_count = 0;
 
         let mut ai = 0;
         let mut bi = 0;
@@ -60,7 +73,7 @@ impl OverlayGraph {
                 (0, next_b_cnt)
             };
 
-            let node_id = nodes.len();
+            let node_id = nodes_count;
 
             if a_cnt > 0 {
                 next_a_cnt = 0;
@@ -90,11 +103,16 @@ impl OverlayGraph {
                 }
             }
 
-            nodes.push(OverlayNode::new(indices.as_slice()));
+            if node_id < nodes.len() {
+                nodes[node_id].reset(indices.as_slice());
+            } else {
+                nodes.push(OverlayNode::new(indices.as_slice()));
+            }
+            nodes_count += 1;
             indices.clear();
         }
 
-        nodes
+        nodes.truncate(nodes_count);
     }
 }
 
@@ -1,13 +1,14 @@
+use std::marker::PhantomData;
 use i_float::int::point::IntPoint;
 use crate::core::fill_rule::FillRule;
 use crate::core::filter::{ClipFilter, DifferenceFilter, StringClipInsideBoundaryExcludedFilter, StringClipInsideBoundaryIncludedFilter, FillerFilter, IntersectFilter, InverseDifferenceFilter, StringClipOutsideBoundaryExcludedFilter, StringClipOutsideBoundaryIncludedFilter, SubjectFilter, UnionFilter, XorFilter, InclusionFilterStrategy};
 use crate::core::overlay_rule::OverlayRule;
 use crate::core::solver::Solver;
-use crate::fill::solver::{FillSolver, FillStrategy};
+use crate::fill::solver::{FillBuffer, FillSolver, FillStrategy};
 use crate::geom::id_point::IdPoint;
-use crate::segm::segment::{Segment, SegmentFill};
-use crate::segm::winding_count::{WindingCount, ShapeCountBoolean, ShapeCountString};
-use crate::split::solver::SplitSegments;
+use crate::segm::segment::{Segment, SegmentFill, SUBJ_BOTH};
+use crate::segm::winding_count::{WindingCount, ShapeCountBoolean, ShapeCountString, ShapeCountSource};
+use crate::split::solver::{SplitBuffer, SplitSegments};
 use crate::string::clip::ClipRule;
 
 #[derive(Debug, Clone, Copy)]
@@ -17,6 +18,15 @@ pub(crate) struct OverlayLink {
     pub(crate) fill: SegmentFill,
 }
 
+/// The buffers of the link building, kept between boolean operations to reuse their memory.
+#[derive(Default)]
+pub(crate) struct LinkBuffer {
+    pub(crate) split: SplitBuffer<ShapeCountBoolean>,
+    pub(crate) fill: FillBuffer<ShapeCountBoolean>,
+    pub(crate) fills: Vec<SegmentFill>,
+    pub(crate) links: Vec<OverlayLink>,
+}
+
 impl OverlayLink {
     #[inline(always)]
     pub(crate) fn new(a: IdPoint, b: IdPoint, fill: SegmentFill) -> OverlayLink {
@@ -60,6 +70,53 @@ impl OverlayLinkBuilder {
         }
     }
 
+    /// Builds the links of a boolean operation like `build_with_overlay_filter`, but reuses the split, fill
+    /// and link buffers of `buffer`. The links are written into `buffer.links`.
+    /// Returns the split segments, so their memory can be reused as well.
+    pub(crate) fn build_with_overlay_filter_into(
+        segments: Vec<Segment<ShapeCountBoolean>>,
+        fill_rule: FillRule,
+        overlay_rule: OverlayRule,
+        solver: Solver,
+        buffer: &mut LinkBuffer,
+    ) -> Vec<Segment<ShapeCountBoolean>> {
+        match overlay_rule {
+            OverlayRule::Subject => Self::build_boolean_into::<SubjectFilter>(segments, fill_rule, solver, buffer),
+            OverlayRule::Clip => Self::build_boolean_into::<ClipFilter>(segments, fill_rule, solver, buffer),
+            OverlayRule::Intersect => Self::build_boolean_into::<IntersectFilter>(segments, fill_rule, solver, buffer),
+            OverlayRule::Union => Self::build_boolean_into::<UnionFilter>(segments, fill_rule, solver, buffer),
+            OverlayRule::Difference => Self::build_boolean_into::<DifferenceFilter>(segments, fill_rule, solver, buffer),
+            OverlayRule::InverseDifference => Self::build_boolean_into::<InverseDifferenceFilter>(segments, fill_rule, solver, buffer),
+            OverlayRule::Xor => Self::build_boolean_into::<XorFilter>(segments, fill_rule, solver, buffer),
+        }
+    }
+
+    /// Builds the links of a boolean operation like `build_with_overlay_filter`, but the clip is always filled
+    /// by the positive rule, so a clip with overlapping parts, like raw stroke segments, can go to the same pass.
+    pub(crate) fn build_with_positive_clip(segments: Vec<Segment<ShapeCountBoolean>>, fill_rule: FillRule, overlay_rule: OverlayRule, solver: Solver) -> Vec<OverlayLink> {
+        if segments.is_empty() { return vec![]; }
+        let segments = segments.split_segments(solver);
+        if segments.is_empty() { return vec![]; }
+
+        let is_list = solver.is_list_fill(&segments);
+        let fills = match fill_rule {
+            FillRule::EvenOdd => FillSolver::fill::<PositiveClipStrategy<EvenOddStrategy>, ShapeCountBoolean>(is_list, &segments),
+            FillRule::NonZero => FillSolver::fill::<PositiveClipStrategy<NonZeroStrategy>, ShapeCountBoolean>(is_list, &segments),
+            FillRule::Positive => FillSolver::fill::<PositiveStrategy, ShapeCountBoolean>(is_list, &segments),
+            FillRule::Negative => FillSolver::fill::<PositiveClipStrategy<NegativeStrategy>, ShapeCountBoolean>(is_list, &segments),
+        };
+
+        match overlay_rule {
+            OverlayRule::Subject => Self::build_links::<SubjectFilter, ShapeCountBoolean>(&segments, &fills),
+            OverlayRule::Clip => Self::build_links::<ClipFilter, ShapeCountBoolean>(&segments, &fills),
+            OverlayRule::Intersect => Self::build_links::<IntersectFilter, ShapeCountBoolean>(&segments, &fills),
+            OverlayRule::Union => Self::build_links::<UnionFilter, ShapeCountBoolean>(&segments, &fills),
+            OverlayRule::Difference => Self::build_links::<DifferenceFilter, ShapeCountBoolean>(&segments, &fills),
+            OverlayRule::InverseDifference => Self::build_links::<InverseDifferenceFilter, ShapeCountBoolean>(&segments, &fills),
+            OverlayRule::Xor => Self::build_links::<XorFilter, ShapeCountBoolean>(&segments, &fills),
+        }
+    }
+
     pub(crate) fn build_string_all(segments: Vec<Segment<ShapeCountString>>, fill_rule: FillRule, solver: Solver) -> Vec<OverlayLink> {
         if segments.is_empty() { return vec![]; }
         let segments = segments.split_segments(solver);
@@ -83,23 +140,48 @@ impl OverlayLinkBuilder {
         }
     }
 
-    fn fill_string(segments: &[Segment<ShapeCountString>], fill_rule: FillRule, solver: Solver) -> Vec<SegmentFill> {
+    /// Builds all links like `build_string_all` and returns the source of every link next to it.
+    pub(crate) fn build_string_all_with_sources(segments: Vec<Segment<ShapeCountSource>>, fill_rule: FillRule, solver: Solver) -> (Vec<OverlayLink>, Vec<ShapeCountSource>) {
+        if segments.is_empty() { return (vec![], vec![]); }
+        let segments = segments.split_segments(solver);
+        if segments.is_empty() { return (vec![], vec![]); }
+        let fills = Self::fill_string(&segments, fill_rule, solver);
+        let links = Self::build_all_links(&segments, &fills);
+        let sources = segments.into_iter().map(|segment| segment.count).collect();
+
+        (links, sources)
+    }
+
+    fn fill_string<C: WindingCount>(segments: &[Segment<C>], fill_rule: FillRule, solver: Solver) -> Vec<SegmentFill>
+    where
+        EvenOddStrategyString: FillStrategy<C>,
+        NonZeroStrategyString: FillStrategy<C>,
+        PositiveStrategyString: FillStrategy<C>,
+        NegativeStrategyString: FillStrategy<C>,
+    {
         let is_list = solver.is_list_fill(segments);
         match fill_rule {
-            FillRule::EvenOdd => FillSolver::fill::<EvenOddStrategyString, ShapeCountString>(is_list, segments),
-            FillRule::NonZero => FillSolver::fill::<NonZeroStrategyString, ShapeCountString>(is_list, segments),
-            FillRule::Positive => FillSolver::fill::<PositiveStrategyString, ShapeCountString>(is_list, segments),
-            FillRule::Negative => FillSolver::fill::<NegativeStrategyString, ShapeCountString>(is_list, segments),
+            FillRule::EvenOdd => FillSolver::fill::<EvenOddStrategyString, C>(is_list, segments),
+            FillRule::NonZero => FillSolver::fill::<NonZeroStrategyString, C>(is_list, segments),
+            FillRule::Positive => FillSolver::fill::<PositiveStrategyString, C>(is_list, segments),
+            FillRule::Negative => FillSolver::fill::<NegativeStrategyString, C>(is_list, segments),
         }
     }
 
+    #[inline]
     fn fill_boolean(segments: &[Segment<ShapeCountBoolean>], fill_rule: FillRule, solver: Solver) -> Vec<SegmentFill> {
+        let mut fills = Vec::new();
+        Self::fill_boolean_into(segments, fill_rule, solver, &mut FillBuffer::default(), &mut fills);
+        fills
+    }
+
+    fn fill_boolean_into(segments: &[Segment<ShapeCountBoolean>], fill_rule: FillRule, solver: Solver, buffer: &mut FillBuffer<ShapeCountBoolean>, fills: &mut Vec<SegmentFill>) {
         let is_list = solver.is_list_fill(segments);
         match fill_rule {
-            FillRule::EvenOdd => FillSolver::fill::<EvenOddStrategy, ShapeCountBoolean>(is_list, segments),
-            FillRule::NonZero => FillSolver::fill::<NonZeroStrategy, ShapeCountBoolean>(is_list, segments),
-            FillRule::Positive => FillSolver::fill::<PositiveStrategy, ShapeCountBoolean>(is_list, segments),
-            FillRule::Negative => FillSolver::fill::<NegativeStrategy, ShapeCountBoolean>(is_list, segments),
+            FillRule::EvenOdd => FillSolver::fill_with_buffer_into::<EvenOddStrategy, ShapeCountBoolean>(is_list, segments, buffer, fills),
+            FillRule::NonZero => FillSolver::fill_with_buffer_into::<NonZeroStrategy, ShapeCountBoolean>(is_list, segments, buffer, fills),
+            FillRule::Positive => FillSolver::fill_with_buffer_into::<PositiveStrategy, ShapeCountBoolean>(is_list, segments, buffer, fills),
+            FillRule::Negative => FillSolver::fill_with_buffer_into::<NegativeStrategy, ShapeCountBoolean>(is_list, segments, buffer, fills),
         }
     }
 
@@ -111,6 +193,21 @@ impl OverlayLinkBuilder {
         Self::build_links::<F, ShapeCountBoolean>(&segments, &fills)
     }
 
+    fn build_boolean_into<F: InclusionFilterStrategy>(
+        segments: Vec<Segment<ShapeCountBoolean>>,
+        fill_rule: FillRule,
+        solver: Solver,
+        buffer: &mut LinkBuffer,
+    ) -> Vec<Segment<ShapeCountBoolean>> {
+        buffer.links.clear();
+        if segments.is_empty() { return segments; }
+        let segments = segments.split_segments_with_buffer(solver, &mut buffer.split);
+        if segments.is_empty() { return segments; }
+        Self::fill_boolean_into(&segments, fill_rule, solver, &mut buffer.fill, &mut buffer.fills);
+        Self::build_links_into::<F, ShapeCountBoolean>(&segments, &buffer.fills, &mut buffer.links);
+        segments
+    }
+
     fn build_boolean_all(segments: Vec<Segment<ShapeCountBoolean>>, fill_rule: FillRule, solver: Solver) -> Vec<OverlayLink> {
         if segments.is_empty() { return vec![]; }
         let segments = segments.split_segments(solver);
@@ -140,6 +237,21 @@ impl OverlayLinkBuilder {
         links
     }
 
+    fn build_links_into<F: InclusionFilterStrategy, C: Send>(segments: &[Segment<C>], fills: &[SegmentFill], links: &mut Vec<OverlayLink>) {
+        let n = fills.iter().fold(0, |s, &fill| s + F::is_included(fill) as usize);
+
+        links.clear();
+        links.reserve(n);
+
+        for (j, &fill) in fills.iter().enumerate() {
+            if !F::is_included(fill) {
+                continue;
+            }
+            let segment = unsafe { segments.get_unchecked(j) };
+            links.push(OverlayLink::new(IdPoint::new(0, segment.x_segment.a), IdPoint::new(0, segment.x_segment.b), fill));
+        }
+    }
+
     pub(crate) fn build_all_links<C: Send>(segments: &[Segment<C>], fills: &[SegmentFill]) -> Vec<OverlayLink> {
         let empty_id = IdPoint::new(0, IntPoint::ZERO);
         let empty_link = OverlayLink::new(empty_id, empty_id, 0);
@@ -219,6 +331,22 @@ impl FillStrategy<ShapeCountBoolean> for NegativeStrategy {
     }
 }
 
+/// Fills the subject by `S` and the clip by the positive rule.
+struct PositiveClipStrategy<S>(PhantomData<S>);
+
+impl<S: FillStrategy<ShapeCountBoolean>> FillStrategy<ShapeCountBoolean> for PositiveClipStrategy<S> {
+    #[inline(always)]
+    fn add_and_fill(this: ShapeCountBoolean, bot: ShapeCountBoolean) -> (ShapeCountBoolean, SegmentFill) {
+        let (top, subj_fill) = S::add_and_fill(this, bot);
+        let clip_top = (top.clip < 0) as SegmentFill;
+        let clip_bot = (bot.clip < 0) as SegmentFill;
+
+        let fill = (subj_fill & SUBJ_BOTH) | (clip_top << 2) | (clip_bot << 3);
+
+        (top, fill)
+    }
+}
+
 pub(crate) struct EvenOddStrategyString;
 pub(crate) struct NonZeroStrategyString;
 pub(crate) struct PositiveStrategyString;
@@ -282,4 +410,13 @@ impl FillStrategy<ShapeCountString> for NegativeStrategyString {
 
         (top, fill)
     }
-}
\ No newline at end of file
+}
+
+impl<S: FillStrategy<ShapeCountString>> FillStrategy<ShapeCountSource> for S {
+    #[inline(always)]
+    fn add_and_fill(this: ShapeCountSource, bot: ShapeCountSource) -> (ShapeCountSource, SegmentFill) {
+        // the sources do not affect the fill
+        let (count, fill) = S::add_and_fill(this.count, bot.count);
+        (ShapeCountSource { count, ..this }, fill)
+    }
+}
@@ -3,10 +3,13 @@ pub mod solver;
 pub mod graph;
 pub mod overlay_rule;
 pub mod extract;
+pub mod flat;
+pub mod context;
 pub mod fill_rule;
 pub mod simplify;
 pub(crate) mod link;
 pub(crate) mod node;
 pub(crate) mod filter;
 pub(crate) mod nearest_vector;
-pub mod divide;
\ No newline at end of file
+pub mod divide;
+pub mod trapezoid;
\ No newline at end of file
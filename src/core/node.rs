@@ -13,4 +13,17 @@ impl OverlayNode {
             Self::Cross(indices.to_vec())
         }
     }
-}
\ No newline at end of file
+
+    /// Replaces the node with `indices`, reusing the memory of a cross node.
+    #[inline]
+    pub(super) fn reset(&mut self, indices: &[usize]) {
+        if indices.len() == 2 {
+            *self = Self::Bridge(unsafe { [*indices.get_unchecked(0), *indices.get_unchecked(1)] });
+        } else if let Self::Cross(cross) = self {
+            cross.clear();
+            cross.extend_from_slice(indices);
+        } else {
+            *self = Self::Cross(indices.to_vec());
+        }
+    }
+}
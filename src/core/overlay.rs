@@ -6,11 +6,15 @@ use i_float::int::point::IntPoint;
 use i_shape::int::count::PointsCount;
 use i_shape::int::shape::{IntContour, IntShape, IntShapes};
 
+use crate::core::context::OverlayContext;
 use crate::core::fill_rule::FillRule;
+use crate::core::flat::FlatShapes;
 use crate::core::link::OverlayLinkBuilder;
 use crate::core::overlay_rule::OverlayRule;
 
 use crate::core::solver::Solver;
+#[cfg(feature = "record")]
+use crate::record::Recorder;
 use crate::segm::build::BuildSegments;
 use crate::segm::segment::Segment;
 use crate::segm::winding_count::ShapeCountBoolean;
@@ -23,6 +27,7 @@ use super::graph::OverlayGraph;
 /// - `Subject`: The primary shape(s) for operations. Acts as the base layer in the operation.
 /// - `Clip`: The modifying shape(s) that are applied to the `Subject`. Determines how the `Subject` is altered or intersected.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShapeType {
     Subject,
     Clip,
@@ -32,6 +37,8 @@ pub enum ShapeType {
 #[derive(Clone)]
 pub struct Overlay {
     pub(crate) segments: Vec<Segment<ShapeCountBoolean>>,
+    #[cfg(feature = "record")]
+    pub(crate) recorder: Option<Recorder>,
 }
 
 impl Overlay {
@@ -41,6 +48,8 @@ impl Overlay {
     pub fn new(capacity: usize) -> Self {
         Self {
             segments: Vec::with_capacity(capacity),
+            #[cfg(feature = "record")]
+            recorder: None,
         }
     }
 
@@ -119,11 +128,19 @@ impl Overlay {
         }
     }
 
+    /// Removes all added paths and keeps the segment buffer for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.segments.clear();
+    }
+
     /// Convert into vector shapes from the added paths or shapes, applying the specified fill and overlay rules. This method is particularly useful for development purposes and for creating visualizations in educational demos, where understanding the impact of different rules on the final geometry is crucial.
     /// - `fill_rule`: The fill rule to use for the shapes.
     /// - `overlay_rule`: The overlay rule to apply.
     /// - `solver`: Type of solver to use.
     pub fn into_shape_vectors(self, fill_rule: FillRule, overlay_rule: OverlayRule, solver: Solver) -> Vec<VectorShape> {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, Some(overlay_rule), 0, solver);
         let links = OverlayLinkBuilder::build_with_overlay_filter(self.segments, fill_rule, overlay_rule, solver);
         let graph = OverlayGraph::new(solver, links);
         graph.extract_shape_vectors(overlay_rule)
@@ -133,6 +150,8 @@ impl Overlay {
     /// - `fill_rule`: The fill rule to use for the shapes.
     /// - `solver`: Type of solver to use.
     pub fn into_separate_vectors(self, fill_rule: FillRule, solver: Solver) -> Vec<VectorEdge> {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, None, 0, solver);
         let links = OverlayLinkBuilder::build_without_filter(self.segments, fill_rule, solver);
         OverlayGraph::new(solver, links).extract_separate_vectors()
     }
@@ -149,6 +168,8 @@ impl Overlay {
     /// - `solver`: Type of solver to use.
     #[inline]
     pub fn into_graph_with_solver(self, fill_rule: FillRule, solver: Solver) -> OverlayGraph {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, None, 0, solver);
         let links = OverlayLinkBuilder::build_with_filler_filter(self.segments, fill_rule, solver);
         OverlayGraph::new(solver, links)
     }
@@ -219,9 +240,44 @@ impl Overlay {
     /// particularly for complex or resource-intensive geometries.
     #[inline]
     pub fn overlay_with_min_area_and_solver(self, overlay_rule: OverlayRule, fill_rule: FillRule, min_area: usize, solver: Solver) -> IntShapes {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, Some(overlay_rule), min_area, solver);
         let links = OverlayLinkBuilder::build_with_overlay_filter(self.segments, fill_rule, overlay_rule, solver);
         let graph = OverlayGraph::new(solver, links);
         let filter = vec![false; graph.links.len()];
         graph.extract(filter, overlay_rule, min_area)
     }
-}
\ No newline at end of file
+
+    /// Executes a single Boolean operation like `overlay_with_min_area_and_solver`, but the clip is filled
+    /// by the positive rule whatever `fill_rule` is, `fill_rule` applies to the subject only.
+    pub(crate) fn overlay_with_positive_clip(self, overlay_rule: OverlayRule, fill_rule: FillRule, min_area: usize, solver: Solver) -> IntShapes {
+        let links = OverlayLinkBuilder::build_with_positive_clip(self.segments, fill_rule, overlay_rule, solver);
+        let graph = OverlayGraph::new(solver, links);
+        let filter = vec![false; graph.links.len()];
+        graph.extract(filter, overlay_rule, min_area)
+    }
+
+    /// Executes a single Boolean operation like `overlay_with_min_area_and_solver`, but borrows its buffers from `context`
+    /// and writes the result into `output`. The overlay is left empty and keeps its segment buffer, so it can be filled
+    /// with the next paths, which makes repeated operations allocation-free once the buffers have grown.
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes.
+    /// - `min_area`: The minimum area threshold for shapes to be included in the result.
+    /// - `solver`: Type of solver to use.
+    /// - `context`: Reusable buffers shared between operations.
+    /// - `output`: Receives the resulting shapes, it is cleared first.
+    pub fn overlay_with_context(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        min_area: usize,
+        solver: Solver,
+        context: &mut OverlayContext,
+        output: &mut FlatShapes<IntPoint>,
+    ) {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, Some(overlay_rule), min_area, solver);
+        let segments = std::mem::take(&mut self.segments);
+        self.segments = context.overlay(segments, overlay_rule, fill_rule, min_area, solver, output);
+    }
+}
@@ -10,6 +10,7 @@ use crate::segm::segment::{SegmentFill, BOTH_BOTTOM, BOTH_TOP, CLIP_TOP, NONE, S
 /// - `InverseDifference`: Subtracts the area of the subject shape from the clip shape, removing the subject shape's area from the clip.
 /// - `Xor`: Produces a shape consisting of areas unique to each shape, excluding any parts where the subject and clip overlap.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OverlayRule {
     Subject,
     Clip,
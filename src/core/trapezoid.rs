@@ -0,0 +1,390 @@
+use std::cmp::Ordering;
+use i_float::int::point::IntPoint;
+use i_float::triangle::Triangle;
+use i_tree::node::EMPTY_REF;
+use i_tree::tree::Tree;
+use crate::core::fill_rule::FillRule;
+use crate::core::link::{OverlayLink, OverlayLinkBuilder};
+use crate::core::overlay::Overlay;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::geom::x_segment::XSegment;
+use crate::string::line::IntLine;
+use crate::util::log::Int;
+
+/// A trapezoid of the vertical decomposition, it is bounded by two vertical lines `min_x` and `max_x`
+/// and by two non-vertical boundary segments `bottom` and `top`.
+///
+/// The segments are stored as they are in the boundary, so they may extend beyond the x-range.
+/// The first point of a segment is always to the left of the second one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntTrapezoid {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub bottom: IntLine,
+    pub top: IntLine,
+}
+
+impl Overlay {
+    /// Splits the result of a boolean operation into vertical trapezoids.
+    ///
+    /// A vertical line is drawn through every vertex up and down to the nearest boundary segments,
+    /// the filled parts between such lines are the trapezoids. Triangles are trapezoids with a collapsed side.
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes.
+    ///
+    /// Returns trapezoids ordered by their right side `max_x`.
+    #[inline]
+    pub fn into_trapezoids(self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Vec<IntTrapezoid> {
+        self.into_trapezoids_with_solver(overlay_rule, fill_rule, Default::default())
+    }
+
+    /// Splits the result of a boolean operation into vertical trapezoids.
+    ///
+    /// A vertical line is drawn through every vertex up and down to the nearest boundary segments,
+    /// the filled parts between such lines are the trapezoids. Triangles are trapezoids with a collapsed side.
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// Returns trapezoids ordered by their right side `max_x`.
+    pub fn into_trapezoids_with_solver(self, overlay_rule: OverlayRule, fill_rule: FillRule, solver: Solver) -> Vec<IntTrapezoid> {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, Some(overlay_rule), 0, solver);
+        let links = OverlayLinkBuilder::build_with_overlay_filter(self.segments, fill_rule, overlay_rule, solver);
+        TrapezoidSweep::decompose(&links, overlay_rule)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ActiveSegment {
+    x_segment: XSegment,
+    // the area above the segment is filled
+    is_fill_top: bool,
+    // the left side of the area between this segment and the next one
+    start_x: i32,
+}
+
+impl Eq for ActiveSegment {}
+
+impl PartialEq<Self> for ActiveSegment {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.x_segment == other.x_segment
+    }
+}
+
+impl PartialOrd<Self> for ActiveSegment {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ActiveSegment {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.x_segment == other.x_segment {
+            Ordering::Equal
+        } else if self.x_segment.is_under_segment(&other.x_segment) {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }
+}
+
+/// The segments crossing the sweep line ordered from bottom to top, the same tree as the fill and bind scans use.
+struct ScanTrapezoidTree {
+    tree: Tree<ActiveSegment>,
+}
+
+impl ScanTrapezoidTree {
+    #[inline]
+    fn new(count: usize) -> Self {
+        let x_segment = XSegment { a: IntPoint::ZERO, b: IntPoint::ZERO };
+        let empty = ActiveSegment { x_segment, is_fill_top: false, start_x: 0 };
+        Self { tree: Tree::new(empty, count.log2_sqrt()) }
+    }
+
+    /// Appends the nearest segment under the point and all segments passing through it.
+    fn cut_by_point(&self, p: IntPoint, cut: &mut Vec<ActiveSegment>, stack: &mut Vec<u32>) {
+        let mut index = self.tree.root;
+        let mut under = EMPTY_REF;
+        while index != EMPTY_REF {
+            let node = self.tree.node(index);
+            if order(&node.value.x_segment, p) == Ordering::Less {
+                under = index;
+                index = node.right;
+            } else {
+                index = node.left;
+            }
+        }
+        if under != EMPTY_REF {
+            cut.push(self.tree.node(under).value.clone());
+        }
+
+        stack.clear();
+        stack.push(self.tree.root);
+        while let Some(index) = stack.pop() {
+            if index == EMPTY_REF {
+                continue;
+            }
+            let node = self.tree.node(index);
+            match order(&node.value.x_segment, p) {
+                Ordering::Less => stack.push(node.right),
+                Ordering::Greater => stack.push(node.left),
+                Ordering::Equal => {
+                    cut.push(node.value.clone());
+                    stack.push(node.left);
+                    stack.push(node.right);
+                }
+            }
+        }
+    }
+
+    /// Returns the nearest segment above the segment.
+    fn above(&self, segment: &ActiveSegment) -> Option<&ActiveSegment> {
+        let mut index = self.tree.root;
+        let mut result = None;
+        while index != EMPTY_REF {
+            let node = self.tree.node(index);
+            if segment < &node.value {
+                result = Some(&node.value);
+                index = node.left;
+            } else {
+                index = node.right;
+            }
+        }
+        result
+    }
+}
+
+struct TrapezoidSweep {
+    scan: ScanTrapezoidTree,
+    cut: Vec<ActiveSegment>,
+    stack: Vec<u32>,
+    result: Vec<IntTrapezoid>,
+}
+
+impl TrapezoidSweep {
+    fn decompose(links: &[OverlayLink], overlay_rule: OverlayRule) -> Vec<IntTrapezoid> {
+        let mut points: Vec<IntPoint> = Vec::with_capacity(2 * links.len());
+        for link in links.iter() {
+            points.push(link.a.point);
+            points.push(link.b.point);
+        }
+        points.sort_unstable();
+        points.dedup();
+
+        let mut sweep = Self {
+            scan: ScanTrapezoidTree::new(links.len()),
+            cut: Vec::new(),
+            stack: Vec::new(),
+            result: Vec::new(),
+        };
+
+        let mut i = 0;
+        let mut j = 0;
+        while i < points.len() {
+            let x = points[i].x;
+
+            sweep.cut.clear();
+            while i < points.len() && points[i].x == x {
+                sweep.scan.cut_by_point(points[i], &mut sweep.cut, &mut sweep.stack);
+                i += 1;
+            }
+
+            sweep.close_cut(x);
+
+            while j < links.len() && links[j].a.point.x == x {
+                let link = &links[j];
+                if link.a.point.x != link.b.point.x {
+                    sweep.insert(link, overlay_rule, x);
+                }
+                j += 1;
+            }
+        }
+
+        sweep.result
+    }
+
+    /// Emits filled areas which are cut at x and removes finished segments.
+    fn close_cut(&mut self, x: i32) {
+        self.cut.sort_unstable();
+        self.cut.dedup();
+
+        for s in self.cut.iter() {
+            if s.is_fill_top && s.start_x < x {
+                // a filled area is always closed from above
+                let top = self.scan.above(s);
+                debug_assert!(top.is_some(), "a filled area must have a top segment");
+                if let Some(top) = top {
+                    self.result.push(IntTrapezoid {
+                        min_x: s.start_x,
+                        max_x: x,
+                        bottom: [s.x_segment.a, s.x_segment.b],
+                        top: [top.x_segment.a, top.x_segment.b],
+                    });
+                }
+            }
+        }
+
+        for s in self.cut.iter() {
+            if s.x_segment.b.x == x {
+                self.scan.tree.delete(s);
+            } else {
+                let index = self.scan.tree.find_index(s.clone());
+                self.scan.tree.mut_node(index).value.start_x = x;
+            }
+        }
+    }
+
+    fn insert(&mut self, link: &OverlayLink, overlay_rule: OverlayRule, x: i32) {
+        self.scan.tree.insert(ActiveSegment {
+            x_segment: XSegment { a: link.a.point, b: link.b.point },
+            is_fill_top: overlay_rule.is_fill_top(link.fill),
+            start_x: x,
+        });
+    }
+}
+
+/// Compares an active segment with a point on the sweep line.
+#[inline]
+fn order(s: &XSegment, p: IntPoint) -> Ordering {
+    if s.b.x == p.x {
+        s.b.y.cmp(&p.y)
+    } else if s.a.x == p.x {
+        s.a.y.cmp(&p.y)
+    } else {
+        0.cmp(&Triangle::area_two_point(s.a, p, s.b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_shape::int::path::PointPathExtension;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay::Overlay;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::core::trapezoid::IntTrapezoid;
+
+    /// Double area of a trapezoid.
+    fn area(t: &IntTrapezoid) -> i64 {
+        let y = |s: &[IntPoint; 2], x: i32| -> f64 {
+            let [a, b] = *s;
+            a.y as f64 + (b.y - a.y) as f64 * (x - a.x) as f64 / (b.x - a.x) as f64
+        };
+        let w = (t.max_x - t.min_x) as f64;
+        let h0 = y(&t.top, t.min_x) - y(&t.bottom, t.min_x);
+        let h1 = y(&t.top, t.max_x) - y(&t.bottom, t.max_x);
+        assert!(h0 >= 0.0 && h1 >= 0.0);
+        ((h0 + h1) * w).round() as i64
+    }
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x, y + size),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x + size, y),
+        ]
+    }
+
+    #[test]
+    fn test_square() {
+        let trapezoids = Overlay::with_contour(&square(0, 0, 10), &[])
+            .into_trapezoids(OverlayRule::Subject, FillRule::NonZero);
+
+        assert_eq!(trapezoids.len(), 1);
+        assert_eq!(trapezoids[0].min_x, 0);
+        assert_eq!(trapezoids[0].max_x, 10);
+        assert_eq!(trapezoids[0].bottom, [IntPoint::new(0, 0), IntPoint::new(10, 0)]);
+        assert_eq!(trapezoids[0].top, [IntPoint::new(0, 10), IntPoint::new(10, 10)]);
+    }
+
+    #[test]
+    fn test_square_with_hole() {
+        let mut hole = square(3, 3, 4);
+        hole.reverse();
+        let trapezoids = Overlay::with_contours(&[square(0, 0, 10), hole], &[])
+            .into_trapezoids(OverlayRule::Subject, FillRule::NonZero);
+
+        // left, bottom, top and right
+        assert_eq!(trapezoids.len(), 4);
+        assert_eq!(trapezoids.iter().map(area).sum::<i64>(), 2 * (100 - 16));
+    }
+
+    #[test]
+    fn test_triangle() {
+        let triangle = [IntPoint::new(0, 0), IntPoint::new(5, 10), IntPoint::new(10, 0)];
+        let trapezoids = Overlay::with_contour(&triangle, &[])
+            .into_trapezoids(OverlayRule::Subject, FillRule::NonZero);
+
+        assert_eq!(trapezoids.len(), 2);
+        assert_eq!(trapezoids.iter().map(area).sum::<i64>(), 100);
+    }
+
+    #[test]
+    fn test_difference() {
+        let trapezoids = Overlay::with_contour(&square(0, 0, 10), &square(5, 5, 10))
+            .into_trapezoids(OverlayRule::Difference, FillRule::NonZero);
+
+        assert_eq!(trapezoids.len(), 2);
+        assert_eq!(trapezoids.iter().map(area).sum::<i64>(), 2 * 75);
+    }
+
+    #[test]
+    fn test_notch() {
+        let path = [
+            IntPoint::new(0, 0),
+            IntPoint::new(0, 10),
+            IntPoint::new(20, 10),
+            IntPoint::new(20, 0),
+            IntPoint::new(15, 0),
+            IntPoint::new(10, 5),
+            IntPoint::new(5, 0),
+        ];
+        let trapezoids = Overlay::with_contour(&path, &[])
+            .into_trapezoids(OverlayRule::Subject, FillRule::NonZero);
+
+        assert_eq!(trapezoids.iter().map(area).sum::<i64>(), 2 * (200 - 25));
+        // every vertex cuts the area above the notch
+        assert_eq!(trapezoids.len(), 4);
+        assert!(trapezoids.iter().any(|t| t.min_x == 5 && t.max_x == 10));
+    }
+
+    #[test]
+    fn test_diamond_grid() {
+        let diamonds: Vec<_> = (0..64)
+            .map(|i| {
+                let (cx, cy, r) = (7 * (i % 8), 7 * (i / 8) + (i % 3), 5 + (i % 2));
+                vec![
+                    IntPoint::new(cx - r, cy),
+                    IntPoint::new(cx, cy + r),
+                    IntPoint::new(cx + r, cy),
+                    IntPoint::new(cx, cy - r),
+                ]
+            })
+            .collect();
+
+        for overlay_rule in [OverlayRule::Subject, OverlayRule::Xor] {
+            let (subj, clip) = diamonds.split_at(32);
+            let shapes = Overlay::with_contours(subj, clip).overlay(overlay_rule, FillRule::NonZero);
+            let trapezoids = Overlay::with_contours(subj, clip).into_trapezoids(overlay_rule, FillRule::NonZero);
+
+            let expected: i64 = shapes.iter().flatten().map(|contour| contour.unsafe_area()).sum();
+            let actual: i64 = trapezoids.iter().map(area).sum();
+            assert!((expected - actual).abs() <= trapezoids.len() as i64);
+            assert!(trapezoids.windows(2).all(|w| w[0].max_x <= w[1].max_x));
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let trapezoids = Overlay::new(0).into_trapezoids(OverlayRule::Subject, FillRule::NonZero);
+        assert!(trapezoids.is_empty());
+    }
+}
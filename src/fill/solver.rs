@@ -1,3 +1,6 @@
+use crate::fill::count_segment::CountSegment;
+use crate::fill::solver_tree::ScanFillTree;
+use crate::geom::end::End;
 use crate::segm::segment::{Segment, SegmentFill};
 use crate::segm::winding_count::WindingCount;
 
@@ -11,10 +14,36 @@ impl FillSolver {
 
     #[inline]
     pub(crate) fn fill<F: FillStrategy<C>, C: WindingCount>(is_list: bool, segments: &[Segment<C>]) -> Vec<SegmentFill> {
+        let mut result = Vec::new();
+        Self::fill_into::<F, C>(is_list, segments, &mut result);
+        result
+    }
+
+    #[inline]
+    pub(crate) fn fill_into<F: FillStrategy<C>, C: WindingCount>(is_list: bool, segments: &[Segment<C>], result: &mut Vec<SegmentFill>) {
+        Self::fill_with_buffer_into::<F, C>(is_list, segments, &mut FillBuffer::default(), result)
+    }
+
+    #[inline]
+    pub(crate) fn fill_with_buffer_into<F: FillStrategy<C>, C: WindingCount>(is_list: bool, segments: &[Segment<C>], buffer: &mut FillBuffer<C>, result: &mut Vec<SegmentFill>) {
         if is_list {
-            Self::list_fill::<F, C>(segments)
+            Self::list_fill::<F, C>(segments, buffer, result)
         } else {
-            Self::tree_fill::<F, C>(segments)
+            Self::tree_fill::<F, C>(segments, buffer, result)
         }
     }
+}
+
+/// The scan buffers of the fill, they can be kept between fills to reuse their memory.
+pub(crate) struct FillBuffer<C> {
+    pub(crate) ends: Vec<End>,
+    pub(super) list: Vec<CountSegment<C>>,
+    pub(super) tree: Option<ScanFillTree<C>>,
+}
+
+impl<C> Default for FillBuffer<C> {
+    #[inline]
+    fn default() -> Self {
+        Self { ends: Vec::new(), list: Vec::new(), tree: None }
+    }
 }
\ No newline at end of file
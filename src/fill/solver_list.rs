@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 use i_float::int::point::IntPoint;
 use i_float::triangle::Triangle;
 use crate::fill::count_segment::CountSegment;
-use crate::fill::solver::{FillSolver, FillStrategy};
+use crate::fill::solver::{FillBuffer, FillSolver, FillStrategy};
 use crate::geom::end::End;
 use crate::segm::segment::{Segment, SegmentFill, NONE};
 use crate::segm::winding_count::WindingCount;
@@ -14,8 +14,10 @@ struct ScanFillList<C> {
 
 impl<C: WindingCount> ScanFillList<C> {
     #[inline(always)]
-    fn new(count: usize) -> Self {
-        Self { buffer: Vec::with_capacity(count.log2_sqrt()) }
+    fn with_buffer(mut buffer: Vec<CountSegment<C>>, count: usize) -> Self {
+        buffer.clear();
+        buffer.reserve(count.log2_sqrt());
+        Self { buffer }
     }
 
     #[inline(always)]
@@ -53,13 +55,15 @@ impl<C: WindingCount> ScanFillList<C> {
 }
 
 impl FillSolver {
-    pub(super) fn list_fill<F: FillStrategy<C>, C: WindingCount>(segments: &[Segment<C>]) -> Vec<SegmentFill> {
+    pub(super) fn list_fill<F: FillStrategy<C>, C: WindingCount>(segments: &[Segment<C>], buffer: &mut FillBuffer<C>, result: &mut Vec<SegmentFill>) {
         // Mark. self is sorted by x_segment.a
-        let mut scan_list = ScanFillList::new(segments.len());
-        let mut buf = Vec::with_capacity(4);
+        let mut scan_list = ScanFillList::with_buffer(std::mem::take(&mut buffer.list), segments.len());
+        let buf = &mut buffer.ends;
+        buf.clear();
 
         let n = segments.len();
-        let mut result = vec![NONE; n];
+        result.clear();
+        result.resize(n, NONE);
         let mut i = 0;
 
         let mut x0 = 0;
@@ -101,6 +105,6 @@ impl FillSolver {
             buf.clear();
         }
 
-        result
+        buffer.list = scan_list.buffer;
     }
 }
@@ -4,7 +4,7 @@ use i_float::triangle::Triangle;
 use i_tree::node::{Color, EMPTY_REF};
 use i_tree::tree::Tree;
 use crate::fill::count_segment::CountSegment;
-use crate::fill::solver::{FillSolver, FillStrategy};
+use crate::fill::solver::{FillBuffer, FillSolver, FillStrategy};
 use crate::geom::end::End;
 use crate::geom::x_segment::XSegment;
 use crate::segm::segment::{Segment, SegmentFill, NONE};
@@ -24,6 +24,11 @@ impl<C: WindingCount> ScanFillTree<C> {
         Self { tree: Tree::new(CountSegment { count, x_segment }, capacity) }
     }
 
+    #[inline]
+    fn clear(&mut self) {
+        self.tree.clear_all();
+    }
+
     pub(super) fn insert(&mut self, segment: CountSegment<C>) {
         let stop = segment.x_segment.a.x;
         let mut index = self.tree.root;
@@ -102,13 +107,20 @@ impl<C: WindingCount> ScanFillTree<C> {
 
 
 impl FillSolver {
-    pub(super) fn tree_fill<F: FillStrategy<C>, C: WindingCount>(segments: &[Segment<C>]) -> Vec<SegmentFill> {
+    pub(super) fn tree_fill<F: FillStrategy<C>, C: WindingCount>(segments: &[Segment<C>], buffer: &mut FillBuffer<C>, result: &mut Vec<SegmentFill>) {
         // Mark. self is sorted by x_segment.a
-        let mut scan_list = ScanFillTree::new(segments.len());
-        let mut buf = Vec::with_capacity(4);
+        let mut scan_list = if let Some(mut tree) = buffer.tree.take() {
+            tree.clear();
+            tree
+        } else {
+            ScanFillTree::new(segments.len())
+        };
+        let buf = &mut buffer.ends;
+        buf.clear();
 
         let n = segments.len();
-        let mut result = vec![NONE; n];
+        result.clear();
+        result.resize(n, NONE);
         let mut i = 0;
 
         while i < n {
@@ -144,6 +156,6 @@ impl FillSolver {
             buf.clear();
         }
 
-        result
+        buffer.tree = Some(scan_list);
     }
 }
\ No newline at end of file
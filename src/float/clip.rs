@@ -1,11 +1,17 @@
+use std::f64::consts::PI;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
 use i_shape::base::data::Paths;
+use i_shape::float::adapter::{PathToFloat, PathToInt, ShapeToFloat};
+use i_shape::int::path::IntPath;
 use crate::core::fill_rule::FillRule;
 use crate::core::solver::Solver;
 use crate::float::source::resource::OverlayResource;
 use crate::float::string_overlay::FloatStringOverlay;
-use crate::string::clip::ClipRule;
+use crate::mesh::stroke::offset::int_stroke_segments;
+use crate::mesh::style::{LineCap, LineJoin, StrokeStyle};
+use crate::string::overlay::StringOverlay;
+use crate::string::clip::{ClassifiedPiece, ClipPiece, ClipRule};
 
 pub trait FloatClip<R, P, T>
 where
@@ -39,6 +45,60 @@ where
     /// # Returns
     /// A `Paths<P>` collection of string lines that meet the clipping conditions.
     fn clip_by_with_solver(&self, source: &R, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Paths<P>;
+
+    /// Clips paths and tags every piece with the path it was cut from and its position along that path.
+    /// - `resource`: A clipping shape.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path. This path is interpreted as closed, so it doesn’t require the start and endpoint to be the same for processing.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how boundary and inversion settings affect the result.
+    ///
+    /// # Returns
+    /// A vector of `ClipPiece<P>` ordered by the source path and by the position along it.
+    /// The source index follows the order of `iter_paths`.
+    fn clip_by_with_source(&self, source: &R, fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPiece<P>>;
+
+    /// Clips paths and tags every piece with the path it was cut from and its position along that path.
+    /// - `resource`: A clipping shape.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path. This path is interpreted as closed, so it doesn’t require the start and endpoint to be the same for processing.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how boundary and inversion settings affect the result.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A vector of `ClipPiece<P>` ordered by the source path and by the position along it.
+    /// The source index follows the order of `iter_paths`.
+    fn clip_by_with_source_and_solver(&self, source: &R, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Vec<ClipPiece<P>>;
+
+    /// Splits paths at the boundaries of the filled areas and classifies every piece as inside, outside or on the boundary.
+    /// - `resource`: A clipping shape.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path. This path is interpreted as closed, so it doesn’t require the start and endpoint to be the same for processing.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    ///
+    /// # Returns
+    /// A vector of `ClassifiedPiece<P>`, which covers the paths in the path order.
+    fn classify_by(&self, source: &R, fill_rule: FillRule) -> Vec<ClassifiedPiece<P>>;
+
+    /// Splits paths at the boundaries of the filled areas and classifies every piece as inside, outside or on the boundary.
+    /// - `resource`: A clipping shape.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path. This path is interpreted as closed, so it doesn’t require the start and endpoint to be the same for processing.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A vector of `ClassifiedPiece<P>`, which covers the paths in the path order.
+    fn classify_by_with_solver(&self, source: &R, fill_rule: FillRule, solver: Solver) -> Vec<ClassifiedPiece<P>>;
 }
 
 impl<R0, R1, P, T> FloatClip<R0, P, T> for R1
@@ -58,4 +118,216 @@ where
         FloatStringOverlay::with_shape_and_string(resource, self)
             .clip_string_lines_with_solver(fill_rule, clip_rule, solver)
     }
+
+    #[inline]
+    fn clip_by_with_source(&self, resource: &R0, fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPiece<P>> {
+        self.clip_by_with_source_and_solver(resource, fill_rule, clip_rule, Default::default())
+    }
+
+    fn clip_by_with_source_and_solver(&self, resource: &R0, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Vec<ClipPiece<P>> {
+        let float_overlay = FloatStringOverlay::with_shape_and_string(resource, self);
+        let adapter = float_overlay.adapter;
+        let paths: Vec<IntPath> = self.iter_paths().map(|path| path.to_int(&adapter)).collect();
+
+        float_overlay.overlay
+            .into_graph_with_solver(fill_rule, solver)
+            .clip_sources(paths.iter().map(|path| path.as_slice()), clip_rule)
+            .into_iter()
+            .map(|piece| ClipPiece {
+                path: piece.path.to_float(&adapter),
+                source: piece.source,
+                start: piece.start,
+                end: piece.end,
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn classify_by(&self, resource: &R0, fill_rule: FillRule) -> Vec<ClassifiedPiece<P>> {
+        self.classify_by_with_solver(resource, fill_rule, Default::default())
+    }
+
+    fn classify_by_with_solver(&self, resource: &R0, fill_rule: FillRule, solver: Solver) -> Vec<ClassifiedPiece<P>> {
+        let float_overlay = FloatStringOverlay::with_shape_and_string(resource, self);
+        let adapter = float_overlay.adapter;
+        let paths: Vec<IntPath> = self.iter_paths().map(|path| path.to_int(&adapter)).collect();
+
+        float_overlay.overlay
+            .into_graph_with_solver(fill_rule, solver)
+            .classify_sources(paths.iter().map(|path| path.as_slice()))
+            .into_iter()
+            .map(|piece| ClassifiedPiece {
+                location: piece.location,
+                path: piece.path.to_float(&adapter),
+                source: piece.source,
+                start: piece.start,
+                end: piece.end,
+            })
+            .collect()
+    }
+}
+
+/// The `FloatClipDistance` trait clips string lines by the area within a distance of other lines.
+pub trait FloatClipDistance<R, P, T>
+where
+    R: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Clips string lines by the area within `distance` of other lines.
+    /// The area is the stroke of `resource` and it goes to the same overlay as the clipped lines,
+    /// so it is the same as `stroke` followed by `clip_by`, but with one conversion and one pass.
+    /// The round caps and joins have a step of `0.1π`, so the area is an inscribed polygon
+    /// and it can miss up to `(1 - cos(0.05π))·distance` (about 1.2%) near the ends and corners of `resource`.
+    /// Use `clip_by_stroke_with_solver` with a smaller round angle for a tighter area.
+    /// - `resource`: A string lines that define the area.
+    /// - `distance`: The maximum distance to `resource`.
+    /// - `clip_rule`: The rule for clipping, determining how the lines should be clipped.
+    /// # Returns
+    /// A vector of `Path<P>` instances representing the clipped sections of the input lines.
+    fn clip_by_distance(&self, resource: &R, distance: T, clip_rule: ClipRule) -> Paths<P>;
+
+    /// Clips string lines by the stroke of other lines, the stroke and the lines are split and filled in one pass.
+    /// - `resource`: A string lines that define the area.
+    /// - `style`: The stroke style of `resource`, the stroke is built as for open paths.
+    /// - `clip_rule`: The rule for clipping, determining how the lines should be clipped.
+    /// - `solver`: Type of solver to use.
+    /// # Returns
+    /// A vector of `Path<P>` instances representing the clipped sections of the input lines.
+    fn clip_by_stroke_with_solver(&self, resource: &R, style: StrokeStyle<P, T>, clip_rule: ClipRule, solver: Solver) -> Paths<P>;
+}
+
+impl<R0, R1, P, T> FloatClipDistance<R0, P, T> for R1
+where
+    R0: OverlayResource<P, T>,
+    R1: OverlayResource<P, T>,
+    P: FloatPointCompatible<T> + 'static,
+    T: FloatNumber + 'static,
+{
+    fn clip_by_distance(&self, resource: &R0, distance: T, clip_rule: ClipRule) -> Paths<P> {
+        let angle = T::from_float(0.1 * PI);
+        let style = StrokeStyle::default()
+            .width(T::from_float(2.0 * distance.to_f64()))
+            .start_cap(LineCap::Round(angle))
+            .end_cap(LineCap::Round(angle))
+            .line_join(LineJoin::Round(angle));
+
+        self.clip_by_stroke_with_solver(resource, style, clip_rule, Default::default())
+    }
+
+    fn clip_by_stroke_with_solver(&self, resource: &R0, style: StrokeStyle<P, T>, clip_rule: ClipRule, solver: Solver) -> Paths<P> {
+        let (stroke, adapter) = int_stroke_segments(resource, style, false, self.iter_paths().flatten());
+
+        // the raw stroke segments go to the string overlay, so it is split and filled once
+        let capacity = stroke.len() + self.iter_paths().fold(0, |s, c| s + c.len());
+        let mut overlay = StringOverlay::new(capacity);
+        overlay.segments.extend(stroke.into_iter().map(|segment| segment.into_string_shape()));
+        for path in self.iter_paths() {
+            overlay.add_string_path(&path.to_int(&adapter));
+        }
+
+        // the stroke area has a negative count and the stroke parts can overlap
+        overlay
+            .clip_string_lines_with_solver(FillRule::Positive, clip_rule, solver)
+            .to_float(&adapter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::float::clip::{FloatClip, FloatClipDistance};
+    use crate::float::string_overlay::FloatStringOverlay;
+    use crate::string::clip::{ClipPath, ClipRule, LinePosition, PieceLocation};
+
+    #[test]
+    fn test_clip_by_with_source() {
+        let rect = [[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let paths = vec![
+            vec![[-1.0, 2.0], [-1.0, 3.0]],
+            vec![[-1.0, 0.5], [3.0, 0.5]],
+        ];
+
+        let result = paths.clip_by_with_source(&rect, FillRule::NonZero, ClipRule { invert: false, boundary_included: false });
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].source, 1);
+        assert_eq!(result[0].path, [[0.0, 0.5], [1.0, 0.5]]);
+        assert_eq!(result[0].start, LinePosition { segment: 0, fraction: 0.25 });
+        assert_eq!(result[0].end, LinePosition { segment: 0, fraction: 0.5 });
+    }
+
+    #[test]
+    fn test_classify_by() {
+        let rect = [[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let path = [[-1.0, 0.5], [3.0, 0.5]];
+
+        let result = path.classify_by(&rect, FillRule::NonZero);
+        let locations: Vec<_> = result.iter().map(|piece| piece.location).collect();
+
+        assert_eq!(locations, [PieceLocation::Outside, PieceLocation::Inside, PieceLocation::Outside]);
+        assert_eq!(result[1].path, [[0.0, 0.5], [1.0, 0.5]]);
+        assert_eq!(result[2].end, LinePosition { segment: 0, fraction: 1.0 });
+    }
+
+    #[test]
+    fn test_clip_by_distance() {
+        let lines = vec![
+            vec![[-10.0f64, 1.0], [10.0, 1.0]],
+            vec![[-10.0, 5.0], [10.0, 5.0]],
+        ];
+        let other = [[0.0, 0.0], [0.0, -10.0]];
+        let clip_rule = ClipRule { invert: false, boundary_included: false };
+
+        let result = lines.clip_by_distance(&other, 2.0, clip_rule);
+
+        assert_eq!(result.len(), 1);
+        let [a, b] = [result[0][0], result[0][result[0].len() - 1]];
+        assert!((a[1] - 1.0).abs() < 1e-6 && (b[1] - 1.0).abs() < 1e-6);
+        assert!((a[0].abs() - 3.0f64.sqrt()).abs() < 0.1);
+        assert!((b[0].abs() - 3.0f64.sqrt()).abs() < 0.1);
+
+        let outside = lines.clip_by_distance(&other, 2.0, ClipRule { invert: true, boundary_included: false });
+        assert_eq!(outside.len(), 3);
+    }
+
+    #[test]
+    fn test_clip_by_distance_overlapped_stroke() {
+        // the strokes of both lines cover the crossing, so the stroke area is counted twice there
+        let line = [[-10.0f64, 0.5], [10.0, 0.5]];
+        let other = vec![
+            vec![[-1.0, -5.0], [1.0, 5.0]],
+            vec![[1.0, -5.0], [-1.0, 5.0]],
+        ];
+        let clip_rule = ClipRule { invert: false, boundary_included: false };
+
+        let result = line.clip_by_distance(&other, 1.0, clip_rule);
+
+        assert_eq!(result.len(), 1);
+        let xs: Vec<f64> = result[0].iter().map(|p| p[0]).collect();
+        let min_x = xs.iter().fold(f64::MAX, |m, &x| m.min(x));
+        let max_x = xs.iter().fold(f64::MIN, |m, &x| m.max(x));
+        assert!(min_x < -1.0 && max_x > 1.0);
+    }
+
+    #[test]
+    fn test_clip_keep_closed() {
+        let rect = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let rings = vec![
+            vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]],
+            // goes out of the rect and out of its bounding box
+            vec![[8.0, 2.0], [8.0, 4.0], [12.0, 4.0], [12.0, 2.0]],
+        ];
+        let clip_rule = ClipRule { invert: false, boundary_included: false };
+
+        let result = FloatStringOverlay::with_shape_and_string_contours(&rect, &rings)
+            .clip_string_lines_keep_closed(FillRule::NonZero, clip_rule);
+
+        assert_eq!(result.len(), 2);
+        assert!(result.contains(&ClipPath { path: rings[0].clone(), is_closed: true }));
+        assert!(result.contains(&ClipPath {
+            path: vec![[10.0, 2.0], [8.0, 2.0], [8.0, 4.0], [10.0, 4.0]],
+            is_closed: false,
+        }));
+    }
 }
@@ -1,6 +1,7 @@
 use i_float::float::number::FloatNumber;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ContourFilter<T> {
     pub min_area: T,
     pub simplify: bool,
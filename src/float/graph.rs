@@ -5,9 +5,11 @@
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
-use i_shape::base::data::Shapes;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::{Shape, Shapes};
 use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::simple::SimplifyContour;
+use crate::core::flat::FlatShapes;
 use crate::core::graph::OverlayGraph;
 use crate::core::overlay_rule::OverlayRule;
 use crate::float::filter::ContourFilter;
@@ -88,4 +90,171 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlayGraph<P, T> {
 
         float
     }
-}
\ No newline at end of file
+
+    /// Extracts shapes like `extract_shapes_with_filter`, but writes them into flat buffers.
+    /// The `output` is cleared first and its memory is reused between calls.
+    ///
+    /// # Parameters
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering and simplification:
+    ///     - `min_area`: Only retain contours with an area larger than this.
+    ///     - `simplify`: Simplifies contours and removes degenerate edges if `true`.
+    /// - `output`: Receives the same shapes as `extract_shapes_with_filter` in the same order.
+    #[inline]
+    pub fn extract_shapes_into(&self, overlay_rule: OverlayRule, filter: ContourFilter<T>, output: &mut FlatShapes<P>) {
+        let area = self.adapter.sqr_float_to_int(filter.min_area);
+        let mut flat = FlatShapes::new();
+        self.graph.extract_shapes_into(overlay_rule, area, &mut flat);
+        output.set_int_flat(&flat, &self.adapter);
+
+        if filter.simplify {
+            output.simplify_contour(&self.adapter);
+        }
+    }
+
+    /// Extracts shapes one by one, converting each of them to float points only when it is requested.
+    /// Use it for large results that are written out shape by shape, so the whole float result never has to be held in memory.
+    /// All contours are traced and holes are attached to their shapes before the first shape is yielded.
+    ///
+    /// # Parameters
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering and simplification:
+    ///     - `min_area`: Only retain contours with an area larger than this.
+    ///     - `simplify`: Simplifies contours and removes degenerate edges if `true`.
+    ///
+    /// # Returns
+    /// A `FloatShapeIter` yielding the same shapes as `extract_shapes_with_filter` in the same order.
+    #[inline]
+    pub fn extract_shapes_iter(&self, overlay_rule: OverlayRule, filter: ContourFilter<T>) -> FloatShapeIter<'_, P, T> {
+        let area = self.adapter.sqr_float_to_int(filter.min_area);
+        let mut flat = FlatShapes::new();
+        self.graph.extract_shapes_into(overlay_rule, area, &mut flat);
+        FloatShapeIter {
+            flat,
+            index: 0,
+            adapter: &self.adapter,
+            simplify: filter.simplify,
+        }
+    }
+
+    /// Passes each extracted shape with its holes to `visitor`, see `extract_shapes_iter`.
+    ///
+    /// # Parameters
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering and simplification.
+    /// - `visitor`: Receives the shapes in the order of `extract_shapes_with_filter`.
+    #[inline]
+    pub fn visit_shapes<F: FnMut(Shape<P>)>(&self, overlay_rule: OverlayRule, filter: ContourFilter<T>, visitor: F) {
+        self.extract_shapes_iter(overlay_rule, filter).for_each(visitor);
+    }
+}
+
+/// An iterator over the shapes of a `FloatOverlayGraph`, created by `FloatOverlayGraph::extract_shapes_iter`.
+/// The int contours are kept in flat buffers, and each shape is converted to float points when it is yielded.
+pub struct FloatShapeIter<'a, P: FloatPointCompatible<T>, T: FloatNumber> {
+    flat: FlatShapes<IntPoint>,
+    index: usize,
+    adapter: &'a FloatPointAdapter<P, T>,
+    simplify: bool,
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> Iterator for FloatShapeIter<'_, P, T> {
+    type Item = Shape<P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.flat.len() {
+            let mut shape: Shape<P> = self.flat
+                .shape(self.index)
+                .map(|contour| contour.iter().map(|p| self.adapter.int_to_float(p)).collect())
+                .collect();
+            self.index += 1;
+            if self.simplify {
+                shape.simplify_contour(self.adapter);
+                if shape.is_empty() {
+                    continue;
+                }
+            }
+            return Some(shape);
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let upper = self.flat.len() - self.index;
+        if self.simplify { (0, Some(upper)) } else { (upper, Some(upper)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::flat::FlatShapes;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::overlay::FloatOverlay;
+
+    #[test]
+    fn test_extract_shapes_iter() {
+        let subj = [
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]],
+            vec![[20.0, 0.0], [20.0, 10.0], [30.0, 10.0], [30.0, 0.0]],
+        ];
+        let clip = [
+            vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]],
+            vec![[22.0, 2.0], [22.0, 4.0], [24.0, 4.0], [24.0, 2.0]],
+            vec![[26.0, 6.0], [26.0, 8.0], [28.0, 8.0], [28.0, 6.0]],
+        ];
+
+        let graph = FloatOverlay::with_subj_and_clip(&subj, &clip).into_graph(FillRule::NonZero);
+        let shapes = graph.extract_shapes(OverlayRule::Difference);
+        let iter_shapes: Vec<_> = graph.extract_shapes_iter(OverlayRule::Difference, Default::default()).collect();
+
+        let mut visited = Vec::new();
+        graph.visit_shapes(OverlayRule::Difference, Default::default(), |shape| visited.push(shape));
+
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(iter_shapes, shapes);
+        assert_eq!(visited, shapes);
+
+        let holes_count: Vec<_> = iter_shapes.iter().map(|shape| shape.len() - 1).collect();
+        assert_eq!(holes_count, [1, 2]);
+    }
+
+    #[test]
+    fn test_visit_int_shapes() {
+        let subj = [vec![[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [4.0, 0.0]]];
+        let clip = [vec![[2.0, 2.0], [2.0, 6.0], [6.0, 6.0], [6.0, 2.0]]];
+
+        let graph = FloatOverlay::with_subj_and_clip(&subj, &clip).into_graph(FillRule::NonZero);
+        let mut count = 0;
+        graph.graph.visit_shapes(OverlayRule::Union, 0, |shape| {
+            assert_eq!(shape.len(), 1);
+            count += 1;
+        });
+
+        assert_eq!(count, 1);
+        assert_eq!(graph.graph.extract_shapes_iter(OverlayRule::Xor, 0).count(), 2);
+    }
+
+    #[test]
+    fn test_extract_shapes_into() {
+        let subj = [vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]]];
+        let clip = [
+            vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]],
+            vec![[6.0, 6.0], [6.0, 8.0], [8.0, 8.0], [8.0, 6.0]],
+            vec![[20.0, 0.0], [20.0, 2.0], [22.0, 2.0], [22.0, 0.0]],
+        ];
+
+        let graph = FloatOverlay::with_subj_and_clip(&subj, &clip).into_graph(FillRule::NonZero);
+        let mut flat = FlatShapes::new();
+
+        graph.extract_shapes_into(OverlayRule::Xor, Default::default(), &mut flat);
+        assert_eq!(flat.len(), 2);
+        assert_eq!(flat.contour_ranges.len(), 4);
+        assert_eq!(flat.to_shapes(), graph.extract_shapes(OverlayRule::Xor));
+
+        graph.extract_shapes_into(OverlayRule::Intersect, Default::default(), &mut flat);
+        assert_eq!(flat.len(), 2);
+        assert_eq!(flat.points.len(), 8);
+    }
+}
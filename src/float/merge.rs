@@ -0,0 +1,72 @@
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::Paths;
+use i_shape::float::adapter::PathToFloat;
+use crate::core::solver::Solver;
+use crate::float::source::resource::OverlayResource;
+use crate::float::string_overlay::FloatStringOverlay;
+
+/// The `FloatMerge` trait joins line pieces that share their endpoints into maximal polylines.
+pub trait FloatMerge<P, T>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Joins the string lines into maximal polylines, which stop at nodes that are not shared by exactly two segments.
+    /// - `respect_direction`: If true, a polyline also stops where the directions of the lines do not follow each other.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of polylines, a closed loop is returned with the same first and last point.
+    fn merge_lines(&self, respect_direction: bool) -> Paths<P>;
+
+    /// Joins the string lines into maximal polylines, which stop at nodes that are not shared by exactly two segments.
+    /// - `respect_direction`: If true, a polyline also stops where the directions of the lines do not follow each other.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of polylines, a closed loop is returned with the same first and last point.
+    fn merge_lines_with_solver(&self, respect_direction: bool, solver: Solver) -> Paths<P>;
+}
+
+impl<R, P, T> FloatMerge<P, T> for R
+where
+    R: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn merge_lines(&self, respect_direction: bool) -> Paths<P> {
+        self.merge_lines_with_solver(respect_direction, Default::default())
+    }
+
+    fn merge_lines_with_solver(&self, respect_direction: bool, solver: Solver) -> Paths<P> {
+        let adapter = FloatPointAdapter::with_iter(self.iter_paths().flatten());
+        let capacity = self.iter_paths().fold(0, |s, c| s + c.len());
+        let float_overlay = FloatStringOverlay::with_adapter(adapter, capacity).unsafe_add_string_lines(self);
+
+        float_overlay.overlay
+            .merge_string_lines_with_solver(respect_direction, solver)
+            .into_iter()
+            .map(|path| path.to_float(&float_overlay.adapter))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::merge::FloatMerge;
+
+    #[test]
+    fn test_merge_lines() {
+        let paths = vec![
+            vec![[1.0, 0.0], [2.0, 0.0]],
+            vec![[0.0, 0.0], [1.0, 0.0]],
+            vec![[2.0, 0.0], [2.0, 1.0]],
+        ];
+
+        let result = paths.merge_lines(true);
+
+        assert_eq!(result, [vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [2.0, 1.0]]]);
+    }
+}
@@ -5,6 +5,11 @@ pub mod string_overlay;
 pub mod string_graph;
 pub mod slice;
 pub mod clip;
+pub mod node;
+pub mod merge;
+pub mod polygonize;
 pub mod single;
 pub mod source;
 pub mod filter;
+
+pub mod trapezoid;
\ No newline at end of file
@@ -0,0 +1,70 @@
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::Paths;
+use i_shape::float::adapter::PathToFloat;
+use crate::core::solver::Solver;
+use crate::float::source::resource::OverlayResource;
+use crate::float::string_overlay::FloatStringOverlay;
+
+/// The `FloatNode` trait splits a line network at all intersections and overlaps.
+pub trait FloatNode<P, T>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Splits the string lines at all intersections and overlaps.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of two point segments, which share their endpoints at the nodes.
+    fn node_lines(&self) -> Paths<P>;
+
+    /// Splits the string lines at all intersections and overlaps.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of two point segments, which share their endpoints at the nodes.
+    fn node_lines_with_solver(&self, solver: Solver) -> Paths<P>;
+}
+
+impl<R, P, T> FloatNode<P, T> for R
+where
+    R: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn node_lines(&self) -> Paths<P> {
+        self.node_lines_with_solver(Default::default())
+    }
+
+    fn node_lines_with_solver(&self, solver: Solver) -> Paths<P> {
+        let adapter = FloatPointAdapter::with_iter(self.iter_paths().flatten());
+        let capacity = self.iter_paths().fold(0, |s, c| s + c.len());
+        let float_overlay = FloatStringOverlay::with_adapter(adapter, capacity).unsafe_add_string_lines(self);
+
+        float_overlay.overlay
+            .node_string_lines_with_solver(solver)
+            .into_iter()
+            .map(|segment| segment.to_float(&float_overlay.adapter))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::node::FloatNode;
+
+    #[test]
+    fn test_node_lines() {
+        let paths = vec![
+            vec![[0.0, 0.0], [2.0, 2.0]],
+            vec![[0.0, 2.0], [2.0, 0.0]],
+        ];
+
+        let result = paths.node_lines();
+
+        assert_eq!(result.len(), 4);
+        assert!(result.iter().all(|segment| segment.contains(&[1.0, 1.0])));
+    }
+}
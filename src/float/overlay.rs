@@ -8,10 +8,14 @@ use i_float::float::number::FloatNumber;
 use i_shape::base::data::Shapes;
 use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::simple::SimplifyContour;
+use crate::core::context::OverlayContext;
 use crate::core::fill_rule::FillRule;
+use crate::core::flat::FlatShapes;
 use crate::core::overlay::{Overlay, ShapeType};
 use crate::core::overlay_rule::OverlayRule;
 use crate::core::solver::Solver;
+#[cfg(feature = "record")]
+use crate::record::Recorder;
 use crate::float::filter::ContourFilter;
 use crate::float::graph::FloatOverlayGraph;
 use crate::float::source::resource::OverlayResource;
@@ -80,6 +84,26 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlay<P, T> {
             .unsafe_add_source(subj, ShapeType::Subject)
     }
 
+    /// Replaces the content of the overlay with new subject and clip shapes, keeping the allocated segment buffer.
+    /// The adapter is recalculated for the new geometry, see `with_subj_and_clip`.
+    /// - `subj`: A `OverlayResource` that define the subject.
+    /// - `clip`: A `OverlayResource` that define the clip.
+    pub fn reset_with_subj_and_clip<R0, R1>(&mut self, subj: &R0, clip: &R1)
+    where
+        R0: OverlayResource<P, T> +?Sized,
+        R1: OverlayResource<P, T> +?Sized,
+    {
+        let iter = subj.iter_paths().chain(clip.iter_paths()).flatten();
+        self.adapter = FloatPointAdapter::with_iter(iter);
+        self.overlay.clear();
+        for contour in subj.iter_paths() {
+            self.overlay.add_path_iter(contour.iter().map(|p| self.adapter.float_to_int(p)), ShapeType::Subject);
+        }
+        for contour in clip.iter_paths() {
+            self.overlay.add_path_iter(contour.iter().map(|p| self.adapter.float_to_int(p)), ShapeType::Clip);
+        }
+    }
+
     /// Adds a shapes to the overlay.
     /// - `resource`: A `OverlayResource` that define subject or clip.
     ///   `OverlayResource` can be one of the following:
@@ -105,6 +129,15 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlay<P, T> {
         self
     }
 
+    /// Attaches a recorder, every following operation of the overlay is stored into it as a `Record`.
+    /// The paths are recorded in the integer space of the adapter, including those added before the call.
+    #[cfg(feature = "record")]
+    #[inline]
+    pub fn record_to(mut self, recorder: &Recorder) -> Self {
+        self.overlay.record_to(recorder);
+        self
+    }
+
     /// Convert into `FloatOverlayGraph` from the added paths or shapes using the specified fill rule. This graph is the foundation for executing boolean operations, allowing for the analysis and manipulation of the geometric data. The `OverlayGraph` created by this method represents a preprocessed state of the input shapes, optimized for the application of boolean operations based on the provided fill rule.
     /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes, influencing how the resulting graph represents intersections and unions.
     #[inline]
@@ -199,12 +232,48 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlay<P, T> {
 
         float
     }
+
+    /// Executes a single Boolean operation like `overlay_with_filter_and_solver`, but borrows its buffers from `context`
+    /// and writes the result into `output`. The overlay is left empty and keeps its segment buffer,
+    /// refill it with `reset_with_subj_and_clip` for the next operation.
+    ///
+    /// ### Parameters:
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering:
+    ///     - `min_area`: Only retain contours with an area larger than this.
+    ///     - `simplify`: Simplifies contours and removes degenerate edges if `true`.
+    /// - `solver`: Type of solver to use.
+    /// - `context`: Reusable buffers shared between operations.
+    /// - `output`: Receives the resulting shapes, it is cleared first.
+    pub fn overlay_with_context(
+        &mut self,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        filter: ContourFilter<T>,
+        solver: Solver,
+        context: &mut OverlayContext,
+        output: &mut FlatShapes<P>,
+    ) {
+        let area = self.adapter.sqr_float_to_int(filter.min_area);
+        let mut int_shapes = std::mem::take(&mut context.int_shapes);
+        self.overlay.overlay_with_context(overlay_rule, fill_rule, area, solver, context, &mut int_shapes);
+        output.set_int_flat(&int_shapes, &self.adapter);
+        context.int_shapes = int_shapes;
+
+        if filter.simplify {
+            output.simplify_contour(&self.adapter);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::core::context::OverlayContext;
     use crate::core::fill_rule::FillRule;
+    use crate::core::flat::FlatShapes;
     use crate::core::overlay_rule::OverlayRule;
+    use crate::float::filter::ContourFilter;
     use crate::float::overlay::FloatOverlay;
 
     #[test]
@@ -370,4 +439,40 @@ mod tests {
 
         assert_eq!(result.len(), 16);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_overlay_with_context() {
+        let mut context = OverlayContext::new();
+        let subj = [[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let mut overlay = FloatOverlay::with_subj(&subj);
+        let mut result = FlatShapes::new();
+
+        for i in 0..5 {
+            let d = 0.25 * i as f64;
+            let clip = [[0.5 + d, 0.5], [0.5 + d, 1.5], [1.5 + d, 1.5], [1.5 + d, 0.5]];
+
+            overlay.reset_with_subj_and_clip(&subj, &clip);
+            overlay.overlay_with_context(OverlayRule::Difference, FillRule::NonZero, Default::default(), Default::default(), &mut context, &mut result);
+
+            let expected = FloatOverlay::with_subj_and_clip(&subj, &clip).overlay(OverlayRule::Difference, FillRule::NonZero);
+            assert_eq!(result.to_shapes(), expected);
+        }
+    }
+
+    #[test]
+    fn test_overlay_with_context_simplify_f32() {
+        let mut context = OverlayContext::new();
+        let subj = [[1_000_000.0f32, 1_000_000.0], [1_000_000.0, 1_000_004.0], [1_000_004.0, 1_000_004.0], [1_000_004.0, 1_000_000.0]];
+        let clip = [[1_000_001.0f32, 999_999.0], [1_000_001.3, 1_000_002.1], [1_000_005.0, 1_000_002.7]];
+        let filter = ContourFilter { min_area: 0.0, simplify: true };
+
+        let expected = FloatOverlay::with_subj_and_clip(&subj, &clip)
+            .overlay_with_filter_and_solver(OverlayRule::Difference, FillRule::NonZero, filter, Default::default());
+
+        let mut overlay = FloatOverlay::with_subj_and_clip(&subj, &clip);
+        let mut result = FlatShapes::new();
+        overlay.overlay_with_context(OverlayRule::Difference, FillRule::NonZero, filter, Default::default(), &mut context, &mut result);
+
+        assert_eq!(result.to_shapes(), expected);
+    }
+}
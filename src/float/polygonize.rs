@@ -0,0 +1,88 @@
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::float::adapter::{PathToFloat, ShapesToFloat};
+use i_shape::float::simple::SimplifyContour;
+use crate::core::solver::Solver;
+use crate::float::filter::ContourFilter;
+use crate::float::source::resource::OverlayResource;
+use crate::float::string_overlay::FloatStringOverlay;
+use crate::string::polygonize::Polygonized;
+
+/// The `FloatPolygonize` trait builds polygons from an unstructured set of lines.
+pub trait FloatPolygonize<P, T>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Splits the string lines at all intersections and extracts all enclosed faces.
+    ///
+    /// # Returns
+    /// A `Polygonized<P>` result with the faces, the dangling lines and the cut edges.
+    fn polygonize(&self) -> Polygonized<P>;
+
+    /// Splits the string lines at all intersections and extracts all enclosed faces.
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering and simplification:
+    ///     - `min_area`: Only retain face contours with an area larger than this.
+    ///     - `simplify`: Simplifies face contours and removes degenerate edges if `true`.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A `Polygonized<P>` result with the faces, the dangling lines and the cut edges.
+    fn polygonize_with_filter_and_solver(&self, filter: ContourFilter<T>, solver: Solver) -> Polygonized<P>;
+}
+
+impl<R, P, T> FloatPolygonize<P, T> for R
+where
+    R: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn polygonize(&self) -> Polygonized<P> {
+        self.polygonize_with_filter_and_solver(Default::default(), Default::default())
+    }
+
+    fn polygonize_with_filter_and_solver(&self, filter: ContourFilter<T>, solver: Solver) -> Polygonized<P> {
+        let adapter = FloatPointAdapter::with_iter(self.iter_paths().flatten());
+        let capacity = self.iter_paths().fold(0, |s, c| s + c.len());
+        let float_overlay = FloatStringOverlay::with_adapter(adapter, capacity).unsafe_add_string_lines(self);
+        let adapter = &float_overlay.adapter;
+
+        let min_area = adapter.sqr_float_to_int(filter.min_area);
+        let result = float_overlay.overlay.polygonize_with_min_area_and_solver(min_area, solver);
+
+        let mut shapes = result.shapes.to_float(adapter);
+        if filter.simplify {
+            shapes.simplify_contour(adapter);
+        }
+
+        Polygonized {
+            shapes,
+            dangles: result.dangles.iter().map(|path| path.to_float(adapter)).collect(),
+            cut_edges: result.cut_edges.iter().map(|path| path.to_float(adapter)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::polygonize::FloatPolygonize;
+
+    #[test]
+    fn test_polygonize() {
+        let lines = vec![
+            vec![[0.0, 0.0], [2.0, 0.0]],
+            vec![[2.0, 0.0], [2.0, 1.0]],
+            vec![[2.0, 1.0], [0.0, 1.0]],
+            vec![[0.0, 1.0], [0.0, 0.0]],
+            vec![[1.0, -1.0], [1.0, 2.0]],
+        ];
+
+        let result = lines.polygonize();
+
+        assert_eq!(result.shapes.len(), 2);
+        assert_eq!(result.dangles.len(), 2);
+        assert!(result.cut_edges.is_empty());
+    }
+}
@@ -1,12 +1,22 @@
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
+use i_float::adapter::FloatPointAdapter;
 use i_shape::base::data::Shapes;
+use i_shape::float::adapter::{PathToInt, ShapeToFloat, ShapesToFloat};
+use i_shape::float::simple::SimplifyContour;
+use i_shape::int::path::IntPath;
+use i_shape::int::shape::IntShapes;
 use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{Overlay, ShapeType};
 use crate::core::solver::Solver;
+use crate::core::overlay_rule::OverlayRule;
 use crate::float::filter::ContourFilter;
 use crate::float::source::resource::OverlayResource;
 use crate::float::string_overlay::FloatStringOverlay;
+use crate::mesh::stroke::offset::int_stroke_segments;
+use crate::mesh::style::StrokeStyle;
 use crate::string::rule::StringRule;
+use crate::string::slice::{slice_with_sources, SlicePiece};
 
 /// The `FloatSlice` trait provides methods to slice geometric shapes using a given path or set of paths,
 /// allowing for boolean operations based on the specified fill rule.
@@ -43,6 +53,50 @@ where
     ///
     /// Returns a `Shapes<P>` collection representing the sliced geometry.
     fn slice_by_with_filter_and_solver(&self, resource: &R, fill_rule: FillRule, filter: ContourFilter<T>, solver: Solver) -> Shapes<P>;
+
+    /// Slices the current shapes by string lines with a cut of the given width, so the pieces are separated by a gap.
+    ///
+    /// - `resource`: A string lines.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Path`: A path representing a string line.
+    ///     - `Paths`: A collection of paths, each representing a string line.
+    ///     - `Vec<Paths>`: A collection of grouped paths, where each group may consist of multiple paths.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `style`: The stroke style of the cut, its width is the kerf, and its caps and join define the cut ends and corners.
+    ///
+    /// The raw stroke of the cut is subtracted from the shapes in the same overlay pass.
+    /// `fill_rule` applies to the shapes only, the cut is always filled, also where its parts overlap.
+    ///
+    /// Returns a `Shapes<P>` collection representing the separated pieces.
+    /// If the cut is too thin to be represented, the shapes are sliced as by `slice_by`.
+    fn slice_by_with_kerf(&self, resource: &R, fill_rule: FillRule, style: StrokeStyle<P, T>) -> Shapes<P>
+    where
+        P: 'static,
+        T: 'static;
+
+    /// Slices the current shapes by string lines with a cut of the given width, so the pieces are separated by a gap.
+    ///
+    /// - `resource`: A string lines.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Path`: A path representing a string line.
+    ///     - `Paths`: A collection of paths, each representing a string line.
+    ///     - `Vec<Paths>`: A collection of grouped paths, where each group may consist of multiple paths.
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `style`: The stroke style of the cut, its width is the kerf, and its caps and join define the cut ends and corners.
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering and simplification:
+    ///     - `min_area`: Only retain contours with an area larger than this.
+    ///     - `simplify`: Simplifies contours and removes degenerate edges if `true`.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// The raw stroke of the cut is subtracted from the shapes in the same overlay pass.
+    /// `fill_rule` applies to the shapes only, the cut is always filled, also where its parts overlap.
+    ///
+    /// Returns a `Shapes<P>` collection representing the separated pieces.
+    /// If the cut is too thin to be represented, the shapes are sliced as by `slice_by`.
+    fn slice_by_with_kerf_filter_and_solver(&self, resource: &R, fill_rule: FillRule, style: StrokeStyle<P, T>, filter: ContourFilter<T>, solver: Solver) -> Shapes<P>
+    where
+        P: 'static,
+        T: 'static;
 }
 
 
@@ -66,6 +120,117 @@ where
             .into_graph_with_solver(fill_rule, solver)
             .extract_shapes_with_filter(StringRule::Slice, filter)
     }
+
+    #[inline]
+    fn slice_by_with_kerf(&self, resource: &R0, fill_rule: FillRule, style: StrokeStyle<P, T>) -> Shapes<P>
+    where
+        P: 'static,
+        T: 'static,
+    {
+        self.slice_by_with_kerf_filter_and_solver(resource, fill_rule, style, Default::default(), Default::default())
+    }
+
+    fn slice_by_with_kerf_filter_and_solver(&self, resource: &R0, fill_rule: FillRule, style: StrokeStyle<P, T>, filter: ContourFilter<T>, solver: Solver) -> Shapes<P>
+    where
+        P: 'static,
+        T: 'static,
+    {
+        // the cut is built in the same int space as the shapes,
+        // its raw segments are the clip of the difference, so the shapes and the cut are split and filled in one pass
+        let (cut, adapter) = int_stroke_segments(resource, style, false, self.iter_paths().flatten());
+        if cut.is_empty() {
+            return self.slice_by_with_filter_and_solver(resource, fill_rule, filter, solver);
+        }
+
+        let capacity = cut.len() + self.iter_paths().fold(0, |s, c| s + c.len());
+        let mut overlay = Overlay::new(capacity);
+        for path in self.iter_paths() {
+            overlay.add_contour(&path.to_int(&adapter), ShapeType::Subject);
+        }
+        overlay.segments.extend(cut.into_iter().map(|segment| segment.into_clip()));
+
+        let area = adapter.sqr_float_to_int(filter.min_area);
+        let mut shapes = overlay
+            .overlay_with_positive_clip(OverlayRule::Difference, fill_rule, area, solver)
+            .to_float(&adapter);
+
+        if filter.simplify {
+            shapes.simplify_contour(&adapter);
+        }
+
+        shapes
+    }
+}
+
+
+/// The `FloatSliceSources` trait slices shapes by string lines and keeps track of where every piece came from.
+pub trait FloatSliceSources<R, P, T>
+where
+    R: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Slices shapes by string lines like `slice_by` and tags every piece with its source shape and the lines that bound it.
+    /// Overlapping shapes are joined by the fill rule, a piece gets the lowest index of the shapes it comes from.
+    /// The shapes are grouped by `OverlayResource::shape_sizes`, so the paths of a contour resource form a single shape.
+    ///
+    /// - `resource`: A string lines, the line indices follow the order of `iter_paths`.
+    /// - `fill_rule`: Fill rule to determine filled areas of every shape.
+    ///
+    /// Returns a vector of `SlicePiece<P>` in the same order as `slice_by`.
+    fn slice_by_with_sources(&self, resource: &R, fill_rule: FillRule) -> Vec<SlicePiece<P>>;
+
+    /// Slices shapes by string lines like `slice_by` and tags every piece with its source shape and the lines that bound it.
+    /// Overlapping shapes are joined by the fill rule, a piece gets the lowest index of the shapes it comes from.
+    ///
+    /// - `resource`: A string lines, the line indices follow the order of `iter_paths`.
+    /// - `fill_rule`: Fill rule to determine filled areas of every shape.
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering and simplification:
+    ///     - `min_area`: Only retain contours with an area larger than this.
+    ///     - `simplify`: Simplifies contours and removes degenerate edges if `true`.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// Returns a vector of `SlicePiece<P>` in the same order as `slice_by`.
+    fn slice_by_with_sources_filter_and_solver(&self, resource: &R, fill_rule: FillRule, filter: ContourFilter<T>, solver: Solver) -> Vec<SlicePiece<P>>;
+}
+
+impl<R0, R1, P, T> FloatSliceSources<R0, P, T> for R1
+where
+    R0: OverlayResource<P, T>,
+    R1: OverlayResource<P, T> + ?Sized,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn slice_by_with_sources(&self, resource: &R0, fill_rule: FillRule) -> Vec<SlicePiece<P>> {
+        self.slice_by_with_sources_filter_and_solver(resource, fill_rule, Default::default(), Default::default())
+    }
+
+    fn slice_by_with_sources_filter_and_solver(&self, resource: &R0, fill_rule: FillRule, filter: ContourFilter<T>, solver: Solver) -> Vec<SlicePiece<P>> {
+        let iter = self.iter_paths().flatten().chain(resource.iter_paths().flatten());
+        let adapter = FloatPointAdapter::with_iter(iter);
+
+        let mut contours = self.iter_paths().map(|path| path.to_int(&adapter));
+        let shapes: IntShapes = self.shape_sizes().into_iter()
+            .map(|size| contours.by_ref().take(size).collect())
+            .collect();
+        let paths: Vec<IntPath> = resource.iter_paths().map(|path| path.to_int(&adapter)).collect();
+        let min_area = adapter.sqr_float_to_int(filter.min_area);
+
+        let mut pieces = Vec::new();
+        for piece in slice_with_sources(&shapes, &paths, fill_rule, min_area, solver) {
+            let mut shape = piece.shape.to_float(&adapter);
+            if filter.simplify {
+                shape.simplify_contour(&adapter);
+                if shape.is_empty() {
+                    continue;
+                }
+            }
+            pieces.push(SlicePiece { shape, source: piece.source, lines: piece.lines });
+        }
+
+        pieces
+    }
 }
 
 
@@ -73,6 +238,8 @@ where
 mod tests {
     use crate::core::fill_rule::FillRule;
     use crate::float::simplify::SimplifyShape;
+    use crate::float::slice::{FloatSlice, FloatSliceSources};
+    use crate::mesh::style::StrokeStyle;
 
     #[test]
     fn test_contour_slice() {
@@ -95,4 +262,113 @@ mod tests {
         assert_eq!(shapes[0].len(), 1);
         assert_eq!(shapes[0][0].len(), 4);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_slice_by_with_kerf() {
+        let rect = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let line = [[5.0, -5.0], [5.0, 15.0]];
+
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero, FillRule::Positive] {
+            let shapes = rect.slice_by_with_kerf(&line, fill_rule, StrokeStyle::new(1.0));
+
+            assert_eq!(shapes.len(), 2);
+            for shape in shapes.iter() {
+                assert_eq!(shape.len(), 1);
+                let x_max = shape[0].iter().fold(f64::MIN, |m, p| m.max(p[0]));
+                let x_min = shape[0].iter().fold(f64::MAX, |m, p| m.min(p[0]));
+                assert!(x_max <= 4.5 + 1e-6 || x_min >= 5.5 - 1e-6);
+            }
+        }
+
+        let mut reversed = rect;
+        reversed.reverse();
+        let shapes = reversed.slice_by_with_kerf(&line, FillRule::Negative, StrokeStyle::new(1.0));
+        assert_eq!(shapes.len(), 2);
+    }
+
+    #[test]
+    fn test_slice_by_with_long_kerf() {
+        let rect = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let line = [[5.0, -1000.0], [5.0, 1000.0]];
+
+        let shapes = rect.slice_by_with_kerf(&line, FillRule::NonZero, StrokeStyle::new(1.0));
+
+        assert_eq!(shapes.len(), 2);
+        for shape in shapes.iter() {
+            assert_eq!(shape.len(), 1);
+            assert_eq!(shape[0].len(), 4);
+            let x_max = shape[0].iter().fold(f64::MIN, |m, p| m.max(p[0]));
+            let x_min = shape[0].iter().fold(f64::MAX, |m, p| m.min(p[0]));
+            assert!((x_max - 4.5).abs() < 1e-3 || (x_min - 5.5).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_slice_by_with_thin_kerf() {
+        let rect = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let line = [[5.0, -5.0], [5.0, 15.0]];
+
+        let shapes = rect.slice_by_with_kerf(&line, FillRule::NonZero, StrokeStyle::new(0.0));
+
+        assert_eq!(shapes, rect.slice_by(&line, FillRule::NonZero));
+    }
+
+    #[test]
+    fn test_slice_by_with_crossing_kerf() {
+        // the strokes of both lines overlap at the crossing
+        let rect = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let lines = vec![
+            vec![[5.0, -5.0], [5.0, 15.0]],
+            vec![[-5.0, 5.0], [15.0, 5.0]],
+        ];
+
+        for fill_rule in [FillRule::EvenOdd, FillRule::NonZero, FillRule::Positive] {
+            let shapes = rect.slice_by_with_kerf(&lines, fill_rule, StrokeStyle::new(1.0));
+
+            assert_eq!(shapes.len(), 4);
+            for shape in shapes.iter() {
+                assert_eq!(shape.len(), 1);
+                assert_eq!(shape[0].len(), 4);
+            }
+        }
+
+        let mut reversed = rect;
+        reversed.reverse();
+        let shapes = reversed.slice_by_with_kerf(&lines, FillRule::Negative, StrokeStyle::new(1.0));
+        assert_eq!(shapes.len(), 4);
+    }
+
+    #[test]
+    fn test_slice_by_with_sources() {
+        let shapes = [
+            vec![vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]]],
+            vec![vec![[20.0, 0.0], [20.0, 10.0], [30.0, 10.0], [30.0, 0.0]]],
+        ];
+        let lines = vec![
+            vec![[25.0, -5.0], [25.0, 15.0]],
+            vec![[-5.0, 5.0], [5.0, 5.0]],
+        ];
+
+        let pieces = shapes.slice_by_with_sources(&lines, FillRule::NonZero);
+
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].source, 0);
+        assert!(pieces[0].lines.is_empty());
+        assert!(pieces[1..].iter().all(|piece| piece.source == 1 && piece.lines == [0]));
+
+        // the paths of a contour resource form a single shape
+        let contours = [
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]],
+            vec![[2.0, 2.0], [8.0, 2.0], [8.0, 8.0], [2.0, 8.0]],
+        ];
+        let lines = [
+            vec![[5.0, -5.0], [5.0, 15.0]],
+            vec![[5.0, 0.0], [5.0, 20.0]],
+        ];
+
+        let pieces = contours.slice_by_with_sources(&lines, FillRule::NonZero);
+
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(|piece| piece.source == 0 && piece.lines == [0, 1]));
+    }
+}
@@ -1,4 +1,4 @@
 pub mod resource;
 mod single;
-mod double;
+pub(crate) mod double;
 mod triple;
\ No newline at end of file
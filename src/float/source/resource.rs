@@ -12,4 +12,11 @@ where
         Self: 'a;
 
     fn iter_paths(&self) -> Self::ResourceIter<'_>;
+
+    /// Returns the number of paths of every shape, in the order of `iter_paths`.
+    /// By default all paths form a single shape, a resource of shapes overrides it.
+    #[inline]
+    fn shape_sizes(&self) -> Vec<usize> {
+        vec![self.iter_paths().count()]
+    }
 }
\ No newline at end of file
@@ -50,6 +50,11 @@ where
     fn iter_paths(&self) -> Self::ResourceIter<'_> {
         TripleResourceIterator::with_slice(self)
     }
+
+    #[inline]
+    fn shape_sizes(&self) -> Vec<usize> {
+        self.iter().map(|shape| shape.len()).collect()
+    }
 }
 
 impl<P, T, const N: usize> OverlayResource<P, T> for [Vec<Vec<P>>; N]
@@ -66,6 +71,11 @@ where
     fn iter_paths(&self) -> Self::ResourceIter<'_> {
         TripleResourceIterator::with_slice(self)
     }
+
+    #[inline]
+    fn shape_sizes(&self) -> Vec<usize> {
+        self.iter().map(|shape| shape.len()).collect()
+    }
 }
 
 impl<P, T> OverlayResource<P, T> for Vec<Vec<Vec<P>>>
@@ -82,6 +92,11 @@ where
     fn iter_paths(&self) -> Self::ResourceIter<'_> {
         TripleResourceIterator::with_slice(self.as_slice())
     }
+
+    #[inline]
+    fn shape_sizes(&self) -> Vec<usize> {
+        self.iter().map(|shape| shape.len()).collect()
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,8 @@
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
-use i_shape::base::data::Shapes;
-use i_shape::float::adapter::ShapesToFloat;
+use i_shape::base::data::{Paths, Shapes};
+use i_shape::float::adapter::{PathToFloat, ShapesToFloat};
 use i_shape::float::simple::SimplifyContour;
 use crate::float::filter::ContourFilter;
 use crate::string::graph::StringGraph;
@@ -43,7 +43,10 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringGraph<P, T> {
     ///
     /// # Parameters
     /// - `string_rule`: The string operation rule to apply when extracting shapes from the graph, such as slice.
-    /// - `min_area`: The minimum area threshold for shapes to be included in the result. Shapes with an area smaller than this value will be excluded.
+    /// - `filter`: `ContourFilter<T>` for optional contour filtering and simplification:
+    ///     - `min_area`: Only retain contours with an area larger than this.
+    ///     - `simplify`: Simplifies contours and removes degenerate edges if `true`.
+    ///       With `StringRule::Imprint` the collinear imprinted points are kept, only repeated points are removed.
     ///
     /// # Returns
     /// A `Shapes<P>` collection, representing the geometric result of the applied string rule.
@@ -62,9 +65,35 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringGraph<P, T> {
         let mut float = shapes.to_float(&self.adapter);
 
         if filter.simplify {
-            float.simplify_contour(&self.adapter);
+            if string_rule == StringRule::Imprint {
+                // the imprinted points are collinear, so only the points merged by the float conversion are removed
+                let is_same = |a: &P, b: &P| a.x() == b.x() && a.y() == b.y();
+                for contour in float.iter_mut().flatten() {
+                    contour.dedup_by(|a, b| is_same(a, b));
+                    while contour.len() > 1 && is_same(&contour[0], &contour[contour.len() - 1]) {
+                        contour.pop();
+                    }
+                }
+            } else {
+                float.simplify_contour(&self.adapter);
+            }
         }
 
         float
     }
-}
\ No newline at end of file
+
+    /// Extracts the string lines kept by the specified string rule.
+    /// - `string_rule`: The rule used to determine which lines are kept.
+    ///   Only `StringRule::SliceKeepLines` keeps lines, it returns the cut pieces inside the shapes.
+    ///
+    /// # Returns
+    /// A `Paths<P>` collection of polylines in the direction of the source lines.
+    #[inline]
+    pub fn extract_lines(&self, string_rule: StringRule) -> Paths<P> {
+        self.graph
+            .extract_lines(string_rule)
+            .iter()
+            .map(|path| path.to_float(&self.adapter))
+            .collect()
+    }
+}
@@ -2,12 +2,14 @@ use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
 use i_shape::base::data::Paths;
-use i_shape::float::adapter::ShapeToFloat;
+use i_shape::float::adapter::{PathToFloat, ShapeToFloat};
 use crate::core::fill_rule::FillRule;
 use crate::core::solver::Solver;
+#[cfg(feature = "record")]
+use crate::record::Recorder;
 use crate::float::source::resource::OverlayResource;
 use crate::float::string_graph::FloatStringGraph;
-use crate::string::clip::ClipRule;
+use crate::string::clip::{ClipPath, ClipRule};
 use crate::string::overlay::StringOverlay;
 
 /// The `FloatStringOverlay` struct is a builder for overlaying geometric shapes by converting
@@ -60,6 +62,35 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringOverlay<P, T> {
             .unsafe_add_string_lines(string)
     }
 
+    /// Creates a new `FloatStringOverlay` instance and initializes it with a shape and closed string contours.
+    /// The adapter covers both, so the contours may go out of the shape.
+    /// - `shape`: A `OverlayResource` define the shape.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path. This path is interpreted as closed, so it doesn’t require the start and endpoint to be the same for processing.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    /// - `contours`: A `OverlayResource` define the closed string contours, they are kept as rings by `clip_string_lines_keep_closed`.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed string line.
+    ///     - `Contours`: A collection of contours, each representing a closed string line.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    pub fn with_shape_and_string_contours<R0, R1>(shape: &R0, contours: &R1) -> Self
+    where
+        R0: OverlayResource<P, T>,
+        R1: OverlayResource<P, T>,
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        let iter = shape.iter_paths().chain(contours.iter_paths()).flatten();
+        let adapter = FloatPointAdapter::with_iter(iter);
+        let shape_capacity = shape.iter_paths().fold(0, |s, c| s + c.len());
+        let string_capacity = contours.iter_paths().fold(0, |s, c| s + c.len());
+
+        Self::with_adapter(adapter, shape_capacity + string_capacity)
+            .unsafe_add_shapes(shape)
+            .unsafe_add_string_contours(contours)
+    }
+
     /// Adds a shapes to the overlay.
     /// - `source`: A `OverlayResource` that define shape.
     ///   `OverlayResource` can be one of the following:
@@ -89,6 +120,20 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringOverlay<P, T> {
         self
     }
 
+    /// Adds a closed string line contours to the overlay.
+    /// - `resource`: A `OverlayResource` that define the contours.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed string line.
+    ///     - `Contours`: A collection of contours, each representing a closed string line.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    #[inline]
+    pub fn unsafe_add_string_contours<S: OverlayResource<P, T>>(mut self, resource: &S) -> Self {
+        for contour in resource.iter_paths() {
+            self = self.unsafe_add_string_contour(contour);
+        }
+        self
+    }
+
     /// Adds a closed shape path to the overlay.
     /// - `contour`: An array of points that form a closed path.
     /// - **Safety**: Marked `unsafe` because it assumes the path is fully contained within the bounding box.
@@ -112,6 +157,26 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringOverlay<P, T> {
         self
     }
 
+    /// Adds a closed string line contour to the overlay.
+    /// - `contour`: An array of points that form a closed string line, it doesn’t require the start and endpoint to be the same.
+    /// - **Safety**: Marked `unsafe` because it assumes the contour is fully contained within the bounding box,
+    ///   `with_shape_and_string_contours` builds the adapter over the contours as well.
+    #[inline]
+    pub fn unsafe_add_string_contour(mut self, contour: &[P]) -> Self {
+        let path: Vec<_> = contour.iter().map(|p| self.adapter.float_to_int(p)).collect();
+        self.overlay.add_string_contour(&path);
+        self
+    }
+
+    /// Attaches a recorder, every following operation of the overlay is stored into it as a `Record`.
+    /// The contours and lines are recorded in the integer space of the adapter, including those added before the call.
+    #[cfg(feature = "record")]
+    #[inline]
+    pub fn record_to(mut self, recorder: &Recorder) -> Self {
+        self.overlay.record_to(recorder);
+        self
+    }
+
     /// Converts the current overlay into an `FloatStringGraph` based on the specified fill rule.
     /// The resulting graph is the foundation for performing boolean operations, and it's optimized for such operations based on the provided fill rule.
     /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
@@ -144,4 +209,34 @@ impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatStringOverlay<P, T> {
         let paths = self.overlay.clip_string_lines_with_solver(fill_rule, clip_rule, solver);
         paths.to_float(&self.adapter)
     }
-}
\ No newline at end of file
+
+    /// Executes a clip like `clip_string_lines_with_solver`, but returns every contour added with `unsafe_add_string_contour`
+    /// which lies fully in the result as one closed ring.
+    ///
+    /// ### Parameters:
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how the boundary and inversion settings affect the result.
+    /// - Returns: A vector of `ClipPath<P>`, the other lines and the pieces of rings crossing the boundary are open paths.
+    #[inline]
+    pub fn clip_string_lines_keep_closed(self, fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPath<P>> {
+        self.clip_string_lines_keep_closed_with_solver(fill_rule, clip_rule, Default::default())
+    }
+
+    /// Executes a clip like `clip_string_lines_with_solver`, but returns every contour added with `unsafe_add_string_contour`
+    /// which lies fully in the result as one closed ring.
+    ///
+    /// ### Parameters:
+    /// - `fill_rule`: Fill rule to determine filled areas (non-zero, even-odd, positive, negative).
+    /// - `clip_rule`: Clip rule to determine how the boundary and inversion settings affect the result.
+    /// - `solver`: Type of solver to use.
+    /// - Returns: A vector of `ClipPath<P>`, the other lines and the pieces of rings crossing the boundary are open paths.
+    #[inline]
+    pub fn clip_string_lines_keep_closed_with_solver(self, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Vec<ClipPath<P>> {
+        let adapter = self.adapter;
+        self.overlay
+            .clip_string_lines_keep_closed_with_solver(fill_rule, clip_rule, solver)
+            .into_iter()
+            .map(|path| ClipPath { path: path.path.to_float(&adapter), is_closed: path.is_closed })
+            .collect()
+    }
+}
@@ -0,0 +1,81 @@
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::int::point::IntPoint;
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::float::overlay::FloatOverlay;
+
+/// A trapezoid of the vertical decomposition, it is bounded by two vertical lines `min_x` and `max_x`
+/// and by two non-vertical boundary segments `bottom` and `top`.
+///
+/// The segments are stored as they are in the boundary, so they may extend beyond the x-range.
+/// The first point of a segment is always to the left of the second one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trapezoid<P, T> {
+    pub min_x: T,
+    pub max_x: T,
+    pub bottom: [P; 2],
+    pub top: [P; 2],
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> FloatOverlay<P, T> {
+    /// Splits the result of a boolean operation into vertical trapezoids.
+    ///
+    /// A vertical line is drawn through every vertex up and down to the nearest boundary segments,
+    /// the filled parts between such lines are the trapezoids. Triangles are trapezoids with a collapsed side.
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes.
+    ///
+    /// Returns trapezoids ordered by their right side `max_x`.
+    #[inline]
+    pub fn into_trapezoids(self, overlay_rule: OverlayRule, fill_rule: FillRule) -> Vec<Trapezoid<P, T>> {
+        self.into_trapezoids_with_solver(overlay_rule, fill_rule, Default::default())
+    }
+
+    /// Splits the result of a boolean operation into vertical trapezoids.
+    ///
+    /// A vertical line is drawn through every vertex up and down to the nearest boundary segments,
+    /// the filled parts between such lines are the trapezoids. Triangles are trapezoids with a collapsed side.
+    /// - `overlay_rule`: The boolean operation rule to apply, determining how shapes are combined or subtracted.
+    /// - `fill_rule`: Specifies the rule for determining filled areas within the shapes.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// Returns trapezoids ordered by their right side `max_x`.
+    pub fn into_trapezoids_with_solver(self, overlay_rule: OverlayRule, fill_rule: FillRule, solver: Solver) -> Vec<Trapezoid<P, T>> {
+        let adapter = self.adapter;
+        self.overlay
+            .into_trapezoids_with_solver(overlay_rule, fill_rule, solver)
+            .into_iter()
+            .map(|t| {
+                let bottom = [adapter.int_to_float(&t.bottom[0]), adapter.int_to_float(&t.bottom[1])];
+                let top = [adapter.int_to_float(&t.top[0]), adapter.int_to_float(&t.top[1])];
+                let min_x = adapter.int_to_float(&IntPoint::new(t.min_x, 0)).x();
+                let max_x = adapter.int_to_float(&IntPoint::new(t.max_x, 0)).x();
+                Trapezoid { min_x, max_x, bottom, top }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::overlay::FloatOverlay;
+
+    #[test]
+    fn test_square() {
+        let square = [[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let trapezoids = FloatOverlay::with_subj(&square)
+            .into_trapezoids(OverlayRule::Subject, FillRule::NonZero);
+
+        assert_eq!(trapezoids.len(), 1);
+        let t = &trapezoids[0];
+        assert!((t.min_x - 0.0f64).abs() < 1e-9);
+        assert!((t.max_x - 1.0f64).abs() < 1e-9);
+        assert!((t.bottom[0][1] - 0.0f64).abs() < 1e-9);
+        assert!((t.top[0][1] - 1.0f64).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,255 @@
+//! Reading and writing GeoJSON geometries of the `geojson` crate.
+//!
+//! Shapes are written as `MultiPolygon` and paths as `MultiLineString`. Polygons are written by the
+//! RFC 7946 right-hand rule: outer rings are counterclockwise and holes are clockwise. Read rings may have
+//! any winding, they are oriented as the overlay produces them: outer contours are clockwise and holes are counterclockwise.
+
+use std::fmt;
+use std::fmt::Display;
+use geojson::{Geometry, Position, Value};
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::{Path, Paths, Shape, Shapes};
+use crate::float::source::double::DoubleResourceIterator;
+use crate::float::source::resource::OverlayResource;
+use crate::format::ring::{float_point, float_xy, into_contour, ring_area, RingArea};
+use crate::format::Winding;
+
+/// An error of reading a GeoJSON geometry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeoJsonError {
+    /// A position with less than two coordinates or with a coordinate which is not a finite number.
+    InvalidPosition,
+    /// A geometry type which can not be read as the requested one.
+    UnsupportedGeometry(&'static str),
+}
+
+impl Display for GeoJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoJsonError::InvalidPosition => write!(f, "invalid position"),
+            GeoJsonError::UnsupportedGeometry(name) => write!(f, "unsupported geometry {}", name),
+        }
+    }
+}
+
+impl std::error::Error for GeoJsonError {}
+
+/// Contours of a `Polygon`, `MultiPolygon`, `LineString` or `MultiLineString` prepared to be an overlay source.
+///
+/// GeoJSON positions are separate vectors, so a geometry can not lend its points as slices
+/// and is converted once on creation.
+#[derive(Debug, Clone)]
+pub struct GeoJsonSource<P> {
+    paths: Vec<Vec<P>>,
+}
+
+impl<P> GeoJsonSource<P> {
+    /// Converts the geometry, polygon rings are oriented as the overlay expects them.
+    pub fn new<T>(geometry: &Geometry) -> Result<Self, GeoJsonError>
+    where
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        Self::with_value(&geometry.value)
+    }
+
+    /// Converts the geometry value, polygon rings are oriented as the overlay expects them.
+    pub fn with_value<T>(value: &Value) -> Result<Self, GeoJsonError>
+    where
+        P: FloatPointCompatible<T>,
+        T: FloatNumber,
+    {
+        let paths = match value {
+            Value::Polygon(_) | Value::MultiPolygon(_) => read_polygons(value)?.into_iter().flatten().collect(),
+            Value::LineString(_) | Value::MultiLineString(_) => read_lines(value)?,
+            _ => return Err(GeoJsonError::UnsupportedGeometry(value.type_name())),
+        };
+
+        Ok(Self { paths })
+    }
+}
+
+impl<P, T> OverlayResource<P, T> for GeoJsonSource<P>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    type ResourceIter<'a> = DoubleResourceIterator<'a, P>
+    where
+        P: 'a,
+        Self: 'a;
+
+    #[inline]
+    fn iter_paths(&self) -> Self::ResourceIter<'_> {
+        <Vec<Vec<P>> as OverlayResource<P, T>>::iter_paths(&self.paths)
+    }
+}
+
+/// Writes shapes as a `MultiPolygon` by the right-hand rule.
+pub fn write_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(shapes: &[Shape<P>]) -> Geometry {
+    let polygons = shapes
+        .iter()
+        .map(|shape| {
+            shape
+                .iter()
+                .enumerate()
+                .map(|(i, contour)| {
+                    let is_reversed = Winding::CounterClockwise.is_reversed(ring_area(contour, float_xy), i == 0);
+                    let mut ring: Vec<Position> = if is_reversed {
+                        contour.iter().rev().map(position).collect()
+                    } else {
+                        contour.iter().map(position).collect()
+                    };
+                    if let Some(first) = ring.first().cloned() {
+                        ring.push(first);
+                    }
+                    ring
+                })
+                .collect()
+        })
+        .collect();
+
+    Geometry::new(Value::MultiPolygon(polygons))
+}
+
+/// Writes paths as a `MultiLineString`.
+pub fn write_paths<P: FloatPointCompatible<T>, T: FloatNumber>(paths: &[Path<P>]) -> Geometry {
+    let lines = paths
+        .iter()
+        .map(|path| path.iter().map(position).collect())
+        .collect();
+
+    Geometry::new(Value::MultiLineString(lines))
+}
+
+/// Reads shapes from a `Polygon` or a `MultiPolygon`.
+#[inline]
+pub fn read_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(geometry: &Geometry) -> Result<Shapes<P>, GeoJsonError> {
+    read_polygons(&geometry.value)
+}
+
+/// Reads paths from a `LineString` or a `MultiLineString`.
+#[inline]
+pub fn read_paths<P: FloatPointCompatible<T>, T: FloatNumber>(geometry: &Geometry) -> Result<Paths<P>, GeoJsonError> {
+    read_lines(&geometry.value)
+}
+
+fn read_polygons<P: FloatPointCompatible<T>, T: FloatNumber>(value: &Value) -> Result<Shapes<P>, GeoJsonError> {
+    match value {
+        Value::Polygon(polygon) => Ok(vec![read_polygon(polygon)?]),
+        Value::MultiPolygon(polygons) => polygons.iter().map(|polygon| read_polygon(polygon)).collect(),
+        _ => Err(GeoJsonError::UnsupportedGeometry(value.type_name())),
+    }
+}
+
+fn read_lines<P: FloatPointCompatible<T>, T: FloatNumber>(value: &Value) -> Result<Paths<P>, GeoJsonError> {
+    match value {
+        Value::LineString(line) => Ok(vec![read_positions(line)?.0]),
+        Value::MultiLineString(lines) => lines.iter().map(|line| Ok(read_positions(line)?.0)).collect(),
+        _ => Err(GeoJsonError::UnsupportedGeometry(value.type_name())),
+    }
+}
+
+fn read_polygon<P: FloatPointCompatible<T>, T: FloatNumber>(polygon: &[Vec<Position>]) -> Result<Shape<P>, GeoJsonError> {
+    polygon
+        .iter()
+        .enumerate()
+        .map(|(i, ring)| {
+            let (points, area) = read_positions(ring)?;
+            Ok(into_contour(points, &area, i == 0))
+        })
+        .collect()
+}
+
+fn read_positions<P: FloatPointCompatible<T>, T: FloatNumber>(positions: &[Position]) -> Result<(Vec<P>, RingArea), GeoJsonError> {
+    let mut area = RingArea::default();
+    let mut points = Vec::with_capacity(positions.len());
+    for position in positions.iter() {
+        let (x, y) = match position.as_slice() {
+            [x, y, ..] => (*x, *y),
+            _ => return Err(GeoJsonError::InvalidPosition),
+        };
+        points.push(float_point(x, y).ok_or(GeoJsonError::InvalidPosition)?);
+        area.add([x, y]);
+    }
+
+    Ok((points, area))
+}
+
+#[inline]
+fn position<P: FloatPointCompatible<T>, T: FloatNumber>(p: &P) -> Position {
+    vec![p.x().to_f64(), p.y().to_f64()]
+}
+
+#[cfg(test)]
+mod tests {
+    use geojson::{Geometry, Value};
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::clip::FloatClip;
+    use crate::float::single::SingleFloatOverlay;
+    use crate::format::geojson::{read_paths, read_shapes, write_paths, write_shapes, GeoJsonError, GeoJsonSource};
+    use crate::string::clip::ClipRule;
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<Vec<f64>> {
+        // counterclockwise by the right-hand rule
+        vec![vec![x, y], vec![x + size, y], vec![x + size, y + size], vec![x, y + size], vec![x, y]]
+    }
+
+    #[test]
+    fn test_read_polygon() {
+        let mut hole = square(1.0, 1.0, 2.0);
+        hole.reverse();
+        let geometry = Geometry::new(Value::Polygon(vec![square(0.0, 0.0, 4.0), hole]));
+
+        let shapes: Vec<Vec<Vec<[f64; 2]>>> = read_shapes(&geometry).unwrap();
+
+        assert_eq!(shapes, vec![vec![
+            vec![[0.0, 4.0], [4.0, 4.0], [4.0, 0.0], [0.0, 0.0]],
+            vec![[3.0, 1.0], [3.0, 3.0], [1.0, 3.0], [1.0, 1.0]],
+        ]]);
+    }
+
+    #[test]
+    fn test_overlay() {
+        let subj = GeoJsonSource::new(&Geometry::new(Value::Polygon(vec![square(0.0, 0.0, 4.0)]))).unwrap();
+        // the winding of the clip is wrong, it must not matter
+        let mut clip = square(2.0, 2.0, 4.0);
+        clip.reverse();
+        let clip = GeoJsonSource::new(&Geometry::new(Value::MultiPolygon(vec![vec![clip]]))).unwrap();
+
+        let shapes: Vec<Vec<Vec<[f64; 2]>>> = subj.overlay(&clip, OverlayRule::Intersect, FillRule::Positive);
+        let geometry = write_shapes(&shapes);
+
+        let ring = vec![vec![4.0, 2.0], vec![4.0, 4.0], vec![2.0, 4.0], vec![2.0, 2.0], vec![4.0, 2.0]];
+        assert_eq!(geometry.value, Value::MultiPolygon(vec![vec![ring]]));
+        assert_eq!(read_shapes::<[f64; 2], f64>(&geometry).unwrap(), shapes);
+    }
+
+    #[test]
+    fn test_clip_line() {
+        let line = GeoJsonSource::new(&Geometry::new(Value::LineString(vec![vec![-1.0, 1.0], vec![5.0, 1.0]]))).unwrap();
+        let polygon = GeoJsonSource::new(&Geometry::new(Value::Polygon(vec![square(0.0, 0.0, 4.0)]))).unwrap();
+
+        let clip_rule = ClipRule { invert: false, boundary_included: false };
+        let paths: Vec<Vec<[f64; 2]>> = line.clip_by(&polygon, FillRule::NonZero, clip_rule);
+        let geometry = write_paths(&paths);
+
+        let lines: Vec<Vec<[f64; 2]>> = read_paths(&geometry).unwrap();
+        assert_eq!(lines.len(), 1);
+        let mut ends = lines[0].clone();
+        ends.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+        assert_eq!(ends, vec![[0.0, 1.0], [4.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let point = Geometry::new(Value::Point(vec![0.0, 0.0]));
+        assert_eq!(GeoJsonSource::<[f64; 2]>::new(&point).unwrap_err(), GeoJsonError::UnsupportedGeometry("Point"));
+
+        let line = Geometry::new(Value::LineString(vec![vec![0.0, 0.0], vec![1.0]]));
+        assert_eq!(read_paths::<[f64; 2], f64>(&line), Err(GeoJsonError::InvalidPosition));
+        assert_eq!(read_shapes::<[f64; 2], f64>(&line), Err(GeoJsonError::UnsupportedGeometry("LineString")));
+    }
+}
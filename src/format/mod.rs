@@ -0,0 +1,25 @@
+//! Conversions between overlay types and external geometry formats.
+//!
+//! Every format is behind its own cargo feature.
+
+#[cfg(any(feature = "wkt", feature = "wkb", feature = "geojson"))]
+pub(crate) mod ring;
+#[cfg(feature = "wkt")]
+pub mod wkt;
+#[cfg(feature = "wkb")]
+pub mod wkb;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+#[cfg(feature = "svg")]
+pub mod svg;
+
+/// Winding order of outer contours in an external format, holes always have the opposite order.
+///
+/// The overlay produces clockwise outer contours, while OGC Simple Features expects counterclockwise ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Winding {
+    Clockwise,
+    #[default]
+    CounterClockwise,
+}
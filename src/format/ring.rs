@@ -0,0 +1,104 @@
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+#[cfg(any(feature = "wkt", feature = "wkb"))]
+use i_float::int::point::IntPoint;
+use crate::format::Winding;
+
+impl Winding {
+    /// Checks whether a ring must be reversed to follow the winding.
+    /// - `area`: Double signed area of the ring, positive for clockwise rings.
+    #[inline]
+    pub(crate) fn is_reversed(self, area: f64, is_outer: bool) -> bool {
+        let is_clockwise = (self == Winding::Clockwise) == is_outer;
+        area != 0.0 && (area > 0.0) != is_clockwise
+    }
+}
+
+/// Accumulates the double signed area of a ring point by point, positive for clockwise rings.
+#[derive(Default)]
+pub(crate) struct RingArea {
+    first: Option<[f64; 2]>,
+    last: [f64; 2],
+    area: f64,
+}
+
+impl RingArea {
+    #[inline]
+    pub(crate) fn add(&mut self, p: [f64; 2]) {
+        if self.first.is_some() {
+            self.area += p[0] * self.last[1] - p[1] * self.last[0];
+        } else {
+            self.first = Some(p);
+        }
+        self.last = p;
+    }
+
+    /// Returns true if the last added point repeats the first one.
+    #[inline]
+    pub(crate) fn is_closed(&self) -> bool {
+        self.first.is_some_and(|p| p == self.last)
+    }
+
+    #[inline]
+    pub(crate) fn area(&self) -> f64 {
+        match self.first {
+            Some(p) => self.area + p[0] * self.last[1] - p[1] * self.last[0],
+            None => 0.0,
+        }
+    }
+}
+
+/// Double signed area of a contour, positive for clockwise contours.
+#[inline]
+pub(crate) fn ring_area<P, F: Fn(&P) -> [f64; 2]>(ring: &[P], xy: F) -> f64 {
+    let mut area = RingArea::default();
+    for p in ring.iter() {
+        area.add(xy(p));
+    }
+    area.area()
+}
+
+/// Drops the closing point of a ring and orients it as the overlay does.
+pub(crate) fn into_contour<P>(mut ring: Vec<P>, area: &RingArea, is_outer: bool) -> Vec<P> {
+    if ring.len() > 1 && area.is_closed() {
+        ring.pop();
+    }
+    if Winding::Clockwise.is_reversed(area.area(), is_outer) {
+        ring.reverse();
+    }
+    ring
+}
+
+#[inline]
+pub(crate) fn float_point<P: FloatPointCompatible<T>, T: FloatNumber>(x: f64, y: f64) -> Option<P> {
+    if x.is_finite() && y.is_finite() {
+        Some(P::from_xy(T::from_float(x), T::from_float(y)))
+    } else {
+        None
+    }
+}
+
+/// Accepts only whole numbers which fit i32.
+#[cfg(any(feature = "wkt", feature = "wkb"))]
+#[inline]
+pub(crate) fn int_point(x: f64, y: f64) -> Option<IntPoint> {
+    let int = |v: f64| {
+        if v.fract() == 0.0 && v >= i32::MIN as f64 && v <= i32::MAX as f64 {
+            Some(v as i32)
+        } else {
+            None
+        }
+    };
+    Some(IntPoint::new(int(x)?, int(y)?))
+}
+
+#[inline]
+pub(crate) fn float_xy<P: FloatPointCompatible<T>, T: FloatNumber>(p: &P) -> [f64; 2] {
+    [p.x().to_f64(), p.y().to_f64()]
+}
+
+#[cfg(any(feature = "wkt", feature = "wkb"))]
+#[inline]
+pub(crate) fn int_xy(p: &IntPoint) -> [f64; 2] {
+    [p.x as f64, p.y as f64]
+}
@@ -0,0 +1,600 @@
+//! Reading and writing SVG path data.
+//!
+//! The reader supports every command of the `d` attribute, curves and arcs are flattened
+//! to a tolerance. Coordinates are taken as they are, the y-axis is not flipped.
+
+use std::f64::consts::PI;
+use std::fmt;
+use std::fmt::{Display, Write};
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::{Path, Paths, Shape, Shapes};
+use crate::core::fill_rule::FillRule;
+use crate::float::simplify::SimplifyShape;
+
+/// An error of reading SVG path data, positions are byte offsets in the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SvgError {
+    /// The path data ended in the middle of a command.
+    UnexpectedEnd,
+    /// A character which does not fit the grammar.
+    UnexpectedChar(usize),
+    /// A number which is not finite.
+    InvalidNumber(usize),
+}
+
+impl Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::UnexpectedEnd => write!(f, "unexpected end of path data"),
+            SvgError::UnexpectedChar(pos) => write!(f, "unexpected character at {}", pos),
+            SvgError::InvalidNumber(pos) => write!(f, "invalid number at {}", pos),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+/// Reads the subpaths of path data as polylines, a closed subpath ends with its first point.
+/// - `tolerance`: The maximum distance between a curve and its flattened polyline.
+pub fn read_paths<P: FloatPointCompatible<T>, T: FloatNumber>(data: &str, tolerance: T) -> Result<Paths<P>, SvgError> {
+    let subpaths = PathParser::new(data, tolerance.to_f64()).parse()?;
+    Ok(subpaths
+        .into_iter()
+        .map(|mut subpath| {
+            if subpath.is_closed && subpath.points.len() > 1 {
+                let first = subpath.points[0];
+                subpath.points.push(first);
+            }
+            to_points(&subpath.points)
+        })
+        .collect())
+}
+
+/// Reads path data as a filled area, every subpath is a contour even if it is not closed by `Z`.
+/// - `fill_rule`: Fill rule to determine filled areas, as the `fill-rule` attribute does.
+/// - `tolerance`: The maximum distance between a curve and its flattened polyline.
+pub fn read_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(data: &str, fill_rule: FillRule, tolerance: T) -> Result<Shapes<P>, SvgError> {
+    let subpaths = PathParser::new(data, tolerance.to_f64()).parse()?;
+    let contours = subpaths
+        .into_iter()
+        .filter(|subpath| subpath.points.len() > 2)
+        .map(|subpath| to_points(&subpath.points))
+        .collect::<Vec<Vec<P>>>();
+
+    Ok(contours.simplify_shape(fill_rule, T::from_float(0.0)))
+}
+
+/// Writes shapes as path data, every contour is a closed subpath.
+pub fn write_path_data<P: FloatPointCompatible<T>, T: FloatNumber>(shapes: &[Shape<P>]) -> String {
+    let mut data = String::new();
+    for contour in shapes.iter().flatten() {
+        write_subpath(&mut data, contour, true);
+    }
+    data
+}
+
+/// Writes every shape as a `<path>` element.
+/// - `fill_rule`: Fill rule for the `fill-rule` attribute. SVG has no positive and negative rules,
+///   they are written as `nonzero` which renders overlay results the same way.
+pub fn write_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(shapes: &[Shape<P>], fill_rule: FillRule) -> String {
+    let fill_rule = match fill_rule {
+        FillRule::EvenOdd => "evenodd",
+        FillRule::NonZero | FillRule::Positive | FillRule::Negative => "nonzero",
+    };
+
+    let mut text = String::new();
+    for shape in shapes.iter() {
+        let mut data = String::new();
+        for contour in shape.iter() {
+            write_subpath(&mut data, contour, true);
+        }
+        let _ = writeln!(text, "<path d=\"{}\" fill-rule=\"{}\"/>", data, fill_rule);
+    }
+    text
+}
+
+/// Writes every path as an unfilled `<path>` element.
+pub fn write_paths<P: FloatPointCompatible<T>, T: FloatNumber>(paths: &[Path<P>]) -> String {
+    let mut text = String::new();
+    for path in paths.iter() {
+        let mut data = String::new();
+        write_subpath(&mut data, path, false);
+        let _ = writeln!(text, "<path d=\"{}\" fill=\"none\"/>", data);
+    }
+    text
+}
+
+fn write_subpath<P: FloatPointCompatible<T>, T: FloatNumber>(data: &mut String, points: &[P], is_closed: bool) {
+    let mut command = 'M';
+    for p in points.iter() {
+        if !data.is_empty() {
+            data.push(' ');
+        }
+        // writing to a String never fails
+        let _ = write!(data, "{} {} {}", command, p.x(), p.y());
+        command = 'L';
+    }
+    if is_closed && !points.is_empty() {
+        data.push_str(" Z");
+    }
+}
+
+#[inline]
+fn to_points<P: FloatPointCompatible<T>, T: FloatNumber>(points: &[[f64; 2]]) -> Vec<P> {
+    points
+        .iter()
+        .map(|p| P::from_xy(T::from_float(p[0]), T::from_float(p[1])))
+        .collect()
+}
+
+struct Subpath {
+    points: Vec<[f64; 2]>,
+    is_closed: bool,
+}
+
+struct PathParser<'a> {
+    text: &'a [u8],
+    pos: usize,
+    tolerance: f64,
+    subpaths: Vec<Subpath>,
+    points: Vec<[f64; 2]>,
+    start: [f64; 2],
+    current: [f64; 2],
+    // the last control point and whether it belongs to a cubic curve
+    control: Option<([f64; 2], bool)>,
+}
+
+impl<'a> PathParser<'a> {
+    fn new(text: &'a str, tolerance: f64) -> Self {
+        Self {
+            text: text.as_bytes(),
+            pos: 0,
+            tolerance: tolerance.max(f64::EPSILON),
+            subpaths: Vec::new(),
+            points: Vec::new(),
+            start: [0.0, 0.0],
+            current: [0.0, 0.0],
+            control: None,
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<Subpath>, SvgError> {
+        let mut last: Option<u8> = None;
+        loop {
+            self.skip_separators();
+            let Some(&c) = self.text.get(self.pos) else {
+                break;
+            };
+
+            let command = if c.is_ascii_alphabetic() {
+                let is_valid = match c.to_ascii_uppercase() {
+                    b'M' => true,
+                    b'L' | b'H' | b'V' | b'C' | b'S' | b'Q' | b'T' | b'A' | b'Z' => last.is_some(),
+                    _ => false,
+                };
+                if !is_valid {
+                    return Err(SvgError::UnexpectedChar(self.pos));
+                }
+                self.pos += 1;
+                c
+            } else {
+                // a repeated command may be omitted
+                match last {
+                    Some(c) if !c.eq_ignore_ascii_case(&b'Z') => c,
+                    _ => return Err(SvgError::UnexpectedChar(self.pos)),
+                }
+            };
+
+            self.segment(command)?;
+
+            // the following coordinates of a move are lines
+            last = Some(match command {
+                b'M' => b'L',
+                b'm' => b'l',
+                c => c,
+            });
+        }
+
+        self.finish(false);
+        Ok(self.subpaths)
+    }
+
+    fn segment(&mut self, command: u8) -> Result<(), SvgError> {
+        let is_relative = command.is_ascii_lowercase();
+        let origin = if is_relative { self.current } else { [0.0, 0.0] };
+        let control = self.control.take();
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let p = self.point(origin)?;
+                self.finish(false);
+                self.points.push(p);
+                self.start = p;
+                self.current = p;
+            }
+            b'L' => {
+                let p = self.point(origin)?;
+                self.line_to(p);
+            }
+            b'H' => {
+                let x = self.number()? + origin[0];
+                self.line_to([x, self.current[1]]);
+            }
+            b'V' => {
+                let y = self.number()? + origin[1];
+                self.line_to([self.current[0], y]);
+            }
+            b'C' => {
+                let c1 = self.point(origin)?;
+                let c2 = self.point(origin)?;
+                let p = self.point(origin)?;
+                self.cubic_to(c1, c2, p);
+            }
+            b'S' => {
+                let c1 = self.reflect(control, true);
+                let c2 = self.point(origin)?;
+                let p = self.point(origin)?;
+                self.cubic_to(c1, c2, p);
+            }
+            b'Q' => {
+                let c = self.point(origin)?;
+                let p = self.point(origin)?;
+                self.quad_to(c, p);
+            }
+            b'T' => {
+                let c = self.reflect(control, false);
+                let p = self.point(origin)?;
+                self.quad_to(c, p);
+            }
+            b'A' => {
+                let rx = self.number()?;
+                let ry = self.number()?;
+                let angle = self.number()?;
+                let is_large = self.flag()?;
+                let is_sweep = self.flag()?;
+                let p = self.point(origin)?;
+                self.arc_to(rx, ry, angle, is_large, is_sweep, p);
+            }
+            _ => {
+                // Z
+                self.finish(true);
+                self.current = self.start;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reflects the last control point if the previous command is a curve of the same kind.
+    #[inline]
+    fn reflect(&self, control: Option<([f64; 2], bool)>, is_cubic: bool) -> [f64; 2] {
+        match control {
+            Some((c, kind)) if kind == is_cubic => [2.0 * self.current[0] - c[0], 2.0 * self.current[1] - c[1]],
+            _ => self.current,
+        }
+    }
+
+    fn finish(&mut self, is_closed: bool) {
+        if !self.points.is_empty() {
+            let points = std::mem::take(&mut self.points);
+            self.subpaths.push(Subpath { points, is_closed });
+        }
+    }
+
+    #[inline]
+    fn line_to(&mut self, p: [f64; 2]) {
+        if self.points.is_empty() {
+            // a drawing command right after Z starts a new subpath
+            self.points.push(self.current);
+        }
+        self.points.push(p);
+        self.current = p;
+    }
+
+    fn cubic_to(&mut self, c1: [f64; 2], c2: [f64; 2], p: [f64; 2]) {
+        let p0 = self.current;
+        // the max of the second derivative is 6 * max(|p0 - 2c1 + c2|, |c1 - 2c2 + p|)
+        let d = length(p0[0] - 2.0 * c1[0] + c2[0], p0[1] - 2.0 * c1[1] + c2[1])
+            .max(length(c1[0] - 2.0 * c2[0] + p[0], c1[1] - 2.0 * c2[1] + p[1]));
+        let n = segment_count((0.75 * d / self.tolerance).sqrt());
+        for i in 1..n {
+            let t = i as f64 / n as f64;
+            let s = 1.0 - t;
+            let a = s * s * s;
+            let b = 3.0 * s * s * t;
+            let c = 3.0 * s * t * t;
+            let e = t * t * t;
+            self.line_to([
+                a * p0[0] + b * c1[0] + c * c2[0] + e * p[0],
+                a * p0[1] + b * c1[1] + c * c2[1] + e * p[1],
+            ]);
+        }
+        self.line_to(p);
+        self.control = Some((c2, true));
+    }
+
+    fn quad_to(&mut self, c: [f64; 2], p: [f64; 2]) {
+        let p0 = self.current;
+        // the second derivative is 2 * (p0 - 2c + p)
+        let d = length(p0[0] - 2.0 * c[0] + p[0], p0[1] - 2.0 * c[1] + p[1]);
+        let n = segment_count((0.25 * d / self.tolerance).sqrt());
+        for i in 1..n {
+            let t = i as f64 / n as f64;
+            let s = 1.0 - t;
+            let a = s * s;
+            let b = 2.0 * s * t;
+            let e = t * t;
+            self.line_to([
+                a * p0[0] + b * c[0] + e * p[0],
+                a * p0[1] + b * c[1] + e * p[1],
+            ]);
+        }
+        self.line_to(p);
+        self.control = Some((c, false));
+    }
+
+    /// Converts the endpoint parametrization to the center one as the SVG specification describes.
+    fn arc_to(&mut self, rx: f64, ry: f64, angle: f64, is_large: bool, is_sweep: bool, p: [f64; 2]) {
+        let p0 = self.current;
+        if p0 == p {
+            return;
+        }
+
+        let mut rx = rx.abs();
+        let mut ry = ry.abs();
+        if rx == 0.0 || ry == 0.0 {
+            self.line_to(p);
+            return;
+        }
+
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let dx = 0.5 * (p0[0] - p[0]);
+        let dy = 0.5 * (p0[1] - p[1]);
+        let x1 = cos * dx + sin * dy;
+        let y1 = -sin * dx + cos * dy;
+
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            let k = lambda.sqrt();
+            rx *= k;
+            ry *= k;
+        }
+
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let sign = if is_large == is_sweep { -1.0 } else { 1.0 };
+        let coef = sign * (num / den).max(0.0).sqrt();
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+
+        let cx = cos * cx1 - sin * cy1 + 0.5 * (p0[0] + p[0]);
+        let cy = sin * cx1 + cos * cy1 + 0.5 * (p0[1] + p[1]);
+
+        let ux = (x1 - cx1) / rx;
+        let uy = (y1 - cy1) / ry;
+        let vx = (-x1 - cx1) / rx;
+        let vy = (-y1 - cy1) / ry;
+
+        let start = uy.atan2(ux);
+        let mut sweep = (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+        if !is_sweep && sweep > 0.0 {
+            sweep -= 2.0 * PI;
+        } else if is_sweep && sweep < 0.0 {
+            sweep += 2.0 * PI;
+        }
+
+        // the chord of the step deviates from the arc by r * (1 - cos(step / 2))
+        let r = rx.max(ry);
+        let step = if self.tolerance < r { 2.0 * (1.0 - self.tolerance / r).acos() } else { 0.5 * PI };
+        let n = segment_count(sweep.abs() / step);
+        for i in 1..n {
+            let t = start + sweep * i as f64 / n as f64;
+            let (sin_t, cos_t) = t.sin_cos();
+            self.line_to([
+                cx + rx * cos_t * cos - ry * sin_t * sin,
+                cy + rx * cos_t * sin + ry * sin_t * cos,
+            ]);
+        }
+        self.line_to(p);
+    }
+
+    #[inline]
+    fn point(&mut self, origin: [f64; 2]) -> Result<[f64; 2], SvgError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok([origin[0] + x, origin[1] + y])
+    }
+
+    fn number(&mut self) -> Result<f64, SvgError> {
+        self.skip_separators();
+        let start = self.pos;
+
+        let mut i = self.pos;
+        if matches!(self.text.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        let digits = self.digits(&mut i);
+        let mut fraction = 0;
+        if self.text.get(i) == Some(&b'.') {
+            i += 1;
+            fraction = self.digits(&mut i);
+        }
+        if digits + fraction == 0 {
+            return Err(self.unexpected(start));
+        }
+        if matches!(self.text.get(i), Some(b'e' | b'E')) {
+            let mut j = i + 1;
+            if matches!(self.text.get(j), Some(b'+' | b'-')) {
+                j += 1;
+            }
+            if self.digits(&mut j) > 0 {
+                i = j;
+            }
+        }
+
+        self.pos = i;
+        std::str::from_utf8(&self.text[start..i])
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|v| v.is_finite())
+            .ok_or(SvgError::InvalidNumber(start))
+    }
+
+    /// Arc flags may go without separators, so a flag is a single character.
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        self.skip_separators();
+        match self.text.get(self.pos) {
+            Some(b'0') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => Err(self.unexpected(self.pos)),
+        }
+    }
+
+    #[inline]
+    fn digits(&self, i: &mut usize) -> usize {
+        let start = *i;
+        while self.text.get(*i).is_some_and(|c| c.is_ascii_digit()) {
+            *i += 1;
+        }
+        *i - start
+    }
+
+    #[inline]
+    fn skip_separators(&mut self) {
+        while self.text.get(self.pos).is_some_and(|&c| c.is_ascii_whitespace() || c == b',') {
+            self.pos += 1;
+        }
+    }
+
+    #[inline]
+    fn unexpected(&self, pos: usize) -> SvgError {
+        if pos < self.text.len() {
+            SvgError::UnexpectedChar(pos)
+        } else {
+            SvgError::UnexpectedEnd
+        }
+    }
+}
+
+#[inline]
+fn length(x: f64, y: f64) -> f64 {
+    (x * x + y * y).sqrt()
+}
+
+#[inline]
+fn segment_count(n: f64) -> usize {
+    (n.ceil() as usize).clamp(1, 1 << 16)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::single::SingleFloatOverlay;
+    use crate::format::svg::{read_paths, read_shapes, write_path_data, write_paths, write_shapes, SvgError};
+
+    fn area(contour: &[[f64; 2]]) -> f64 {
+        let mut area = 0.0;
+        let mut p0 = contour[contour.len() - 1];
+        for &p1 in contour.iter() {
+            area += p1[0] * p0[1] - p1[1] * p0[0];
+            p0 = p1;
+        }
+        0.5 * area
+    }
+
+    #[test]
+    fn test_lines() {
+        let paths: Vec<Vec<[f64; 2]>> = read_paths("M0,0 L10 0 h5 v5 l-5-5 M1 1 2 2 3 3", 0.1).unwrap();
+        assert_eq!(paths, vec![
+            vec![[0.0, 0.0], [10.0, 0.0], [15.0, 0.0], [15.0, 5.0], [10.0, 0.0]],
+            vec![[1.0, 1.0], [2.0, 2.0], [3.0, 3.0]],
+        ]);
+    }
+
+    #[test]
+    fn test_relative_and_close() {
+        let paths: Vec<Vec<[f64; 2]>> = read_paths("m1 1 h2 v2 h-2 z l1 0", 0.1).unwrap();
+        assert_eq!(paths, vec![
+            vec![[1.0, 1.0], [3.0, 1.0], [3.0, 3.0], [1.0, 3.0], [1.0, 1.0]],
+            vec![[1.0, 1.0], [2.0, 1.0]],
+        ]);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let paths: Vec<Vec<[f64; 2]>> = read_paths("M.5.5L-1e1-2E+0,+3 4", 0.1).unwrap();
+        assert_eq!(paths, vec![vec![[0.5, 0.5], [-10.0, -2.0], [3.0, 4.0]]]);
+    }
+
+    #[test]
+    fn test_circle_by_arcs() {
+        let tolerance = 0.01;
+        let shapes: Vec<Vec<Vec<[f64; 2]>>> = read_shapes("M-10 0 A10 10 0 1 0 10 0 A10 10 0 1 0-10 0Z", FillRule::NonZero, tolerance).unwrap();
+
+        assert_eq!(shapes.len(), 1);
+        let contour = &shapes[0][0];
+        for p in contour.iter() {
+            let r = (p[0] * p[0] + p[1] * p[1]).sqrt();
+            assert!((r - 10.0).abs() < 1e-6);
+        }
+        let area = area(contour).abs();
+        let circle = std::f64::consts::PI * 100.0;
+        assert!(area < circle && circle - area < 2.0 * std::f64::consts::PI * 10.0 * tolerance);
+    }
+
+    #[test]
+    fn test_curves_tolerance() {
+        // the quarter of a circle by a cubic curve deviates from the circle less than 0.03%
+        let tolerance = 0.001;
+        let k = 0.5522847498;
+        let d = format!("M 1 0 C 1 {k} {k} 1 0 1", k = k);
+        let paths: Vec<Vec<[f64; 2]>> = read_paths(&d, tolerance).unwrap();
+        let path = &paths[0];
+        assert!(path.len() > 4);
+        for w in path.windows(2) {
+            let m = [0.5 * (w[0][0] + w[1][0]), 0.5 * (w[0][1] + w[1][1])];
+            let r = (m[0] * m[0] + m[1] * m[1]).sqrt();
+            assert!(1.0 - r < tolerance + 0.0003);
+        }
+
+        // the smooth curves reflect the control points
+        let paths: Vec<Vec<[f64; 2]>> = read_paths("M0 0 Q 1 1 2 0 T 4 0", 0.01).unwrap();
+        let path = &paths[0];
+        assert!(path.iter().any(|p| p[0] > 2.0 && p[1] < 0.0));
+    }
+
+    #[test]
+    fn test_write() {
+        let subj = [[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+        let clip = [[1.0, 1.0], [1.0, 3.0], [3.0, 3.0], [3.0, 1.0]];
+        let shapes = subj.overlay(&clip, OverlayRule::Union, FillRule::EvenOdd);
+
+        let data = write_path_data(&shapes);
+        assert_eq!(data, "M 0 0 L 0 2 L 1 2 L 1 3 L 3 3 L 3 1 L 2 1 L 2 0 Z");
+        assert_eq!(write_shapes(&shapes, FillRule::EvenOdd), format!("<path d=\"{}\" fill-rule=\"evenodd\"/>\n", data));
+
+        let result: Vec<Vec<Vec<[f64; 2]>>> = read_shapes(&data, FillRule::EvenOdd, 0.1).unwrap();
+        assert_eq!(result, shapes);
+
+        let paths = vec![vec![[0.0, 0.0], [1.5, 1.0]]];
+        assert_eq!(write_paths(&paths), "<path d=\"M 0 0 L 1.5 1\" fill=\"none\"/>\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(read_paths::<[f64; 2], f64>("L 1 1", 0.1), Err(SvgError::UnexpectedChar(0)));
+        assert_eq!(read_paths::<[f64; 2], f64>("M 1 1 X", 0.1), Err(SvgError::UnexpectedChar(6)));
+        assert_eq!(read_paths::<[f64; 2], f64>("M 1 1 L 2", 0.1), Err(SvgError::UnexpectedEnd));
+        assert_eq!(read_paths::<[f64; 2], f64>("M 1 1 Z 2 2", 0.1), Err(SvgError::UnexpectedChar(8)));
+        assert_eq!(read_paths::<[f64; 2], f64>("M 1 1 A 1 1 0 2 0 3 3", 0.1), Err(SvgError::UnexpectedChar(14)));
+        assert_eq!(read_paths::<[f64; 2], f64>("M 1e999 1", 0.1), Err(SvgError::InvalidNumber(2)));
+    }
+}
@@ -0,0 +1,460 @@
+//! Reading and writing OGC Well-Known Binary.
+//!
+//! Shapes are written as `MultiPolygon` and paths as `MultiLineString` in little endian byte order
+//! with 2D coordinates. The readers also accept the single `Polygon` and `LineString` geometries,
+//! both byte orders, ISO and extended (PostGIS) `Z`, `M` and `ZM` types with the extra ordinates dropped.
+//! Read contours are oriented as the overlay produces them: outer contours are clockwise and holes are counterclockwise.
+
+use std::fmt;
+use std::fmt::Display;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::{Path, Paths, Shape, Shapes};
+use i_shape::int::path::{IntPath, IntPaths};
+use i_shape::int::shape::{IntShape, IntShapes};
+use crate::format::ring::{float_point, float_xy, int_point, int_xy, into_contour, ring_area, RingArea};
+use crate::format::Winding;
+
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+
+// The smallest encoded items: a header with an empty count, and an empty ring count.
+const POLYGON_MIN_SIZE: usize = 9;
+const LINE_STRING_MIN_SIZE: usize = 9;
+const RING_MIN_SIZE: usize = 4;
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// An error of reading Well-Known Binary, positions are byte offsets in the data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WkbError {
+    /// The data ended before the geometry was complete.
+    UnexpectedEnd,
+    /// A byte order flag which is neither 0 nor 1.
+    InvalidByteOrder(usize),
+    /// A coordinate which is not a finite number or does not fit the point type.
+    InvalidNumber(usize),
+    /// A geometry type which can not be read as the requested one.
+    UnsupportedGeometry(u32),
+    /// Bytes left after the geometry.
+    TrailingBytes(usize),
+}
+
+impl Display for WkbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WkbError::UnexpectedEnd => write!(f, "unexpected end of WKB"),
+            WkbError::InvalidByteOrder(pos) => write!(f, "invalid byte order at {}", pos),
+            WkbError::InvalidNumber(pos) => write!(f, "invalid coordinate at {}", pos),
+            WkbError::UnsupportedGeometry(code) => write!(f, "unsupported geometry type {}", code),
+            WkbError::TrailingBytes(pos) => write!(f, "trailing bytes at {}", pos),
+        }
+    }
+}
+
+impl std::error::Error for WkbError {}
+
+/// Writes shapes as a `MultiPolygon`.
+/// - `winding`: The winding order of the outer contours in the data.
+pub fn write_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(shapes: &[Shape<P>], winding: Winding) -> Vec<u8> {
+    write_polygons(shapes, winding, float_xy)
+}
+
+/// Writes integer shapes as a `MultiPolygon`.
+/// - `winding`: The winding order of the outer contours in the data.
+pub fn write_int_shapes(shapes: &[IntShape], winding: Winding) -> Vec<u8> {
+    write_polygons(shapes, winding, int_xy)
+}
+
+/// Writes paths as a `MultiLineString`.
+pub fn write_paths<P: FloatPointCompatible<T>, T: FloatNumber>(paths: &[Path<P>]) -> Vec<u8> {
+    write_lines(paths, float_xy)
+}
+
+/// Writes integer paths as a `MultiLineString`.
+pub fn write_int_paths(paths: &[IntPath]) -> Vec<u8> {
+    write_lines(paths, int_xy)
+}
+
+/// Reads shapes from a `Polygon` or a `MultiPolygon`.
+pub fn read_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(data: &[u8]) -> Result<Shapes<P>, WkbError> {
+    Reader::new(data, float_point::<P, T>).read_polygons()
+}
+
+/// Reads integer shapes from a `Polygon` or a `MultiPolygon`, coordinates must be whole numbers.
+pub fn read_int_shapes(data: &[u8]) -> Result<IntShapes, WkbError> {
+    Reader::new(data, int_point).read_polygons()
+}
+
+/// Reads paths from a `LineString` or a `MultiLineString`.
+pub fn read_paths<P: FloatPointCompatible<T>, T: FloatNumber>(data: &[u8]) -> Result<Paths<P>, WkbError> {
+    Reader::new(data, float_point::<P, T>).read_lines()
+}
+
+/// Reads integer paths from a `LineString` or a `MultiLineString`, coordinates must be whole numbers.
+pub fn read_int_paths(data: &[u8]) -> Result<IntPaths, WkbError> {
+    Reader::new(data, int_point).read_lines()
+}
+
+fn write_polygons<P, A: Fn(&P) -> [f64; 2]>(shapes: &[Vec<Vec<P>>], winding: Winding, xy: A) -> Vec<u8> {
+    let mut data = Vec::new();
+    write_header(&mut data, MULTI_POLYGON, shapes.len());
+    for shape in shapes.iter() {
+        write_header(&mut data, POLYGON, shape.len());
+        for (j, contour) in shape.iter().enumerate() {
+            let is_reversed = winding.is_reversed(ring_area(contour, &xy), j == 0);
+            let count = if contour.is_empty() { 0 } else { contour.len() + 1 };
+            write_u32(&mut data, count as u32);
+            if is_reversed {
+                write_points(&mut data, contour.iter().rev(), &xy);
+                write_points(&mut data, contour.last(), &xy);
+            } else {
+                write_points(&mut data, contour.iter(), &xy);
+                write_points(&mut data, contour.first(), &xy);
+            }
+        }
+    }
+    data
+}
+
+fn write_lines<P, A: Fn(&P) -> [f64; 2]>(paths: &[Vec<P>], xy: A) -> Vec<u8> {
+    let mut data = Vec::new();
+    write_header(&mut data, MULTI_LINE_STRING, paths.len());
+    for path in paths.iter() {
+        write_header(&mut data, LINE_STRING, path.len());
+        write_points(&mut data, path.iter(), &xy);
+    }
+    data
+}
+
+#[inline]
+fn write_header(data: &mut Vec<u8>, geometry: u32, count: usize) {
+    data.push(1);
+    write_u32(data, geometry);
+    write_u32(data, count as u32);
+}
+
+#[inline]
+fn write_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&value.to_le_bytes());
+}
+
+#[inline]
+fn write_points<'a, P: 'a, I, A>(data: &mut Vec<u8>, points: I, xy: &A)
+where
+    I: IntoIterator<Item = &'a P>,
+    A: Fn(&P) -> [f64; 2],
+{
+    for p in points {
+        let [x, y] = xy(p);
+        data.extend_from_slice(&x.to_le_bytes());
+        data.extend_from_slice(&y.to_le_bytes());
+    }
+}
+
+struct Reader<'a, P> {
+    data: &'a [u8],
+    pos: usize,
+    is_little_endian: bool,
+    point: fn(f64, f64) -> Option<P>,
+}
+
+impl<'a, P> Reader<'a, P> {
+    fn new(data: &'a [u8], point: fn(f64, f64) -> Option<P>) -> Self {
+        Self { data, pos: 0, is_little_endian: true, point }
+    }
+
+    fn read_polygons(mut self) -> Result<Vec<Vec<Vec<P>>>, WkbError> {
+        let (geometry, dim) = self.header()?;
+        let polygons = match geometry {
+            POLYGON => vec![self.rings(dim)?],
+            MULTI_POLYGON => {
+                let count = self.count(POLYGON_MIN_SIZE)?;
+                let mut polygons = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let dim = self.expect_header(POLYGON)?;
+                    polygons.push(self.rings(dim)?);
+                }
+                polygons
+            }
+            _ => return Err(WkbError::UnsupportedGeometry(geometry)),
+        };
+        self.end()?;
+
+        Ok(polygons
+            .into_iter()
+            .map(|rings| {
+                rings
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (ring, area))| into_contour(ring, &area, i == 0))
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn read_lines(mut self) -> Result<Vec<Vec<P>>, WkbError> {
+        let (geometry, dim) = self.header()?;
+        let lines = match geometry {
+            LINE_STRING => vec![self.points(dim)?.0],
+            MULTI_LINE_STRING => {
+                let count = self.count(LINE_STRING_MIN_SIZE)?;
+                let mut lines = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let dim = self.expect_header(LINE_STRING)?;
+                    lines.push(self.points(dim)?.0);
+                }
+                lines
+            }
+            _ => return Err(WkbError::UnsupportedGeometry(geometry)),
+        };
+        self.end()?;
+
+        Ok(lines)
+    }
+
+    /// Reads the byte order and the type, returns the base type and the count of ordinates.
+    fn header(&mut self) -> Result<(u32, usize), WkbError> {
+        let pos = self.pos;
+        self.is_little_endian = match self.bytes::<1>()? {
+            [0] => false,
+            [1] => true,
+            _ => return Err(WkbError::InvalidByteOrder(pos)),
+        };
+
+        let code = self.u32()?;
+        let mut dim = 2;
+        if code & EWKB_Z != 0 {
+            dim += 1;
+        }
+        if code & EWKB_M != 0 {
+            dim += 1;
+        }
+        if code & EWKB_SRID != 0 {
+            self.u32()?;
+        }
+
+        let iso = code & 0x0FFF_FFFF;
+        let geometry = iso % 1000;
+        dim += match iso / 1000 {
+            0 => 0,
+            1 | 2 => 1,
+            3 => 2,
+            _ => return Err(WkbError::UnsupportedGeometry(code)),
+        };
+
+        Ok((geometry, dim))
+    }
+
+    fn expect_header(&mut self, expected: u32) -> Result<usize, WkbError> {
+        let (geometry, dim) = self.header()?;
+        if geometry == expected {
+            Ok(dim)
+        } else {
+            Err(WkbError::UnsupportedGeometry(geometry))
+        }
+    }
+
+    fn rings(&mut self, dim: usize) -> Result<Vec<(Vec<P>, RingArea)>, WkbError> {
+        let count = self.count(RING_MIN_SIZE)?;
+        let mut rings = Vec::with_capacity(count);
+        for _ in 0..count {
+            rings.push(self.points(dim)?);
+        }
+        Ok(rings)
+    }
+
+    fn points(&mut self, dim: usize) -> Result<(Vec<P>, RingArea), WkbError> {
+        let count = self.count(8 * dim)?;
+
+        let mut area = RingArea::default();
+        let mut points = Vec::with_capacity(count);
+        for _ in 0..count {
+            let pos = self.pos;
+            let x = self.f64()?;
+            let y = self.f64()?;
+            for _ in 2..dim {
+                self.f64()?;
+            }
+            points.push((self.point)(x, y).ok_or(WkbError::InvalidNumber(pos))?);
+            area.add([x, y]);
+        }
+
+        Ok((points, area))
+    }
+
+    /// Reads a count of items, every item takes at least `item_size` bytes,
+    /// so a count which does not fit the remaining data is rejected before any allocation.
+    #[inline]
+    fn count(&mut self, item_size: usize) -> Result<usize, WkbError> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(item_size) > self.data.len() - self.pos {
+            return Err(WkbError::UnexpectedEnd);
+        }
+        Ok(count)
+    }
+
+    #[inline]
+    fn u32(&mut self) -> Result<u32, WkbError> {
+        let bytes = self.bytes::<4>()?;
+        Ok(if self.is_little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+    }
+
+    #[inline]
+    fn f64(&mut self) -> Result<f64, WkbError> {
+        let bytes = self.bytes::<8>()?;
+        Ok(if self.is_little_endian { f64::from_le_bytes(bytes) } else { f64::from_be_bytes(bytes) })
+    }
+
+    #[inline]
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], WkbError> {
+        let bytes = self.data.get(self.pos..self.pos + N).ok_or(WkbError::UnexpectedEnd)?;
+        self.pos += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn end(&self) -> Result<(), WkbError> {
+        if self.pos < self.data.len() {
+            Err(WkbError::TrailingBytes(self.pos))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::format::Winding;
+    use crate::format::wkb::{read_int_paths, read_int_shapes, read_shapes, write_int_paths, write_int_shapes, write_shapes, WkbError};
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x, y + size),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x + size, y),
+        ]
+    }
+
+    #[test]
+    fn test_polygon() {
+        // POLYGON ((0 0, 1 0, 0 1, 0 0)) in big endian
+        let mut data = vec![0, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 4];
+        for v in [0.0f64, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0] {
+            data.extend_from_slice(&v.to_be_bytes());
+        }
+
+        let shapes = read_int_shapes(&data).unwrap();
+        assert_eq!(shapes, vec![vec![vec![IntPoint::new(0, 1), IntPoint::new(1, 0), IntPoint::new(0, 0)]]]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut hole = square(2, 2, 4);
+        hole.reverse();
+        let shapes = vec![vec![square(0, 0, 10), hole], vec![square(20, 0, 5)]];
+
+        for winding in [Winding::Clockwise, Winding::CounterClockwise] {
+            let data = write_int_shapes(&shapes, winding);
+            assert_eq!(read_int_shapes(&data).unwrap(), shapes);
+        }
+
+        let ogc = write_int_shapes(&shapes, Winding::CounterClockwise);
+        let native = write_int_shapes(&shapes, Winding::Clockwise);
+        assert_ne!(ogc, native);
+    }
+
+    #[test]
+    fn test_float_shapes() {
+        let shapes = vec![vec![vec![[0.0, 0.0], [0.0, 0.5], [0.5, 0.5], [0.5, 0.0]]]];
+        let data = write_shapes(&shapes, Winding::CounterClockwise);
+        let result: Vec<Vec<Vec<[f64; 2]>>> = read_shapes(&data).unwrap();
+        assert_eq!(result, shapes);
+    }
+
+    #[test]
+    fn test_paths() {
+        let paths = vec![vec![IntPoint::new(0, 0), IntPoint::new(5, 5)], vec![IntPoint::new(1, 2), IntPoint::new(3, 4), IntPoint::new(5, 0)]];
+        let data = write_int_paths(&paths);
+        assert_eq!(read_int_paths(&data).unwrap(), paths);
+    }
+
+    #[test]
+    fn test_ewkb_z_with_srid() {
+        // LINESTRING Z (1 2 3, 4 5 6) with SRID 4326 in little endian
+        let mut data = vec![1];
+        data.extend_from_slice(&(2u32 | 0x8000_0000 | 0x2000_0000).to_le_bytes());
+        data.extend_from_slice(&4326u32.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        for v in [1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let paths = read_int_paths(&data).unwrap();
+        assert_eq!(paths, vec![vec![IntPoint::new(1, 2), IntPoint::new(4, 5)]]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(read_int_shapes(&[]), Err(WkbError::UnexpectedEnd));
+        assert_eq!(read_int_shapes(&[2, 3, 0, 0, 0]), Err(WkbError::InvalidByteOrder(0)));
+        assert_eq!(read_int_shapes(&[1, 1, 0, 0, 0]), Err(WkbError::UnsupportedGeometry(1)));
+        assert_eq!(read_int_shapes(&[1, 3, 0, 0, 0, 0, 0, 0, 0, 7]), Err(WkbError::TrailingBytes(9)));
+
+        let mut data = write_int_paths(&[vec![IntPoint::new(0, 0), IntPoint::new(1, 1)]]);
+        let last = data.len() - 8;
+        data[last..].copy_from_slice(&0.5f64.to_le_bytes());
+        assert_eq!(read_int_paths(&data), Err(WkbError::InvalidNumber(last - 8)));
+    }
+
+    #[test]
+    fn test_oversized_counts() {
+        let count = [0xff, 0xff, 0xff, 0x7f];
+        for geometry in [3, 6] {
+            let data = [&[1, geometry, 0, 0, 0][..], &count].concat();
+            assert_eq!(read_int_shapes(&data), Err(WkbError::UnexpectedEnd));
+        }
+        for geometry in [2, 5] {
+            let data = [&[1, geometry, 0, 0, 0][..], &count].concat();
+            assert_eq!(read_int_paths(&data), Err(WkbError::UnexpectedEnd));
+        }
+
+        // a polygon with one ring of a huge point count
+        let mut data = vec![1, 3, 0, 0, 0, 1, 0, 0, 0];
+        data.extend_from_slice(&count);
+        assert_eq!(read_int_shapes(&data), Err(WkbError::UnexpectedEnd));
+
+        // a multi polygon with one polygon of a huge ring count
+        let mut data = vec![1, 6, 0, 0, 0, 1, 0, 0, 0, 1, 3, 0, 0, 0];
+        data.extend_from_slice(&count);
+        assert_eq!(read_int_shapes(&data), Err(WkbError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn test_truncated() {
+        let shapes = vec![vec![square(0, 0, 10)], vec![square(20, 0, 5)]];
+        let polygons = write_int_shapes(&shapes, Winding::Clockwise);
+        let polygon = write_int_shapes(&shapes[..1], Winding::Clockwise)[9..].to_vec();
+        assert!(read_int_shapes(&polygon).is_ok());
+        for data in [polygons, polygon] {
+            for len in 0..data.len() {
+                assert_eq!(read_int_shapes(&data[..len]), Err(WkbError::UnexpectedEnd));
+            }
+        }
+
+        let paths = [vec![IntPoint::new(0, 0), IntPoint::new(5, 5)], vec![IntPoint::new(1, 2), IntPoint::new(3, 4)]];
+        let lines = write_int_paths(&paths);
+        let line = write_int_paths(&paths[..1])[9..].to_vec();
+        assert!(read_int_paths(&line).is_ok());
+        for data in [lines, line] {
+            for len in 0..data.len() {
+                assert_eq!(read_int_paths(&data[..len]), Err(WkbError::UnexpectedEnd));
+            }
+        }
+    }
+}
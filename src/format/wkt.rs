@@ -0,0 +1,427 @@
+//! Reading and writing OGC Well-Known Text.
+//!
+//! Shapes are written as `MULTIPOLYGON` and paths as `MULTILINESTRING`. The readers also accept
+//! the single `POLYGON` and `LINESTRING` geometries, `Z`, `M` and `ZM` coordinates are accepted
+//! with the extra ordinates dropped. Read contours are oriented as the overlay produces them:
+//! outer contours are clockwise and holes are counterclockwise.
+
+use std::fmt;
+use std::fmt::{Display, Write};
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_shape::base::data::{Path, Paths, Shape, Shapes};
+use i_shape::int::path::{IntPath, IntPaths};
+use i_shape::int::shape::{IntShape, IntShapes};
+use crate::format::ring::{float_point, float_xy, int_point, int_xy, into_contour, ring_area, RingArea};
+use crate::format::Winding;
+
+/// An error of reading Well-Known Text, positions are byte offsets in the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WktError {
+    /// The text ended before the geometry was complete.
+    UnexpectedEnd,
+    /// A character which does not fit the grammar.
+    UnexpectedChar(usize),
+    /// A coordinate which is not a number or does not fit the point type.
+    InvalidNumber(usize),
+    /// A geometry type which can not be read as the requested one.
+    UnsupportedGeometry(String),
+}
+
+impl Display for WktError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WktError::UnexpectedEnd => write!(f, "unexpected end of WKT"),
+            WktError::UnexpectedChar(pos) => write!(f, "unexpected character at {}", pos),
+            WktError::InvalidNumber(pos) => write!(f, "invalid coordinate at {}", pos),
+            WktError::UnsupportedGeometry(name) => write!(f, "unsupported geometry {}", name),
+        }
+    }
+}
+
+impl std::error::Error for WktError {}
+
+/// Writes shapes as a `MULTIPOLYGON`.
+/// - `winding`: The winding order of the outer contours in the text.
+pub fn write_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(shapes: &[Shape<P>], winding: Winding) -> String {
+    write_polygons(shapes, winding, float_xy, |text, p| write!(text, "{} {}", p.x(), p.y()))
+}
+
+/// Writes integer shapes as a `MULTIPOLYGON`.
+/// - `winding`: The winding order of the outer contours in the text.
+pub fn write_int_shapes(shapes: &[IntShape], winding: Winding) -> String {
+    write_polygons(shapes, winding, int_xy, |text, p| write!(text, "{} {}", p.x, p.y))
+}
+
+/// Writes paths as a `MULTILINESTRING`.
+pub fn write_paths<P: FloatPointCompatible<T>, T: FloatNumber>(paths: &[Path<P>]) -> String {
+    write_lines(paths, |text, p| write!(text, "{} {}", p.x(), p.y()))
+}
+
+/// Writes integer paths as a `MULTILINESTRING`.
+pub fn write_int_paths(paths: &[IntPath]) -> String {
+    write_lines(paths, |text, p| write!(text, "{} {}", p.x, p.y))
+}
+
+/// Reads shapes from a `POLYGON` or a `MULTIPOLYGON`.
+pub fn read_shapes<P: FloatPointCompatible<T>, T: FloatNumber>(text: &str) -> Result<Shapes<P>, WktError> {
+    Parser::new(text, float_point::<P, T>).read_polygons()
+}
+
+/// Reads integer shapes from a `POLYGON` or a `MULTIPOLYGON`, coordinates must be whole numbers.
+pub fn read_int_shapes(text: &str) -> Result<IntShapes, WktError> {
+    Parser::new(text, int_point).read_polygons()
+}
+
+/// Reads paths from a `LINESTRING` or a `MULTILINESTRING`.
+pub fn read_paths<P: FloatPointCompatible<T>, T: FloatNumber>(text: &str) -> Result<Paths<P>, WktError> {
+    Parser::new(text, float_point::<P, T>).read_lines()
+}
+
+/// Reads integer paths from a `LINESTRING` or a `MULTILINESTRING`, coordinates must be whole numbers.
+pub fn read_int_paths(text: &str) -> Result<IntPaths, WktError> {
+    Parser::new(text, int_point).read_lines()
+}
+
+fn write_polygons<P, A, W>(shapes: &[Vec<Vec<P>>], winding: Winding, xy: A, write_point: W) -> String
+where
+    A: Fn(&P) -> [f64; 2],
+    W: Fn(&mut String, &P) -> fmt::Result,
+{
+    let mut text = String::from("MULTIPOLYGON");
+    if shapes.is_empty() {
+        text.push_str(" EMPTY");
+        return text;
+    }
+
+    text.push_str(" (");
+    for (i, shape) in shapes.iter().enumerate() {
+        if i > 0 {
+            text.push_str(", ");
+        }
+        text.push('(');
+        for (j, contour) in shape.iter().enumerate() {
+            if j > 0 {
+                text.push_str(", ");
+            }
+            let is_reversed = winding.is_reversed(ring_area(contour, &xy), j == 0);
+            if is_reversed {
+                write_points(&mut text, contour.iter().rev(), true, &write_point);
+            } else {
+                write_points(&mut text, contour.iter(), true, &write_point);
+            }
+        }
+        text.push(')');
+    }
+    text.push(')');
+
+    text
+}
+
+fn write_lines<P, W>(paths: &[Vec<P>], write_point: W) -> String
+where
+    W: Fn(&mut String, &P) -> fmt::Result,
+{
+    let mut text = String::from("MULTILINESTRING");
+    if paths.is_empty() {
+        text.push_str(" EMPTY");
+        return text;
+    }
+
+    text.push_str(" (");
+    for (i, path) in paths.iter().enumerate() {
+        if i > 0 {
+            text.push_str(", ");
+        }
+        write_points(&mut text, path.iter(), false, &write_point);
+    }
+    text.push(')');
+
+    text
+}
+
+fn write_points<'a, P: 'a, I, W>(text: &mut String, mut points: I, is_closed: bool, write_point: &W)
+where
+    I: Iterator<Item = &'a P>,
+    W: Fn(&mut String, &P) -> fmt::Result,
+{
+    text.push('(');
+    if let Some(first) = points.next() {
+        // writing to a String never fails
+        let _ = write_point(text, first);
+        for p in points {
+            text.push_str(", ");
+            let _ = write_point(text, p);
+        }
+        if is_closed {
+            text.push_str(", ");
+            let _ = write_point(text, first);
+        }
+    }
+    text.push(')');
+}
+
+struct Parser<'a, P> {
+    text: &'a [u8],
+    pos: usize,
+    point: fn(f64, f64) -> Option<P>,
+}
+
+impl<'a, P> Parser<'a, P> {
+    fn new(text: &'a str, point: fn(f64, f64) -> Option<P>) -> Self {
+        Self { text: text.as_bytes(), pos: 0, point }
+    }
+
+    fn read_polygons(mut self) -> Result<Vec<Vec<Vec<P>>>, WktError> {
+        let (name, dim, is_empty) = self.header()?;
+        let polygons = match name.as_str() {
+            "POLYGON" | "MULTIPOLYGON" if is_empty => Vec::new(),
+            "POLYGON" => vec![self.rings(dim)?],
+            "MULTIPOLYGON" => self.list(|parser| parser.rings(dim))?,
+            _ => return Err(WktError::UnsupportedGeometry(name)),
+        };
+        self.end()?;
+
+        Ok(polygons
+            .into_iter()
+            .map(|rings| {
+                rings
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (ring, area))| into_contour(ring, &area, i == 0))
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn read_lines(mut self) -> Result<Vec<Vec<P>>, WktError> {
+        let (name, dim, is_empty) = self.header()?;
+        let lines = match name.as_str() {
+            "LINESTRING" | "MULTILINESTRING" if is_empty => Vec::new(),
+            "LINESTRING" => vec![self.points(dim)?],
+            "MULTILINESTRING" => self.list(|parser| parser.points(dim))?,
+            _ => return Err(WktError::UnsupportedGeometry(name)),
+        };
+        self.end()?;
+
+        Ok(lines.into_iter().map(|(line, _)| line).collect())
+    }
+
+    /// Reads the geometry type, the count of ordinates and the empty flag.
+    fn header(&mut self) -> Result<(String, usize, bool), WktError> {
+        let name = self.keyword()?;
+        if !matches!(name.as_str(), "POLYGON" | "MULTIPOLYGON" | "LINESTRING" | "MULTILINESTRING") {
+            return Err(WktError::UnsupportedGeometry(name));
+        }
+
+        let mut dim = 2;
+        let mut is_empty = false;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) && !is_empty {
+            let pos = self.pos;
+            match self.keyword()?.as_str() {
+                "Z" | "M" if dim == 2 => dim = 3,
+                "ZM" if dim == 2 => dim = 4,
+                "EMPTY" => is_empty = true,
+                _ => return Err(WktError::UnexpectedChar(pos)),
+            }
+        }
+
+        Ok((name, dim, is_empty))
+    }
+
+    fn keyword(&mut self) -> Result<String, WktError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_alphabetic() {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.unexpected());
+        }
+        Ok(String::from_utf8_lossy(&self.text[start..self.pos]).to_ascii_uppercase())
+    }
+
+    /// Reads a comma separated list in parentheses.
+    fn list<E, F>(&mut self, read: F) -> Result<Vec<E>, WktError>
+    where
+        F: Fn(&mut Self) -> Result<E, WktError>,
+    {
+        self.expect(b'(')?;
+        let mut items = vec![read(self)?];
+        while self.peek() == Some(b',') {
+            self.pos += 1;
+            items.push(read(self)?);
+        }
+        self.expect(b')')?;
+        Ok(items)
+    }
+
+    fn rings(&mut self, dim: usize) -> Result<Vec<(Vec<P>, RingArea)>, WktError> {
+        self.list(|parser| parser.points(dim))
+    }
+
+    fn points(&mut self, dim: usize) -> Result<(Vec<P>, RingArea), WktError> {
+        let mut area = RingArea::default();
+        let points = self.list(|parser| {
+            parser.skip_whitespace();
+            let pos = parser.pos;
+            let x = parser.number()?;
+            let y = parser.number()?;
+            for _ in 2..dim {
+                parser.number()?;
+            }
+            Ok(((parser.point)(x, y), [x, y], pos))
+        })?;
+
+        let mut result = Vec::with_capacity(points.len());
+        for (point, xy, pos) in points {
+            result.push(point.ok_or(WktError::InvalidNumber(pos))?);
+            area.add(xy);
+        }
+
+        Ok((result, area))
+    }
+
+    fn number(&mut self) -> Result<f64, WktError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.pos < self.text.len() && matches!(self.text[self.pos], b'0'..=b'9' | b'+' | b'-' | b'.' | b'e' | b'E') {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.unexpected());
+        }
+        std::str::from_utf8(&self.text[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or(WktError::InvalidNumber(start))
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), WktError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn end(&mut self) -> Result<(), WktError> {
+        self.skip_whitespace();
+        if self.pos < self.text.len() {
+            Err(WktError::UnexpectedChar(self.pos))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Skips whitespaces and returns the next character.
+    #[inline]
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.text.get(self.pos).copied()
+    }
+
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.text.len() && self.text[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    #[inline]
+    fn unexpected(&self) -> WktError {
+        if self.pos < self.text.len() {
+            WktError::UnexpectedChar(self.pos)
+        } else {
+            WktError::UnexpectedEnd
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::float::single::SingleFloatOverlay;
+    use crate::format::Winding;
+    use crate::format::wkt::{read_int_paths, read_int_shapes, read_paths, read_shapes, write_int_paths, write_int_shapes, write_paths, write_shapes, WktError};
+
+    #[test]
+    fn test_write_shapes() {
+        let subj = [[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+        let clip = [[0.5, 0.5], [0.5, 1.5], [1.5, 1.5], [1.5, 0.5]];
+        let shapes = subj.overlay(&clip, OverlayRule::Difference, FillRule::NonZero);
+
+        let ogc = write_shapes(&shapes, Winding::CounterClockwise);
+        assert_eq!(
+            ogc,
+            "MULTIPOLYGON (((2 0, 2 2, 0 2, 0 0, 2 0), (1.5 1.5, 1.5 0.5, 0.5 0.5, 0.5 1.5, 1.5 1.5)))"
+        );
+
+        let native = write_shapes(&shapes, Winding::Clockwise);
+        assert_eq!(
+            native,
+            "MULTIPOLYGON (((0 0, 0 2, 2 2, 2 0, 0 0), (0.5 1.5, 0.5 0.5, 1.5 0.5, 1.5 1.5, 0.5 1.5)))"
+        );
+    }
+
+    #[test]
+    fn test_read_shapes() {
+        let shapes: Vec<Vec<Vec<[f64; 2]>>> = read_shapes(
+            "MULTIPOLYGON (((0 0, 2 0, 2 2, 0 2, 0 0), (0.5 0.5, 0.5 1.5, 1.5 1.5, 1.5 0.5, 0.5 0.5)))"
+        ).unwrap();
+
+        assert_eq!(shapes, vec![vec![
+            vec![[0.0, 2.0], [2.0, 2.0], [2.0, 0.0], [0.0, 0.0]],
+            vec![[1.5, 0.5], [1.5, 1.5], [0.5, 1.5], [0.5, 0.5]],
+        ]]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = "MULTIPOLYGON (((0 0, 10 0, 10 10, 0 10, 0 0)), ((20 0, 30 0, 25 5, 20 0)))";
+        let shapes = read_int_shapes(text).unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(write_int_shapes(&shapes, Winding::CounterClockwise), text);
+    }
+
+    #[test]
+    fn test_polygon_z() {
+        let shapes = read_int_shapes("polygon z ((0 0 1, 0 10 1, 10 10 1, 0 0 1))").unwrap();
+        assert_eq!(shapes, vec![vec![vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(0, 10),
+            IntPoint::new(10, 10),
+        ]]]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let shapes = read_int_shapes("MULTIPOLYGON EMPTY").unwrap();
+        assert!(shapes.is_empty());
+        assert_eq!(write_int_shapes(&shapes, Winding::CounterClockwise), "MULTIPOLYGON EMPTY");
+        assert_eq!(write_int_paths(&[]), "MULTILINESTRING EMPTY");
+    }
+
+    #[test]
+    fn test_paths() {
+        let paths: Vec<Vec<[f32; 2]>> = read_paths("LINESTRING (0 0, 1.5 1, 2 0)").unwrap();
+        assert_eq!(paths, vec![vec![[0.0, 0.0], [1.5, 1.0], [2.0, 0.0]]]);
+        assert_eq!(write_paths(&paths), "MULTILINESTRING ((0 0, 1.5 1, 2 0))");
+
+        let text = "MULTILINESTRING ((0 0, 1 1), (5 5, 6 6, 7 5))";
+        assert_eq!(write_int_paths(&read_int_paths(text).unwrap()), text);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(read_int_shapes("POINT (1 1)"), Err(WktError::UnsupportedGeometry("POINT".to_string())));
+        assert_eq!(read_int_shapes("LINESTRING (1 1, 2 2)"), Err(WktError::UnsupportedGeometry("LINESTRING".to_string())));
+        assert_eq!(read_int_paths("POLYGON EMPTY"), Err(WktError::UnsupportedGeometry("POLYGON".to_string())));
+        assert_eq!(read_int_shapes("POLYGON ((0 0, 1 1"), Err(WktError::UnexpectedEnd));
+        assert_eq!(read_int_shapes("POLYGON ((0 0, 1 1.5, 1 0))"), Err(WktError::InvalidNumber(15)));
+        assert_eq!(read_int_shapes("POLYGON ((0 0, 1 1, 1 0)) x"), Err(WktError::UnexpectedChar(26)));
+    }
+}
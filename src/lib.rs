@@ -196,6 +196,15 @@ pub mod float;
 pub mod string;
 pub mod segm;
 
+#[cfg(any(feature = "wkt", feature = "wkb", feature = "geojson", feature = "svg"))]
+pub mod format;
+
+#[cfg(feature = "record")]
+pub mod record;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
 pub(crate) mod split;
 pub(crate) mod bind;
 pub(crate) mod geom;
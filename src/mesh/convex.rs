@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::Contour;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
+use crate::float::source::resource::OverlayResource;
+use crate::mesh::triangulation::ear_clip::EarClipper;
+
+/// The `IntConvexPartition` trait splits integer shapes with holes into convex pieces.
+///
+/// The pieces are built by the Hertel–Mehlhorn algorithm: a triangulation is merged back
+/// while the merged pieces stay convex, so the count of pieces is at most four times the optimal one.
+///
+/// Contours are expected to be oriented as the overlay produces them: outer contours are clockwise
+/// and holes are counterclockwise.
+pub trait IntConvexPartition {
+    /// Splits the shapes into convex clockwise contours.
+    fn convex_partition(&self) -> Vec<IntContour>;
+}
+
+impl IntConvexPartition for IntShapes {
+    #[inline]
+    fn convex_partition(&self) -> Vec<IntContour> {
+        let mut contours = Vec::new();
+        for shape in self.iter() {
+            append_convex_contours(shape, &mut contours);
+        }
+        contours
+    }
+}
+
+impl IntConvexPartition for IntShape {
+    #[inline]
+    fn convex_partition(&self) -> Vec<IntContour> {
+        let mut contours = Vec::new();
+        append_convex_contours(self, &mut contours);
+        contours
+    }
+}
+
+impl IntConvexPartition for [IntPoint] {
+    #[inline]
+    fn convex_partition(&self) -> Vec<IntContour> {
+        let mut contours = Vec::new();
+        append_convex_contours(&[self], &mut contours);
+        contours
+    }
+}
+
+/// The `FloatConvexPartition` trait splits float shapes with holes into convex pieces.
+///
+/// The pieces are built by the Hertel–Mehlhorn algorithm: a triangulation is merged back
+/// while the merged pieces stay convex, so the count of pieces is at most four times the optimal one.
+///
+/// Contours are expected to be oriented as the overlay produces them: outer contours are clockwise
+/// and holes are counterclockwise. The pieces consist of the source points.
+pub trait FloatConvexPartition<P, T>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Splits the contours, or shapes into convex clockwise contours.
+    fn convex_partition(&self) -> Vec<Contour<P>>;
+}
+
+impl<S, P, T> FloatConvexPartition<P, T> for S
+where
+    S: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    fn convex_partition(&self) -> Vec<Contour<P>> {
+        let adapter = FloatPointAdapter::with_iter(self.iter_paths().flatten());
+
+        let contours: Vec<IntContour> = self
+            .iter_paths()
+            .map(|path| path.iter().map(|p| adapter.float_to_int(p)).collect())
+            .collect();
+
+        let int_points: Vec<IntPoint> = contours.iter().flatten().copied().collect();
+        let triangles = EarClipper::triangulate(contours.iter().map(|contour| contour.as_slice()));
+
+        let points: Vec<P> = self.iter_paths().flatten().copied().collect();
+
+        merge_triangles(&int_points, triangles)
+            .into_iter()
+            .map(|piece| piece.iter().rev().map(|&i| points[i]).collect())
+            .collect()
+    }
+}
+
+fn append_convex_contours<C: AsRef<[IntPoint]>>(shape: &[C], contours: &mut Vec<IntContour>) {
+    let points: Vec<IntPoint> = shape.iter().flat_map(|contour| contour.as_ref().iter().copied()).collect();
+    let triangles = EarClipper::triangulate(shape.iter().map(|contour| contour.as_ref()));
+
+    for piece in merge_triangles(&points, triangles) {
+        contours.push(piece.iter().rev().map(|&i| points[i]).collect());
+    }
+}
+
+/// Removes inner edges of counterclockwise triangles while both of their ends stay convex.
+///
+/// Returns counterclockwise pieces as lists of point indices.
+fn merge_triangles(points: &[IntPoint], triangles: Vec<[usize; 3]>) -> Vec<Vec<usize>> {
+    let mut pieces: Vec<Vec<usize>> = triangles.iter().map(|t| t.to_vec()).collect();
+    let mut is_alive = vec![true; pieces.len()];
+
+    // every directed edge is mapped to its piece
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::with_capacity(3 * triangles.len());
+    for (i, t) in triangles.iter().enumerate() {
+        edges.insert((t[0], t[1]), i);
+        edges.insert((t[1], t[2]), i);
+        edges.insert((t[2], t[0]), i);
+    }
+
+    for t in triangles.iter() {
+        for k in 0..3 {
+            let a = t[k];
+            let b = t[(k + 1) % 3];
+            let (p0, p1) = match (edges.get(&(a, b)), edges.get(&(b, a))) {
+                (Some(&p0), Some(&p1)) if p0 != p1 => (p0, p1),
+                _ => continue,
+            };
+
+            let piece0 = &pieces[p0];
+            let piece1 = &pieces[p1];
+
+            // piece0 contains a -> b, piece1 contains b -> a
+            let n0 = piece0.len();
+            let n1 = piece1.len();
+            let i0 = edge_position(piece0, a, b);
+            let i1 = edge_position(piece1, b, a);
+
+            let a_prev = piece0[(i0 + n0 - 1) % n0];
+            let b_next = piece0[(i0 + 2) % n0];
+            let b_prev = piece1[(i1 + n1 - 1) % n1];
+            let a_next = piece1[(i1 + 2) % n1];
+
+            if !is_convex(points[a_prev], points[a], points[a_next])
+                || !is_convex(points[b_prev], points[b], points[b_next])
+            {
+                continue;
+            }
+
+            // merged: b .. a from piece0, then a .. b from piece1 without the shared ends
+            let mut merged = Vec::with_capacity(n0 + n1 - 2);
+            for j in 0..n0 {
+                merged.push(piece0[(i0 + 1 + j) % n0]);
+            }
+            for j in 2..n1 {
+                merged.push(piece1[(i1 + j) % n1]);
+            }
+
+            edges.remove(&(a, b));
+            edges.remove(&(b, a));
+            for j in 0..n1 {
+                let e = (piece1[j], piece1[(j + 1) % n1]);
+                if let Some(p) = edges.get_mut(&e) {
+                    *p = p0;
+                }
+            }
+
+            pieces[p0] = merged;
+            pieces[p1].clear();
+            is_alive[p1] = false;
+        }
+    }
+
+    pieces
+        .into_iter()
+        .zip(is_alive)
+        .filter_map(|(piece, is_alive)| if is_alive { Some(piece) } else { None })
+        .collect()
+}
+
+#[inline]
+fn edge_position(piece: &[usize], a: usize, b: usize) -> usize {
+    let n = piece.len();
+    (0..n).find(|&i| piece[i] == a && piece[(i + 1) % n] == b).unwrap()
+}
+
+/// A counterclockwise corner is convex if it turns left or goes straight.
+#[inline]
+fn is_convex(a: IntPoint, b: IntPoint, c: IntPoint) -> bool {
+    let v0 = b - a;
+    let v1 = c - b;
+    let cross = v0.cross_product(v1);
+    cross > 0 || cross == 0 && v0.dot_product(v1) > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay::Overlay;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::mesh::convex::{FloatConvexPartition, IntConvexPartition};
+
+    fn area(path: &[IntPoint]) -> i64 {
+        let mut area = 0;
+        let mut p0 = path[path.len() - 1];
+        for &p1 in path.iter() {
+            area += p1.x as i64 * p0.y as i64 - p1.y as i64 * p0.x as i64;
+            p0 = p1;
+        }
+        area
+    }
+
+    /// Every piece must be clockwise and convex, returns the total double area.
+    fn assert_convex(pieces: &[Vec<IntPoint>]) -> i64 {
+        let mut total = 0;
+        for piece in pieces.iter() {
+            let n = piece.len();
+            for i in 0..n {
+                let a = piece[i];
+                let b = piece[(i + 1) % n];
+                let c = piece[(i + 2) % n];
+                assert!((b - a).cross_product(c - b) <= 0, "piece is not convex");
+            }
+            let a = area(piece);
+            assert!(a > 0);
+            total += a;
+        }
+        total
+    }
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x, y + size),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x + size, y),
+        ]
+    }
+
+    #[test]
+    fn test_convex_contour() {
+        let pieces = square(0, 0, 10).as_slice().convex_partition();
+
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].len(), 4);
+        assert_eq!(assert_convex(&pieces), 200);
+    }
+
+    #[test]
+    fn test_l_shape() {
+        let path = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(0, 10),
+            IntPoint::new(5, 10),
+            IntPoint::new(5, 5),
+            IntPoint::new(10, 5),
+            IntPoint::new(10, 0),
+        ];
+
+        let pieces = path.as_slice().convex_partition();
+
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(assert_convex(&pieces), 150);
+    }
+
+    #[test]
+    fn test_shape_with_hole() {
+        let mut hole = square(3, 3, 4);
+        hole.reverse();
+        let shape = vec![square(0, 0, 10), hole];
+
+        let pieces = shape.convex_partition();
+
+        assert!(pieces.len() >= 4);
+        assert_eq!(assert_convex(&pieces), 2 * (100 - 16));
+    }
+
+    #[test]
+    fn test_overlay_result() {
+        let subj = vec![square(0, 0, 20)];
+        let clip = vec![square(2, 2, 4), square(6, 6, 4), square(12, 2, 4), square(6, 12, 4)];
+
+        let shapes = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Difference, FillRule::NonZero);
+        let pieces = shapes.convex_partition();
+
+        assert_eq!(assert_convex(&pieces), 2 * (400 - 4 * 16));
+    }
+
+    #[test]
+    fn test_float_l_shape() {
+        let path = vec![[0.0, 0.0], [0.0, 1.0], [0.5, 1.0], [0.5, 0.5], [1.0, 0.5], [1.0, 0.0]];
+
+        let pieces = path.convex_partition();
+
+        assert_eq!(pieces.len(), 2);
+        for piece in pieces.iter() {
+            for p in piece.iter() {
+                assert!(path.contains(p));
+            }
+        }
+    }
+}
@@ -0,0 +1,469 @@
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::float::rect::FloatRect;
+use i_float::int::point::IntPoint;
+use i_shape::base::data::Shapes;
+use i_shape::float::adapter::ShapesToFloat;
+use i_shape::int::shape::{IntShape, IntShapes};
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::{Overlay, ShapeType};
+use crate::core::overlay_rule::OverlayRule;
+use crate::float::simplify::SimplifyShape;
+use crate::float::source::resource::OverlayResource;
+
+/// The `IntMinkowski` trait provides the Minkowski sum and difference of integer shapes.
+///
+/// Both operands may be non-convex and may contain holes. The result coordinates are sums of
+/// the source coordinates, so they must fit into `i32`.
+pub trait IntMinkowski {
+    /// Computes the Minkowski sum `{ a + b | a ∈ self, b ∈ other }`.
+    ///
+    /// - `other`: The second operand.
+    /// - `fill_rule`: Fill rule to determine filled areas of both operands (non-zero, even-odd, positive, negative).
+    ///
+    /// Returns an `IntShapes` collection representing the sum.
+    fn minkowski_sum(&self, other: &Self, fill_rule: FillRule) -> IntShapes;
+
+    /// Computes the Minkowski difference `{ a - b | a ∈ self, b ∈ other }`,
+    /// which is the sum of `self` with `other` reflected through the origin.
+    ///
+    /// - `other`: The second operand.
+    /// - `fill_rule`: Fill rule to determine filled areas of both operands (non-zero, even-odd, positive, negative).
+    ///
+    /// Returns an `IntShapes` collection representing the difference.
+    fn minkowski_difference(&self, other: &Self, fill_rule: FillRule) -> IntShapes;
+}
+
+impl IntMinkowski for IntShapes {
+    #[inline]
+    fn minkowski_sum(&self, other: &Self, fill_rule: FillRule) -> IntShapes {
+        let a = Overlay::with_shapes(self, &[]).overlay(OverlayRule::Subject, fill_rule);
+        let b = Overlay::with_shapes(other, &[]).overlay(OverlayRule::Subject, fill_rule);
+        int_minkowski_sum(&a, &b)
+    }
+
+    #[inline]
+    fn minkowski_difference(&self, other: &Self, fill_rule: FillRule) -> IntShapes {
+        let a = Overlay::with_shapes(self, &[]).overlay(OverlayRule::Subject, fill_rule);
+        let b = Overlay::with_shapes(other, &[]).overlay(OverlayRule::Subject, fill_rule);
+        int_minkowski_sum(&a, &int_reflected(&b))
+    }
+}
+
+impl IntMinkowski for IntShape {
+    #[inline]
+    fn minkowski_sum(&self, other: &Self, fill_rule: FillRule) -> IntShapes {
+        let a = Overlay::with_contours(self, &[]).overlay(OverlayRule::Subject, fill_rule);
+        let b = Overlay::with_contours(other, &[]).overlay(OverlayRule::Subject, fill_rule);
+        int_minkowski_sum(&a, &b)
+    }
+
+    #[inline]
+    fn minkowski_difference(&self, other: &Self, fill_rule: FillRule) -> IntShapes {
+        let a = Overlay::with_contours(self, &[]).overlay(OverlayRule::Subject, fill_rule);
+        let b = Overlay::with_contours(other, &[]).overlay(OverlayRule::Subject, fill_rule);
+        int_minkowski_sum(&a, &int_reflected(&b))
+    }
+}
+
+impl IntMinkowski for [IntPoint] {
+    #[inline]
+    fn minkowski_sum(&self, other: &Self, fill_rule: FillRule) -> IntShapes {
+        let a = Overlay::with_contour(self, &[]).overlay(OverlayRule::Subject, fill_rule);
+        let b = Overlay::with_contour(other, &[]).overlay(OverlayRule::Subject, fill_rule);
+        int_minkowski_sum(&a, &b)
+    }
+
+    #[inline]
+    fn minkowski_difference(&self, other: &Self, fill_rule: FillRule) -> IntShapes {
+        let a = Overlay::with_contour(self, &[]).overlay(OverlayRule::Subject, fill_rule);
+        let b = Overlay::with_contour(other, &[]).overlay(OverlayRule::Subject, fill_rule);
+        int_minkowski_sum(&a, &int_reflected(&b))
+    }
+}
+
+/// The `FloatMinkowski` trait provides the Minkowski sum and difference of float shapes.
+///
+/// Both operands may be non-convex and may contain holes.
+pub trait FloatMinkowski<R, P, T>
+where
+    R: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Computes the Minkowski sum `{ a + b | a ∈ self, b ∈ resource }`.
+    ///
+    /// - `resource`: The second operand.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    /// - `fill_rule`: Fill rule to determine filled areas of both operands (non-zero, even-odd, positive, negative).
+    ///
+    /// Returns a `Shapes<P>` collection representing the sum.
+    fn minkowski_sum(&self, resource: &R, fill_rule: FillRule) -> Shapes<P>;
+
+    /// Computes the Minkowski difference `{ a - b | a ∈ self, b ∈ resource }`,
+    /// which is the sum of `self` with `resource` reflected through the origin.
+    ///
+    /// - `resource`: The second operand.
+    ///   `OverlayResource` can be one of the following:
+    ///     - `Contour`: A contour representing a closed path.
+    ///     - `Contours`: A collection of contours, each representing a closed path.
+    ///     - `Shapes`: A collection of shapes, where each shape may consist of multiple contours.
+    /// - `fill_rule`: Fill rule to determine filled areas of both operands (non-zero, even-odd, positive, negative).
+    ///
+    /// Returns a `Shapes<P>` collection representing the difference.
+    fn minkowski_difference(&self, resource: &R, fill_rule: FillRule) -> Shapes<P>;
+}
+
+impl<R0, R1, P, T> FloatMinkowski<R0, P, T> for R1
+where
+    R0: OverlayResource<P, T>,
+    R1: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn minkowski_sum(&self, resource: &R0, fill_rule: FillRule) -> Shapes<P> {
+        let a = self.simplify_shape(fill_rule, T::from_float(0.0));
+        let b = resource.simplify_shape(fill_rule, T::from_float(0.0));
+        float_minkowski_sum(&a, &b)
+    }
+
+    #[inline]
+    fn minkowski_difference(&self, resource: &R0, fill_rule: FillRule) -> Shapes<P> {
+        let a = self.simplify_shape(fill_rule, T::from_float(0.0));
+        let b = resource.simplify_shape(fill_rule, T::from_float(0.0));
+        float_minkowski_sum(&a, &float_reflected(&b))
+    }
+}
+
+/// Both operands must be normalized: outer contours are clockwise, holes are counterclockwise
+/// and every shape is connected, as it is returned by the overlay.
+fn int_minkowski_sum(a: &IntShapes, b: &IntShapes) -> IntShapes {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut sink = IntSink { overlay: Overlay::new(capacity(a, b)) };
+    convolve(a, b, |p0, p1| p0 + p1, &mut sink);
+
+    sink.overlay.overlay(OverlayRule::Subject, FillRule::Positive)
+}
+
+fn float_minkowski_sum<P, T>(a: &Shapes<P>, b: &Shapes<P>) -> Shapes<P>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    let (a_rect, b_rect) = match (
+        FloatRect::with_iter(a.iter().flatten().flatten()),
+        FloatRect::with_iter(b.iter().flatten().flatten()),
+    ) {
+        (Some(a_rect), Some(b_rect)) => (a_rect, b_rect),
+        _ => return vec![],
+    };
+
+    let rect = FloatRect::new(
+        a_rect.min_x + b_rect.min_x,
+        a_rect.max_x + b_rect.max_x,
+        a_rect.min_y + b_rect.min_y,
+        a_rect.max_y + b_rect.max_y,
+    );
+
+    let adapter = FloatPointAdapter::new(rect);
+
+    let mut sink = FloatSink { overlay: Overlay::new(capacity(a, b)), adapter };
+    convolve(a, b, |p0: P, p1: P| P::from_xy(p0.x() + p1.x(), p0.y() + p1.y()), &mut sink);
+
+    let shapes = sink.overlay.overlay(OverlayRule::Subject, FillRule::Positive);
+
+    shapes.to_float(&sink.adapter)
+}
+
+/// Collects the contours whose union with the positive fill rule is the Minkowski sum.
+///
+/// For connected shapes `A` and `B` the sum is covered by:
+/// - `A` translated by any point of `B`,
+/// - `B` translated by any point of every boundary contour of `A`,
+/// - the parallelograms `ea ⊕ eb` of every edge pair of `A` and `B`.
+fn convolve<Q, F, S>(a: &[Vec<Vec<Q>>], b: &[Vec<Vec<Q>>], add: F, sink: &mut S)
+where
+    Q: Copy,
+    F: Fn(Q, Q) -> Q,
+    S: MinkowskiSink<Q>,
+{
+    for b_shape in b.iter() {
+        let b0 = b_shape[0][0];
+        for a_contour in a.iter().flatten() {
+            sink.add_contour(a_contour.iter().map(|&p| add(p, b0)));
+        }
+    }
+
+    for a_contour in a.iter().flatten() {
+        let a0 = a_contour[0];
+        for b_contour in b.iter().flatten() {
+            sink.add_contour(b_contour.iter().map(|&p| add(a0, p)));
+        }
+    }
+
+    for a_contour in a.iter().flatten() {
+        let mut a0 = a_contour[a_contour.len() - 1];
+        for &a1 in a_contour.iter() {
+            for b_contour in b.iter().flatten() {
+                let mut b0 = b_contour[b_contour.len() - 1];
+                for &b1 in b_contour.iter() {
+                    sink.add_quad([add(a0, b0), add(a1, b0), add(a1, b1), add(a0, b1)]);
+                    b0 = b1;
+                }
+            }
+            a0 = a1;
+        }
+    }
+}
+
+fn capacity<Q>(a: &[Vec<Vec<Q>>], b: &[Vec<Vec<Q>>]) -> usize {
+    let a_count = a.iter().flatten().fold(0, |s, c| s + c.len());
+    let b_count = b.iter().flatten().fold(0, |s, c| s + c.len());
+    a_count * b.len() + b_count * a.iter().flatten().count() + 4 * a_count * b_count
+}
+
+trait MinkowskiSink<Q> {
+    /// Adds an already oriented contour.
+    fn add_contour<I: Iterator<Item = Q>>(&mut self, iter: I);
+
+    /// Adds a quad of any orientation, degenerate quads are skipped.
+    fn add_quad(&mut self, quad: [Q; 4]);
+}
+
+struct IntSink {
+    overlay: Overlay,
+}
+
+struct FloatSink<P: FloatPointCompatible<T>, T: FloatNumber> {
+    overlay: Overlay,
+    adapter: FloatPointAdapter<P, T>,
+}
+
+impl MinkowskiSink<IntPoint> for IntSink {
+    #[inline]
+    fn add_contour<I: Iterator<Item = IntPoint>>(&mut self, iter: I) {
+        self.overlay.add_path_iter(iter, ShapeType::Subject);
+    }
+
+    #[inline]
+    fn add_quad(&mut self, quad: [IntPoint; 4]) {
+        add_oriented_quad(&mut self.overlay, quad);
+    }
+}
+
+impl<P: FloatPointCompatible<T>, T: FloatNumber> MinkowskiSink<P> for FloatSink<P, T> {
+    #[inline]
+    fn add_contour<I: Iterator<Item = P>>(&mut self, iter: I) {
+        self.overlay.add_path_iter(iter.map(|p| self.adapter.float_to_int(&p)), ShapeType::Subject);
+    }
+
+    #[inline]
+    fn add_quad(&mut self, quad: [P; 4]) {
+        let quad = quad.map(|p| self.adapter.float_to_int(&p));
+        add_oriented_quad(&mut self.overlay, quad);
+    }
+}
+
+#[inline]
+fn add_oriented_quad(overlay: &mut Overlay, mut quad: [IntPoint; 4]) {
+    let mut area: i64 = 0;
+    let mut p0 = quad[3];
+    for &p1 in quad.iter() {
+        area += p1.x as i64 * p0.y as i64 - p1.y as i64 * p0.x as i64;
+        p0 = p1;
+    }
+
+    if area == 0 {
+        return;
+    }
+
+    if area < 0 {
+        // outer contours must be clockwise to be filled by the positive rule
+        quad.reverse();
+    }
+
+    overlay.add_contour(&quad, ShapeType::Subject);
+}
+
+fn int_reflected(shapes: &IntShapes) -> IntShapes {
+    shapes.iter()
+        .map(|shape| shape.iter()
+            .map(|path| path.iter().map(|p| IntPoint::new(-p.x, -p.y)).collect())
+            .collect())
+        .collect()
+}
+
+fn float_reflected<P: FloatPointCompatible<T>, T: FloatNumber>(shapes: &Shapes<P>) -> Shapes<P> {
+    shapes.iter()
+        .map(|shape| shape.iter()
+            .map(|path| path.iter().map(|p| P::from_xy(-p.x(), -p.y())).collect())
+            .collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_shape::int::path::PointPathExtension;
+    use crate::core::fill_rule::FillRule;
+    use crate::mesh::minkowski::{FloatMinkowski, IntMinkowski};
+
+    fn int_area(shapes: &[Vec<Vec<IntPoint>>]) -> i64 {
+        shapes.iter().flatten().map(|path| path.unsafe_area()).sum::<i64>() / 2
+    }
+
+    fn float_area(shapes: &[Vec<Vec<[f64; 2]>>]) -> f64 {
+        let mut area = 0.0;
+        for path in shapes.iter().flatten() {
+            let mut p0 = path[path.len() - 1];
+            for &p1 in path.iter() {
+                area += p1[0] * p0[1] - p1[1] * p0[0];
+                p0 = p1;
+            }
+        }
+        0.5 * area
+    }
+
+    fn int_square(x: i32, y: i32, size: i32) -> Vec<IntPoint> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x, y + size),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x + size, y),
+        ]
+    }
+
+    #[test]
+    fn test_squares() {
+        let a = int_square(0, 0, 10);
+        let b = int_square(-1, -1, 2);
+
+        let shapes = a.as_slice().minkowski_sum(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_eq!(shapes[0][0].len(), 4);
+        assert_eq!(int_area(&shapes), 144);
+    }
+
+    #[test]
+    fn test_counter_clockwise_input() {
+        let mut a = int_square(0, 0, 10);
+        a.reverse();
+        let mut b = int_square(-1, -1, 2);
+        b.reverse();
+
+        let shapes = a.as_slice().minkowski_sum(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(int_area(&shapes), 144);
+    }
+
+    #[test]
+    fn test_difference() {
+        let a = int_square(0, 0, 10);
+        let b = int_square(0, 0, 2);
+
+        let shapes = a.as_slice().minkowski_difference(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0][0].len(), 4);
+        assert_eq!(int_area(&shapes), 144);
+        assert!(shapes[0][0].contains(IntPoint::new(-1, -1)));
+        assert!(shapes[0][0].contains(IntPoint::new(9, 9)));
+    }
+
+    #[test]
+    fn test_hole_shrinks() {
+        let mut hole = int_square(2, 2, 6);
+        hole.reverse();
+        let a = vec![vec![int_square(0, 0, 10), hole]];
+        let b = vec![vec![int_square(-1, -1, 2)]];
+
+        let shapes = a.minkowski_sum(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+        assert_eq!(int_area(&shapes), 144 - 16);
+    }
+
+    #[test]
+    fn test_hole_closes() {
+        let mut hole = int_square(4, 4, 2);
+        hole.reverse();
+        let a = vec![vec![int_square(0, 0, 10), hole]];
+        let b = vec![vec![int_square(-2, -2, 4)]];
+
+        let shapes = a.minkowski_sum(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_eq!(int_area(&shapes), 196);
+    }
+
+    #[test]
+    fn test_non_convex() {
+        // L-shape
+        let a = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(0, 10),
+            IntPoint::new(5, 10),
+            IntPoint::new(5, 5),
+            IntPoint::new(10, 5),
+            IntPoint::new(10, 0),
+        ];
+        let b = int_square(0, 0, 1);
+
+        let shapes = a.as_slice().minkowski_sum(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_eq!(shapes[0][0].len(), 6);
+        assert_eq!(int_area(&shapes), 75 + 10 + 10 + 1);
+    }
+
+    #[test]
+    fn test_empty() {
+        let a = int_square(0, 0, 10);
+        let b: Vec<IntPoint> = vec![];
+
+        let shapes = a.as_slice().minkowski_sum(&b, FillRule::NonZero);
+
+        assert!(shapes.is_empty());
+    }
+
+    #[test]
+    fn test_float_triangle() {
+        let a = vec![[0.0, 0.0], [0.0, 4.0], [4.0, 0.0]];
+        let b = vec![[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+
+        let shapes = a.minkowski_sum(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 1);
+        assert_eq!(shapes[0][0].len(), 5);
+        assert!((float_area(&shapes) - (8.0 + 4.0 * 2.0 * 2.0 + 4.0)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_float_separated() {
+        let a = vec![
+            vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]],
+            vec![[5.0, 0.0], [5.0, 1.0], [6.0, 1.0], [6.0, 0.0]],
+        ];
+        let b = vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+
+        let shapes = a.minkowski_sum(&b, FillRule::NonZero);
+
+        assert_eq!(shapes.len(), 2);
+        assert!((float_area(&shapes) - 8.0).abs() < 0.001);
+    }
+}
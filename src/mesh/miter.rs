@@ -1,6 +1,7 @@
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
+use i_float::float::vector::FloatPointMath;
 use i_float::int::point::IntPoint;
 
 pub(super) struct Miter;
@@ -57,4 +58,53 @@ impl Miter {
             SharpMiter::AcB(ia, ic, ib)
         }
     }
+
+    /// Miter for sections with different offsets, where the miter point is not on the bisector.
+    /// Returns `None` if the miter point is farther than `max_a` or `max_b` from `pa` or `pb`.
+    #[inline]
+    pub(super) fn sharp_uneven<T: FloatNumber, P: FloatPointCompatible<T>>(
+        pa: P,
+        pb: P,
+        va: P,
+        vb: P,
+        max_a: T,
+        max_b: T,
+        adapter: &FloatPointAdapter<P, T>,
+    ) -> Option<SharpMiter> {
+        let ia = adapter.float_to_int(&pa);
+        let ib = adapter.float_to_int(&pb);
+
+        if ia == ib {
+            return Some(SharpMiter::Degenerate);
+        }
+
+        // solve pa + k * va = pb - m * vb
+        let cross = FloatPointMath::cross_product(&va, &vb);
+        if cross.abs() < T::from_float(0.000_001) {
+            // almost parallel sections, there is only a step between them
+            return Some(SharpMiter::AB(ia, ib));
+        }
+
+        let d = FloatPointMath::sub(&pb, &pa);
+        let k = FloatPointMath::cross_product(&d, &vb) / cross;
+        let m = FloatPointMath::cross_product(&va, &d) / cross;
+
+        let zero = T::from_float(0.0);
+        if k < zero || m < zero {
+            return Some(SharpMiter::AB(ia, ib));
+        }
+
+        if k > max_a || m > max_b {
+            return None;
+        }
+
+        let c = FloatPointMath::add(&pa, &FloatPointMath::scale(&va, k));
+        let ic = adapter.float_to_int(&c);
+
+        if ia == ic || ib == ic {
+            Some(SharpMiter::AB(ia, ib))
+        } else {
+            Some(SharpMiter::AcB(ia, ic, ib))
+        }
+    }
 }
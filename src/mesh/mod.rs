@@ -5,4 +5,7 @@ mod rotator;
 mod subject;
 pub mod style;
 mod boolean;
-mod miter;
\ No newline at end of file
+mod miter;
+pub mod minkowski;
+pub mod triangulation;
+pub mod convex;
\ No newline at end of file
@@ -17,6 +17,14 @@ trait OutlineBuild<P: FloatPointCompatible<T>, T: FloatNumber> {
         segments: &mut Vec<Segment<OffsetCountBoolean>>,
     );
 
+    fn build_with_offsets(
+        &self,
+        path: &[P],
+        offsets: &[T],
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<OffsetCountBoolean>>,
+    );
+
     fn capacity(&self, points_count: usize) -> usize;
     fn additional_offset(&self, radius: T) -> T;
 }
@@ -65,6 +73,19 @@ impl<P: FloatPointCompatible<T> + 'static, T: FloatNumber + 'static> OutlineBuil
         self.builder.build(path, adapter, segments);
     }
 
+    /// Builds the outline of `path` where the edge from `path[i]` to `path[i + 1]` is moved by `offsets[i]`.
+    #[inline]
+    pub(super) fn build_with_offsets(
+        &self,
+        path: &[P],
+        offsets: &[T],
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<OffsetCountBoolean>>,
+    ) {
+        debug_assert_eq!(path.len(), offsets.len());
+        self.builder.build_with_offsets(path, offsets, adapter, segments);
+    }
+
     #[inline]
     pub(super) fn capacity(
         &self,
@@ -87,6 +108,42 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> OutlineBu
         path: &[P],
         adapter: &FloatPointAdapter<P, T>,
         segments: &mut Vec<Segment<OffsetCountBoolean>>,
+    ) {
+        self.build_sections(path, |_| self.radius, adapter, segments);
+    }
+
+    #[inline]
+    fn build_with_offsets(
+        &self,
+        path: &[P],
+        offsets: &[T],
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<OffsetCountBoolean>>,
+    ) {
+        let n = path.len();
+        // the edge which ends at point `i` starts at the previous point
+        self.build_sections(path, |i| offsets[(i + n - 1) % n], adapter, segments);
+    }
+
+    #[inline]
+    fn capacity(&self, points_count: usize) -> usize {
+        self.join_builder.capacity() * points_count
+    }
+
+    #[inline]
+    fn additional_offset(&self, radius: T) -> T {
+        self.join_builder.additional_offset(radius)
+    }
+}
+
+impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> Builder<J, P, T> {
+    #[inline]
+    fn build_sections<R: Fn(usize) -> T>(
+        &self,
+        path: &[P],
+        radius: R,
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<OffsetCountBoolean>>,
     ) {
         if path.len() < 2 { return; }
 
@@ -95,14 +152,14 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> OutlineBu
         let i1 = Self::next_unique_point(i0, 0, path, adapter);
         if i1 == usize::MAX { return }
 
-        let start = Section::new(self.radius, &path[i0], &path[i1]);
+        let start = Section::new(radius(i1), &path[i0], &path[i1]);
         let mut s0 = start.clone();
         segments.add_section(&s0, adapter);
 
         let mut i = i1;
         i = Self::next_unique_point(i, i + 1, path, adapter);
         while i != usize::MAX {
-            let si = Section::new(self.radius, &s0.b, &path[i]);
+            let si = Section::new(radius(i), &s0.b, &path[i]);
             self.join_builder.add_join(&s0, &si, adapter, segments);
             segments.add_section(&si, adapter);
 
@@ -113,18 +170,6 @@ impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> OutlineBu
         self.join_builder.add_join(&s0, &start, adapter, segments);
     }
 
-    #[inline]
-    fn capacity(&self, points_count: usize) -> usize {
-        self.join_builder.capacity() * points_count
-    }
-
-    #[inline]
-    fn additional_offset(&self, radius: T) -> T {
-        self.join_builder.additional_offset(radius)
-    }
-}
-
-impl<J: JoinBuilder<P, T>, P: FloatPointCompatible<T>, T: FloatNumber> Builder<J, P, T> {
     #[inline]
     fn next_unique_point(start: usize, index: usize, path: &[P], adapter: &FloatPointAdapter<P, T>) -> usize {
         let a = adapter.float_to_int(&path[start]);
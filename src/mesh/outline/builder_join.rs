@@ -78,7 +78,7 @@ pub(super) struct MiterJoinBuilder<T> {
     limit_dot_product: T,
     expand: bool,
     max_offset: T,
-    max_length: T,
+    tan: f64,
 }
 
 impl<T: FloatNumber> MiterJoinBuilder<T> {
@@ -95,16 +95,20 @@ impl<T: FloatNumber> MiterJoinBuilder<T> {
 
         // add extra 10% to avoid problems with floating point precision.
         let max_offset = T::from_float(1.1 * (r * r + l * l).sqrt());
-        let max_length = T::from_float(l);
 
         let expand = radius >= T::from_float(0.0);
         Self {
             limit_dot_product,
             max_offset,
-            max_length,
+            tan,
             expand,
         }
     }
+
+    #[inline]
+    fn max_length(&self, radius: T) -> T {
+        T::from_float(radius.to_f64().abs() / self.tan)
+    }
 }
 
 impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for MiterJoinBuilder<T> {
@@ -137,13 +141,33 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for MiterJoin
         let dot_product = FloatPointMath::dot_product(&s0.dir, &s1.dir);
         let is_limited = self.limit_dot_product > dot_product;
 
-        if is_limited {
+        let is_uneven = s0.radius != s1.radius;
+        let sharp_miter = if is_limited {
+            None
+        } else if is_uneven {
+            Miter::sharp_uneven(pa, pb, s0.dir, s1.dir, self.max_length(s0.radius), self.max_length(s1.radius), adapter)
+        } else {
+            Some(Miter::sharp(pa, pb, s0.dir, s1.dir, adapter))
+        };
+
+        if let Some(sharp_miter) = sharp_miter {
+            match sharp_miter {
+                SharpMiter::AB(a, b) => segments.push(Segment::bold_subject_ab(a, b)),
+                SharpMiter::AcB(a, c, b) => {
+                    segments.push(Segment::bold_subject_ab(a, c));
+                    segments.push(Segment::bold_subject_ab(c, b));
+                },
+                SharpMiter::Degenerate => {}
+            }
+        } else {
             let (va, vb) = (s0.dir, s1.dir);
+            let la = self.max_length(s0.radius);
+            let lb = self.max_length(s1.radius);
 
-            let ax = pa.x() + self.max_length * va.x();
-            let ay = pa.y() + self.max_length * va.y();
-            let bx = pb.x() - self.max_length * vb.x();
-            let by = pb.y() - self.max_length * vb.y();
+            let ax = pa.x() + la * va.x();
+            let ay = pa.y() + la * va.y();
+            let bx = pb.x() - lb * vb.x();
+            let by = pb.y() - lb * vb.y();
 
             let ac = P::from_xy(ax, ay);
             let bc = P::from_xy(bx, by);
@@ -160,15 +184,6 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for MiterJoin
             if ibc != ib {
                 segments.push(Segment::bold_subject_ab(ibc, ib));
             }
-        } else {
-            match Miter::sharp(pa, pb, s0.dir, s1.dir, adapter) {
-                SharpMiter::AB(a, b) => segments.push(Segment::bold_subject_ab(a, b)),
-                SharpMiter::AcB(a, c, b) => {
-                    segments.push(Segment::bold_subject_ab(a, c));
-                    segments.push(Segment::bold_subject_ab(c, b));
-                },
-                SharpMiter::Degenerate => {}
-            }
         }
     }
 
@@ -186,7 +201,6 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for MiterJoin
 pub(super) struct RoundJoinBuilder<T> {
     inv_ratio: f64,
     average_count: usize,
-    radius: T,
     limit_dot_product: T,
     expand: bool,
     rot_dir: f64,
@@ -207,7 +221,6 @@ impl<T: FloatNumber> RoundJoinBuilder<T> {
         Self {
             inv_ratio: 1.0 / fixed_ratio,
             average_count,
-            radius,
             limit_dot_product,
             expand,
             rot_dir,
@@ -247,12 +260,17 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> JoinBuilder<P, T> for RoundJoin
 
         let rotator = Rotator::<T>::with_angle(self.rot_dir * delta_angle);
 
+        // the radius is changed smoothly along the arc if sections have different offsets
+        let r0 = s0.radius;
+        let dr = (s1.radius - s0.radius) / T::from_float(n);
+
         let center = s0.b;
         let mut v = dir;
         let mut a = adapter.float_to_int(&start);
-        for _ in 1..cnt {
+        for i in 1..cnt {
             v = rotator.rotate(&v);
-            let p = FloatPointMath::add(&center, &FloatPointMath::scale(&v, self.radius));
+            let radius = r0 + dr * T::from_float(i as f64);
+            let p = FloatPointMath::add(&center, &FloatPointMath::scale(&v, radius));
 
             let b = adapter.float_to_int(&p);
             if a != b {
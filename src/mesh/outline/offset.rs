@@ -5,15 +5,19 @@ use crate::core::overlay_rule::OverlayRule;
 use crate::float::filter::ContourFilter;
 use crate::float::source::resource::OverlayResource;
 use crate::mesh::outline::builder::OutlineBuilder;
-use crate::mesh::style::OutlineStyle;
+use crate::mesh::style::{LineJoin, OutlineStyle};
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
 use i_float::float::rect::FloatRect;
 use i_shape::base::data::Shapes;
+use i_shape::int::shape::IntShapes;
 use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::area::IntArea;
 use i_shape::float::simple::SimplifyContour;
+use crate::core::flat::FlatShapes;
+use crate::mesh::boolean::OffsetCountBoolean;
+use crate::segm::segment::Segment;
 
 pub trait OutlineOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// Generates an outline shapes for contours, or shapes.
@@ -34,6 +38,53 @@ pub trait OutlineOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// # Returns
     /// A collection of `Shapes<P>` representing the outline geometry.
     fn outline_with_filter(&self, style: OutlineStyle<T>, filter: ContourFilter<T>) -> Shapes<P>;
+
+    /// Generates an outline shapes like `outline_with_filter`, but writes them into flat buffers.
+    ///
+    /// - `style`: Defines the outline properties, including offset, and joins.
+    /// - `filter`: Defines optional contour filtering and simplification.
+    /// - `output`: Receives the outline geometry, it is cleared first and its memory is reused.
+    fn outline_into(&self, style: OutlineStyle<T>, filter: ContourFilter<T>, output: &mut FlatShapes<P>);
+
+    /// Generates an outline shapes for contours, or shapes, where every edge has its own offset.
+    ///
+    /// - `edge_offset`: Returns the offset of an edge by `(contour_index, edge_index)`:
+    ///     - `contour_index`: The index of the contour in the order it is stored in the source.
+    ///     - `edge_index`: The edge `i` goes from point `i` to point `i + 1`, the last edge closes the contour.
+    ///
+    ///   A positive offset expands the shape and a negative one shrinks it, both for outer contours and holes.
+    ///   All edges of a single contour must have offsets of the same sign. If any contour has both positive
+    ///   and negative offsets, the whole source is rejected and the result is empty: dropping only that
+    ///   contour would fill a dropped hole or leave the holes of a dropped outer contour without a shape.
+    /// - `join`: Defines the join style where two edges meet.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the outline geometry.
+    fn outline_by_edges<F: Fn(usize, usize) -> T>(&self, edge_offset: F, join: LineJoin<T>) -> Shapes<P>;
+
+    /// Generates an outline shapes for contours, or shapes, where every edge has its own offset, with optional filtering.
+    ///
+    /// - `edge_offset`: Returns the offset of an edge by `(contour_index, edge_index)`:
+    ///     - `contour_index`: The index of the contour in the order it is stored in the source.
+    ///     - `edge_index`: The edge `i` goes from point `i` to point `i + 1`, the last edge closes the contour.
+    ///
+    ///   A positive offset expands the shape and a negative one shrinks it, both for outer contours and holes.
+    ///   All edges of a single contour must have offsets of the same sign. If any contour has both positive
+    ///   and negative offsets, the whole source is rejected and the result is empty: dropping only that
+    ///   contour would fill a dropped hole or leave the holes of a dropped outer contour without a shape.
+    /// - `join`: Defines the join style where two edges meet.
+    /// - `filter`: Defines optional contour filtering and simplification:
+    ///     - `min_area`: Retains only contours with an area larger than this value.
+    ///     - `simplify`: If `true`, simplifies contours and removes degenerate edges.
+    ///
+    /// # Returns
+    /// A collection of `Shapes<P>` representing the outline geometry.
+    fn outline_by_edges_with_filter<F: Fn(usize, usize) -> T>(
+        &self,
+        edge_offset: F,
+        join: LineJoin<T>,
+        filter: ContourFilter<T>,
+    ) -> Shapes<P>;
 }
 
 impl<S, P, T> OutlineOffset<P, T> for S
@@ -53,122 +104,318 @@ where
     }
 
     fn outline_with_filter(&self, style: OutlineStyle<T>, filter: ContourFilter<T>) -> Shapes<P> {
-        let (points_count, paths_count) = {
-            let mut points_count = 0;
-            let mut paths_count = 0;
-            for path in self.iter_paths() {
-                points_count += path.len();
-                paths_count += 1;
-            }
-            (points_count, paths_count)
+        let (shapes, adapter) = int_outline(self, &StyleOffsets::new(style), filter);
+
+        if filter.simplify {
+            let mut float = shapes.to_float(&adapter);
+            float.simplify_contour(&adapter);
+            float
+        } else {
+            shapes.to_float(&adapter)
+        }
+    }
+
+    fn outline_into(&self, style: OutlineStyle<T>, filter: ContourFilter<T>, output: &mut FlatShapes<P>) {
+        let (shapes, adapter) = int_outline(self, &StyleOffsets::new(style), filter);
+        output.set_int_shapes(&shapes, &adapter);
+
+        if filter.simplify {
+            output.simplify_contour(&adapter);
+        }
+    }
+
+    fn outline_by_edges<F: Fn(usize, usize) -> T>(&self, edge_offset: F, join: LineJoin<T>) -> Shapes<P> {
+        self.outline_by_edges_with_filter(
+            edge_offset,
+            join,
+            ContourFilter {
+                min_area: T::from_float(0.0),
+                simplify: false,
+            },
+        )
+    }
+
+    fn outline_by_edges_with_filter<F: Fn(usize, usize) -> T>(
+        &self,
+        edge_offset: F,
+        join: LineJoin<T>,
+        filter: ContourFilter<T>,
+    ) -> Shapes<P> {
+        let Some(offsets) = EdgeOffsets::new(self, edge_offset, join.normalize()) else {
+            // a contour has offsets of both signs
+            return vec![];
         };
+        let (shapes, adapter) = int_outline(self, &offsets, filter);
+
+        if filter.simplify {
+            let mut float = shapes.to_float(&adapter);
+            float.simplify_contour(&adapter);
+            float
+        } else {
+            shapes.to_float(&adapter)
+        }
+    }
+}
+
+/// Builds the outline segments of a single contour.
+trait ContourOffsets<P: FloatPointCompatible<T>, T: FloatNumber> {
+    /// The distance the outline can go beyond the source contours.
+    fn additional_offset(&self) -> T;
+
+    fn capacity(&self, points_count: usize) -> usize;
+
+    /// Appends the outline of the contour `index`, a hole comes inverted, so `path` is always positive ordered.
+    fn build(
+        &self,
+        index: usize,
+        path: &[P],
+        is_hole: bool,
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<OffsetCountBoolean>>,
+    );
+}
+
+struct StyleOffsets<P: FloatPointCompatible<T>, T: FloatNumber> {
+    outer_builder: OutlineBuilder<P, T>,
+    inner_builder: OutlineBuilder<P, T>,
+    additional_offset: T,
+}
 
+impl<P: FloatPointCompatible<T> + 'static, T: FloatNumber + 'static> StyleOffsets<P, T> {
+    fn new(style: OutlineStyle<T>) -> Self {
         let join = style.join.normalize();
 
         let outer_builder = OutlineBuilder::new(style.outer_offset, &join);
         let inner_builder = OutlineBuilder::new(-style.inner_offset, &join);
 
-        let adapter = {
-            let outer_radius = style.outer_offset;
-            let inner_radius = style.inner_offset;
+        let outer_additional_offset = outer_builder.additional_offset(style.outer_offset);
+        let inner_additional_offset = inner_builder.additional_offset(style.inner_offset);
+        let additional_offset = outer_additional_offset.abs() + inner_additional_offset.abs();
+
+        Self { outer_builder, inner_builder, additional_offset }
+    }
+}
+
+impl<P: FloatPointCompatible<T> + 'static, T: FloatNumber + 'static> ContourOffsets<P, T> for StyleOffsets<P, T> {
+    #[inline]
+    fn additional_offset(&self) -> T {
+        self.additional_offset
+    }
+
+    #[inline]
+    fn capacity(&self, points_count: usize) -> usize {
+        self.outer_builder.capacity(points_count)
+    }
+
+    #[inline]
+    fn build(
+        &self,
+        _index: usize,
+        path: &[P],
+        is_hole: bool,
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<OffsetCountBoolean>>,
+    ) {
+        if is_hole {
+            self.inner_builder.build(path, adapter, segments);
+        } else {
+            self.outer_builder.build(path, adapter, segments);
+        }
+    }
+}
+
+struct EdgeOffsets<P: FloatPointCompatible<T>, T: FloatNumber> {
+    /// The edge offsets of every contour.
+    offsets: Vec<Vec<T>>,
+    join: LineJoin<T>,
+    builder: OutlineBuilder<P, T>,
+    max_offset: T,
+}
 
-            let outer_additional_offset = outer_builder.additional_offset(outer_radius);
-            let inner_additional_offset = inner_builder.additional_offset(inner_radius);
+impl<P: FloatPointCompatible<T> + 'static, T: FloatNumber + 'static> EdgeOffsets<P, T> {
+    /// Returns `None` if any contour has offsets of both signs.
+    fn new<S, F>(source: &S, edge_offset: F, join: LineJoin<T>) -> Option<Self>
+    where
+        S: OverlayResource<P, T>,
+        F: Fn(usize, usize) -> T,
+    {
+        let zero = T::from_float(0.0);
+        let mut max_offset = zero;
+        let mut offsets = Vec::new();
+        for (contour_index, path) in source.iter_paths().enumerate() {
+            let path_offsets: Vec<T> = (0..path.len())
+                .map(|edge_index| edge_offset(contour_index, edge_index))
+                .collect();
+
+            let is_positive = path_offsets.iter().any(|&offset| offset > zero);
+            let is_negative = path_offsets.iter().any(|&offset| offset < zero);
+            if is_positive && is_negative {
+                return None;
+            }
 
-            let additional_offset = outer_additional_offset.abs() + inner_additional_offset.abs();
+            for &offset in path_offsets.iter() {
+                max_offset = max_offset.max(offset.abs());
+            }
+            offsets.push(path_offsets);
+        }
+
+        let builder = OutlineBuilder::new(max_offset, &join);
+
+        Some(Self { offsets, join, builder, max_offset })
+    }
+}
+
+impl<P: FloatPointCompatible<T> + 'static, T: FloatNumber + 'static> ContourOffsets<P, T> for EdgeOffsets<P, T> {
+    #[inline]
+    fn additional_offset(&self) -> T {
+        self.builder.additional_offset(self.max_offset).abs()
+    }
 
-            let mut rect =
-                FloatRect::with_iter(self.iter_paths().flatten()).unwrap_or(FloatRect::zero());
-            rect.add_offset(additional_offset);
+    #[inline]
+    fn capacity(&self, points_count: usize) -> usize {
+        self.builder.capacity(points_count)
+    }
 
-            FloatPointAdapter::new(rect)
+    fn build(
+        &self,
+        index: usize,
+        path: &[P],
+        is_hole: bool,
+        adapter: &FloatPointAdapter<P, T>,
+        segments: &mut Vec<Segment<OffsetCountBoolean>>,
+    ) {
+        let offsets = &self.offsets[index];
+
+        let offsets = if is_hole {
+            // the edge `i` of the inverted contour is the edge `n - 2 - i` of the source, and it goes to the other side
+            let n = offsets.len();
+            (0..n).map(|i| -offsets[(2 * n - 2 - i) % n]).collect()
+        } else {
+            offsets.clone()
         };
 
-        let int_min_area = adapter.sqr_float_to_int(filter.min_area).max(1);
+        // the largest offset defines the side of the outline: expand or shrink
+        let radius = offsets.iter().fold(T::from_float(0.0), |r, &offset| {
+            if offset.abs() > r.abs() { offset } else { r }
+        });
 
-        let shapes = if paths_count <= 1 {
-            // fast solution for a single path
+        OutlineBuilder::new(radius, &self.join).build_with_offsets(path, &offsets, adapter, segments);
+    }
+}
 
-            let path = if let Some(first) = self.iter_paths().next() {
-                first
-            } else {
-                return vec![];
-            };
+fn int_outline<S, P, T, O>(source: &S, offsets: &O, filter: ContourFilter<T>) -> (IntShapes, FloatPointAdapter<P, T>)
+where
+    S: OverlayResource<P, T>,
+    P: FloatPointCompatible<T> + 'static,
+    T: FloatNumber + 'static,
+    O: ContourOffsets<P, T>,
+{
+    let (points_count, paths_count) = {
+        let mut points_count = 0;
+        let mut paths_count = 0;
+        for path in source.iter_paths() {
+            points_count += path.len();
+            paths_count += 1;
+        }
+        (points_count, paths_count)
+    };
 
-            let area = path.unsafe_int_area(&adapter);
-            if area <= 1 {
-                // single path must be positive ordered
-                return vec![];
-            }
+    let adapter = {
+        let mut rect =
+            FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
+        rect.add_offset(offsets.additional_offset());
 
-            let capacity = outer_builder.capacity(path.len());
-            let mut segments = Vec::with_capacity(capacity);
-            outer_builder.build(path, &adapter, &mut segments);
+        FloatPointAdapter::new(rect)
+    };
 
-            OverlayGraph::offset_graph_with_solver(segments, Default::default())
-                .extract_offset_min_area(int_min_area)
+    let int_min_area = adapter.sqr_float_to_int(filter.min_area).max(1);
+
+    let shapes = if paths_count <= 1 {
+        // fast solution for a single path
+
+        let path = if let Some(first) = source.iter_paths().next() {
+            first
         } else {
-            let total_capacity = outer_builder.capacity(points_count);
+            return (vec![], adapter);
+        };
+
+        let area = path.unsafe_int_area(&adapter);
+        if area <= 1 {
+            // single path must be positive ordered
+            return (vec![], adapter);
+        }
+
+        let capacity = offsets.capacity(path.len());
+        let mut segments = Vec::with_capacity(capacity);
+        offsets.build(0, path, false, &adapter, &mut segments);
+        if segments.is_empty() {
+            return (vec![], adapter);
+        }
 
-            let mut overlay = Overlay::new(total_capacity);
+        OverlayGraph::offset_graph_with_solver(segments, Default::default())
+            .extract_offset_min_area(int_min_area)
+    } else {
+        let total_capacity = offsets.capacity(points_count);
 
-            for path in self.iter_paths() {
-                let area = path.unsafe_int_area(&adapter);
-                if area.abs() <= 1 {
-                    // ignore degenerate paths
+        let mut overlay = Overlay::new(total_capacity);
+
+        for (index, path) in source.iter_paths().enumerate() {
+            let area = path.unsafe_int_area(&adapter);
+            if area.abs() <= 1 {
+                // ignore degenerate paths
+                continue;
+            }
+
+            if area > 0 {
+                let capacity = offsets.capacity(path.len());
+                let mut segments = Vec::with_capacity(capacity);
+                offsets.build(index, path, false, &adapter, &mut segments);
+                if segments.is_empty() {
                     continue;
                 }
+                let shapes = OverlayGraph::offset_graph_with_solver(segments, Default::default())
+                    .extract_offset_min_area(0);
+                overlay.add_shapes(&shapes, ShapeType::Subject);
+            } else {
+                let mut inverted = Vec::with_capacity(path.len());
+                for p in path.iter().rev() {
+                    inverted.push(*p);
+                }
 
-                if area > 0 {
-                    let capacity = outer_builder.capacity(path.len());
-                    let mut segments = Vec::with_capacity(capacity);
-                    outer_builder.build(path, &adapter, &mut segments);
-                    let shapes = OverlayGraph::offset_graph_with_solver(segments, Default::default())
+                let capacity = offsets.capacity(inverted.len());
+                let mut segments = Vec::with_capacity(capacity);
+                offsets.build(index, &inverted, true, &adapter, &mut segments);
+                if segments.is_empty() {
+                    continue;
+                }
+                let mut shapes =
+                    OverlayGraph::offset_graph_with_solver(segments, Default::default())
                         .extract_offset_min_area(0);
-                    overlay.add_shapes(&shapes, ShapeType::Subject);
-                } else {
-                    let mut inverted = Vec::with_capacity(path.len());
-                    for p in path.iter().rev() {
-                        inverted.push(*p);
-                    }
 
-                    let capacity = inner_builder.capacity(inverted.len());
-                    let mut segments = Vec::with_capacity(capacity);
-                    inner_builder.build(&inverted, &adapter, &mut segments);
-                    let mut shapes =
-                        OverlayGraph::offset_graph_with_solver(segments, Default::default())
-                            .extract_offset_min_area(0);
-
-                    for shape in shapes.iter_mut() {
-                        for path in shape.iter_mut() {
-                            path.reverse();
-                        }
+                for shape in shapes.iter_mut() {
+                    for path in shape.iter_mut() {
+                        path.reverse();
                     }
-
-                    overlay.add_shapes(&shapes, ShapeType::Subject);
                 }
+
+                overlay.add_shapes(&shapes, ShapeType::Subject);
             }
+        }
 
-            overlay.overlay_with_min_area_and_solver(
-                OverlayRule::Subject,
-                FillRule::Positive,
-                int_min_area,
-                Default::default(),
-            )
-        };
+        overlay.overlay_with_min_area_and_solver(
+            OverlayRule::Subject,
+            FillRule::Positive,
+            int_min_area,
+            Default::default(),
+        )
+    };
 
-        if filter.simplify {
-            let mut float = shapes.to_float(&adapter);
-            float.simplify_contour(&adapter);
-            float
-        } else {
-            shapes.to_float(&adapter)
-        }
-    }
+    (shapes, adapter)
 }
-
 #[cfg(test)]
 mod tests {
+    use crate::core::flat::FlatShapes;
+    use crate::float::filter::ContourFilter;
     use crate::mesh::outline::offset::OutlineOffset;
     use crate::mesh::style::{LineJoin, OutlineStyle};
     use std::f32::consts::PI;
@@ -278,4 +525,189 @@ mod tests {
         assert_eq!(shapes.len(), 1);
         assert_eq!(shapes.first().unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_square_same_edge_offsets() {
+        let path = [
+            [-5.0, -5.0f32],
+            [-5.0, 5.0f32],
+            [5.0, 5.0f32],
+            [5.0, -5.0f32],
+        ];
+
+        let shapes = path.outline_by_edges(|_, _| 10.0, LineJoin::Bevel);
+
+        assert_eq!(shapes.len(), 1);
+
+        let shape = shapes.first().unwrap();
+        assert_eq!(shape.len(), 1);
+
+        let path = shape.first().unwrap();
+        assert_eq!(path.len(), 8);
+    }
+
+    #[test]
+    fn test_square_miter_edge_offsets() {
+        let path = [
+            [-5.0, -5.0f64],
+            [-5.0, 5.0f64],
+            [5.0, 5.0f64],
+            [5.0, -5.0f64],
+        ];
+
+        // left, top, right, bottom
+        let offsets = [1.0, 2.0, 3.0, 4.0];
+        let shapes = path.outline_by_edges(|_, i| offsets[i], LineJoin::Miter(0.1));
+
+        assert_eq!(shapes.len(), 1);
+
+        let shape = shapes.first().unwrap();
+        assert_eq!(shape.len(), 1);
+
+        let path = shape.first().unwrap();
+        assert_eq!(path.len(), 4);
+
+        let min_x = path.iter().fold(f64::MAX, |m, p| m.min(p[0]));
+        let max_x = path.iter().fold(f64::MIN, |m, p| m.max(p[0]));
+        let min_y = path.iter().fold(f64::MAX, |m, p| m.min(p[1]));
+        let max_y = path.iter().fold(f64::MIN, |m, p| m.max(p[1]));
+
+        assert!((min_x + 6.0).abs() < 0.001);
+        assert!((max_y - 7.0).abs() < 0.001);
+        assert!((max_x - 8.0).abs() < 0.001);
+        assert!((min_y + 9.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_shape_with_hole_edge_offsets() {
+        let shape = vec![
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]],
+            vec![[3.0, 3.0], [7.0, 3.0], [7.0, 7.0], [3.0, 7.0]],
+        ];
+
+        // outer walls are thicker than inner ones
+        let shapes = shape.outline_by_edges(|contour, _| if contour == 0 { 1.0 } else { 0.5 }, LineJoin::Miter(0.1));
+
+        assert_eq!(shapes.len(), 1);
+
+        let shape = shapes.first().unwrap();
+        assert_eq!(shape.len(), 2);
+
+        let hole = &shape[1];
+        let min_x = hole.iter().fold(f64::MAX, |m, p| m.min(p[0]));
+        assert!((min_x - 3.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_hole_edge_order() {
+        let shape = vec![
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]],
+            vec![[3.0, 3.0], [7.0, 3.0], [7.0, 7.0], [3.0, 7.0]],
+        ];
+
+        // hole edges: bottom, right, top, left
+        let hole_offsets = [0.5, 1.0, 1.5, 2.0];
+        let shapes = shape.outline_by_edges(
+            |contour, i| if contour == 0 { 1.0 } else { hole_offsets[i] },
+            LineJoin::Miter(0.1),
+        );
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].len(), 2);
+
+        let hole = &shapes[0][1];
+        let min_x = hole.iter().fold(f64::MAX, |m, p| m.min(p[0]));
+        let max_x = hole.iter().fold(f64::MIN, |m, p| m.max(p[0]));
+        let min_y = hole.iter().fold(f64::MAX, |m, p| m.min(p[1]));
+        let max_y = hole.iter().fold(f64::MIN, |m, p| m.max(p[1]));
+
+        assert!((min_y - 3.5).abs() < 0.001);
+        assert!((max_x - 6.0).abs() < 0.001);
+        assert!((max_y - 5.5).abs() < 0.001);
+        assert!((min_x - 5.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_triangle_round_edge_offsets() {
+        let path = [[0.0, 0.0f32], [0.0, 10.0f32], [10.0, 0.0f32]];
+
+        let shapes = path.outline_by_edges(|_, i| 1.0 + i as f32, LineJoin::Round(0.1));
+
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes.first().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_mixed_sign_edge_offsets() {
+        let square = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+
+        let shapes = square.outline_by_edges(|_, i| if i == 0 { 1.0 } else { -1.0 }, LineJoin::Bevel);
+        assert!(shapes.is_empty());
+
+        // a mixed hole rejects the whole source, otherwise the hole would be filled
+        let shape = vec![
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]],
+            vec![[3.0, 3.0], [7.0, 3.0], [7.0, 7.0], [3.0, 7.0]],
+        ];
+
+        let shapes = shape.outline_by_edges(
+            |contour, i| if contour == 1 && i == 0 { -1.0 } else { 1.0 },
+            LineJoin::Miter(0.1),
+        );
+        assert!(shapes.is_empty());
+
+        // a mixed contour rejects the other contours as well
+        let contours = vec![
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]],
+            vec![[20.0, 0.0], [20.0, 10.0], [30.0, 10.0], [30.0, 0.0]],
+        ];
+
+        let shapes = contours.outline_by_edges(
+            |contour, i| if contour == 0 && i == 0 { -1.0 } else { 1.0 },
+            LineJoin::Miter(0.1),
+        );
+        assert!(shapes.is_empty());
+
+        // zero offsets go with either sign
+        let shapes = contours.outline_by_edges(
+            |contour, i| if contour == 0 && i == 0 { 0.0 } else { 1.0 },
+            LineJoin::Miter(0.1),
+        );
+        assert_eq!(shapes.len(), 2);
+    }
+
+    #[test]
+    fn test_outline_into() {
+        let shape = vec![
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]],
+            vec![[2.0, 2.0], [8.0, 2.0], [8.0, 8.0], [2.0, 8.0]],
+        ];
+
+        let shapes = shape.outline_with_filter(OutlineStyle::new(1.0), ContourFilter::default());
+
+        let mut flat = FlatShapes::new();
+        flat.push_shape(&[[[0.0, 0.0]]]);
+        shape.outline_into(OutlineStyle::new(1.0), ContourFilter::default(), &mut flat);
+
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat.to_shapes(), shapes);
+    }
+
+    #[test]
+    fn test_outline_into_f32_far_from_origin() {
+        let path = [[1_000_000.0f32, 1_000_000.0], [1_000_000.0, 1_000_004.0], [1_000_004.0, 1_000_004.0], [1_000_004.0, 1_000_000.0]];
+        let filter = ContourFilter { min_area: 0.0, simplify: true };
+
+        let style = OutlineStyle::new(1.0f32).line_join(LineJoin::Round(0.05 * PI));
+        let shapes = path.outline_with_filter(style, filter);
+        let style = OutlineStyle::new(1.0f32).line_join(LineJoin::Round(0.05 * PI));
+
+        let mut flat = FlatShapes::new();
+        path.outline_into(style, filter, &mut flat);
+
+        assert_eq!(flat.to_shapes(), shapes);
+        for contour in flat.contour_ranges.iter().map(|range| &flat.points[range.clone()]) {
+            assert!(contour.windows(2).all(|w| w[0] != w[1]));
+        }
+    }
 }
@@ -1,4 +1,3 @@
-use std::marker::PhantomData;
 use i_float::adapter::FloatPointAdapter;
 use i_float::float::compatible::FloatPointCompatible;
 use i_float::float::number::FloatNumber;
@@ -13,7 +12,7 @@ pub(super) struct Section<P: FloatPointCompatible<T>, T: FloatNumber> {
     pub(super) a_top: P,
     pub(super) b_top: P,
     pub(super) dir: P,
-    _phantom: PhantomData<T>,
+    pub(super) radius: T,
 }
 
 impl<T: FloatNumber, P: FloatPointCompatible<T>> Section<P, T> {
@@ -29,7 +28,7 @@ impl<T: FloatNumber, P: FloatPointCompatible<T>> Section<P, T> {
             a_top,
             b_top,
             dir,
-            _phantom: Default::default(),
+            radius,
         }
     }
 }
@@ -9,7 +9,11 @@ use i_float::float::rect::FloatRect;
 use i_shape::base::data::Shapes;
 use i_shape::float::adapter::ShapesToFloat;
 use i_shape::float::simple::SimplifyContour;
+use i_shape::int::shape::IntShapes;
+use crate::core::flat::FlatShapes;
 use crate::core::graph::OverlayGraph;
+use crate::mesh::boolean::OffsetCountBoolean;
+use crate::segm::segment::Segment;
 
 pub trait StrokeOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// Generates a stroke shapes for paths, contours, or shapes.
@@ -37,6 +41,20 @@ pub trait StrokeOffset<P: FloatPointCompatible<T>, T: FloatNumber> {
         is_closed_path: bool,
         filter: ContourFilter<T>,
     ) -> Shapes<P>;
+
+    /// Generates a stroke mesh like `stroke_with_filter`, but writes it into flat buffers.
+    ///
+    /// - `style`: Defines the stroke properties, including width, line caps, and joins.
+    /// - `is_closed_path`: Specifies whether the path is closed (true) or open (false).
+    /// - `filter`: Defines optional contour filtering and simplification.
+    /// - `output`: Receives the stroke geometry, it is cleared first and its memory is reused.
+    fn stroke_into(
+        &self,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        filter: ContourFilter<T>,
+        output: &mut FlatShapes<P>,
+    );
 }
 
 impl<S, P, T> StrokeOffset<P, T> for S
@@ -55,50 +73,104 @@ where
         is_closed_path: bool,
         filter: ContourFilter<T>,
     ) -> Shapes<P> {
-        let mut paths_count = 0;
-        let mut points_count = 0;
-        for path in self.iter_paths() {
-            paths_count += 1;
-            points_count += path.len();
-        }
+        let (shapes, adapter) = int_stroke(self, style, is_closed_path);
 
-        let r = T::from_float(0.5 * style.width.to_f64());
-        let builder = StrokeBuilder::new(style);
-        let a = builder.additional_offset(r);
+        let mut float = shapes.to_float(&adapter);
 
-        let mut rect = FloatRect::with_iter(self.iter_paths().flatten()).unwrap_or(FloatRect::zero());
-        rect.add_offset(a);
-        let adapter = FloatPointAdapter::new(rect);
+        if filter.simplify {
+            float.simplify_contour(&adapter);
+        };
 
-        let ir= adapter.len_float_to_int(r).abs();
-        if ir <= 1 {
-            // offset is too small
-            return vec![];
-        }
+        float
+    }
 
-        let capacity = builder.capacity(paths_count, points_count, is_closed_path);
-        let mut segments = Vec::with_capacity(capacity);
+    fn stroke_into(
+        &self,
+        style: StrokeStyle<P, T>,
+        is_closed_path: bool,
+        filter: ContourFilter<T>,
+        output: &mut FlatShapes<P>,
+    ) {
+        let (shapes, adapter) = int_stroke(self, style, is_closed_path);
+        output.set_int_shapes(&shapes, &adapter);
 
-        for path in self.iter_paths() {
-            builder.build(path, is_closed_path, &adapter, &mut segments);
+        if filter.simplify {
+            output.simplify_contour(&adapter);
         }
+    }
+}
 
-        let shapes = OverlayGraph::offset_graph_with_solver(segments, Default::default())
-            .extract_offset_min_area(0);
+#[inline]
+fn int_stroke<S, P, T>(source: &S, style: StrokeStyle<P, T>, is_closed_path: bool) -> (IntShapes, FloatPointAdapter<P, T>)
+where
+    S: OverlayResource<P, T>,
+    P: FloatPointCompatible<T> + 'static,
+    T: FloatNumber + 'static,
+{
+    let (segments, adapter) = int_stroke_segments(source, style, is_closed_path, std::iter::empty());
+    if segments.is_empty() {
+        return (vec![], adapter);
+    }
 
-        let mut float = shapes.to_float(&adapter);
+    let shapes = OverlayGraph::offset_graph_with_solver(segments, Default::default())
+        .extract_offset_min_area(0);
 
-        if filter.simplify {
-            float.simplify_contour(&adapter);
-        };
+    (shapes, adapter)
+}
 
-        float
+/// Builds the raw stroke segments of `source`, the adapter also covers `points`,
+/// so other geometry can be converted to the same int space and go to the same overlay.
+/// The stroke area is the area with a negative subject count, it is empty if the stroke is too thin.
+pub(crate) fn int_stroke_segments<'a, S, P, T, I>(
+    source: &S,
+    style: StrokeStyle<P, T>,
+    is_closed_path: bool,
+    points: I,
+) -> (Vec<Segment<OffsetCountBoolean>>, FloatPointAdapter<P, T>)
+where
+    S: OverlayResource<P, T>,
+    P: FloatPointCompatible<T> + 'static,
+    T: FloatNumber + 'static,
+    I: Iterator<Item = &'a P>,
+{
+    let mut paths_count = 0;
+    let mut points_count = 0;
+    for path in source.iter_paths() {
+        paths_count += 1;
+        points_count += path.len();
+    }
+
+    let r = T::from_float(0.5 * style.width.to_f64());
+    let builder = StrokeBuilder::new(style);
+    let a = builder.additional_offset(r);
+
+    let mut rect = FloatRect::with_iter(source.iter_paths().flatten()).unwrap_or(FloatRect::zero());
+    rect.add_offset(a);
+    for p in points {
+        rect.add_point(p);
+    }
+    let adapter = FloatPointAdapter::new(rect);
+
+    let ir= adapter.len_float_to_int(r).abs();
+    if ir <= 1 {
+        // offset is too small
+        return (vec![], adapter);
+    }
+
+    let capacity = builder.capacity(paths_count, points_count, is_closed_path);
+    let mut segments = Vec::with_capacity(capacity);
+
+    for path in source.iter_paths() {
+        builder.build(path, is_closed_path, &adapter, &mut segments);
     }
+
+    (segments, adapter)
 }
 
 #[cfg(test)]
 mod tests {
     use std::f32::consts::PI;
+    use crate::core::flat::FlatShapes;
     use crate::mesh::stroke::offset::StrokeOffset;
     use crate::mesh::style::{LineCap, LineJoin, StrokeStyle};
     use crate::float::filter::ContourFilter;
@@ -253,4 +325,41 @@ mod tests {
         let shape = shapes.first().unwrap();
         assert_eq!(shape.len(), 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_stroke_into() {
+        let paths = [
+            vec![[0.0, 0.0], [10.0, 0.0]],
+            vec![[0.0, 5.0], [10.0, 5.0], [10.0, 10.0]],
+        ];
+
+        let style = StrokeStyle::new(2.0).line_join(LineJoin::Round(0.25 * PI));
+        let shapes = paths.stroke_with_filter(style, false, ContourFilter::default());
+
+        let style = StrokeStyle::new(2.0).line_join(LineJoin::Round(0.25 * PI));
+        let mut flat = FlatShapes::new();
+        paths.stroke_into(style, false, ContourFilter::default(), &mut flat);
+
+        assert_eq!(flat.len(), 2);
+        assert_eq!(flat.to_shapes(), shapes);
+    }
+
+    #[test]
+    fn test_stroke_into_f32_far_from_origin() {
+        // f32 can not hold every int point here, so some of them collapse after the conversion
+        let path = [[1_000_000.0f32, 1_000_000.0], [1_000_003.0, 1_000_000.5], [1_000_004.0, 1_000_003.0]];
+        let filter = ContourFilter { min_area: 0.0, simplify: true };
+
+        let style = StrokeStyle::new(1.0f32).line_join(LineJoin::Round(0.05 * PI)).start_cap(LineCap::Round(0.05 * PI));
+        let shapes = path.stroke_with_filter(style, false, filter);
+
+        let style = StrokeStyle::new(1.0f32).line_join(LineJoin::Round(0.05 * PI)).start_cap(LineCap::Round(0.05 * PI));
+        let mut flat = FlatShapes::new();
+        path.stroke_into(style, false, filter, &mut flat);
+
+        assert_eq!(flat.to_shapes(), shapes);
+        for contour in flat.contour_ranges.iter().map(|range| &flat.points[range.clone()]) {
+            assert!(contour.windows(2).all(|w| w[0] != w[1]));
+        }
+    }
+}
@@ -4,6 +4,7 @@ use i_float::float::number::FloatNumber;
 
 /// The endpoint style of a line.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineCap<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// A line with a squared-off end. This is the default.
     Butt,
@@ -18,6 +19,7 @@ pub enum LineCap<P: FloatPointCompatible<T>, T: FloatNumber> {
 
 /// The join style of a line.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineJoin<T: FloatNumber> {
     /// Cuts off the corner where two lines meet. This is the default.
     Bevel,
@@ -33,6 +35,7 @@ pub enum LineJoin<T: FloatNumber> {
 
 /// Defines the stroke style for outlining paths.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StrokeStyle<P: FloatPointCompatible<T>, T: FloatNumber> {
     /// The width of the stroke.
     pub width: T,
@@ -46,6 +49,7 @@ pub struct StrokeStyle<P: FloatPointCompatible<T>, T: FloatNumber> {
 
 /// Defines the outline style for offsetting shapes.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OutlineStyle<T: FloatNumber> {
     pub outer_offset: T,
     pub inner_offset: T,
@@ -2,6 +2,7 @@ use crate::geom::x_segment::XSegment;
 use crate::segm::segment::Segment;
 use i_float::int::point::IntPoint;
 use crate::mesh::boolean::OffsetCountBoolean;
+use crate::segm::winding_count::{ShapeCountBoolean, ShapeCountString};
 
 impl Segment<OffsetCountBoolean> {
 
@@ -34,4 +35,22 @@ impl Segment<OffsetCountBoolean> {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Converts a stroke segment to a shape segment of a string overlay, the stroke area keeps a negative count.
+    #[inline]
+    pub(crate) fn into_string_shape(self) -> Segment<ShapeCountString> {
+        Segment {
+            x_segment: self.x_segment,
+            count: ShapeCountString { subj: self.count.subj, clip: 0 },
+        }
+    }
+
+    /// Converts a stroke segment to a clip segment of a boolean overlay, the stroke area keeps a negative count.
+    #[inline]
+    pub(crate) fn into_clip(self) -> Segment<ShapeCountBoolean> {
+        Segment {
+            x_segment: self.x_segment,
+            count: ShapeCountBoolean { subj: 0, clip: self.count.subj },
+        }
+    }
+}
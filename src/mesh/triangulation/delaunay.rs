@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use i_float::int::point::IntPoint;
+
+/// Flips inner edges of counterclockwise triangles until every edge satisfies the Delaunay condition.
+///
+/// Contour edges belong to a single triangle, so they are never flipped and the triangulation stays constrained.
+pub(crate) fn delaunay_refine(points: &[IntPoint], triangles: &mut [[usize; 3]]) {
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::with_capacity(3 * triangles.len());
+    for (i, triangle) in triangles.iter().enumerate() {
+        edges.insert((triangle[0], triangle[1]), i);
+        edges.insert((triangle[1], triangle[2]), i);
+        edges.insert((triangle[2], triangle[0]), i);
+    }
+
+    let mut stack: Vec<(usize, usize)> = edges
+        .keys()
+        .filter(|&&(a, b)| a < b && edges.contains_key(&(b, a)))
+        .copied()
+        .collect();
+
+    while let Some((a, b)) = stack.pop() {
+        let (t0, t1) = match (edges.get(&(a, b)), edges.get(&(b, a))) {
+            (Some(&t0), Some(&t1)) => (t0, t1),
+            _ => continue,
+        };
+
+        let c = opposite(&triangles[t0], a, b);
+        let d = opposite(&triangles[t1], b, a);
+        if c == d {
+            continue;
+        }
+
+        let pa = points[a];
+        let pb = points[b];
+        let pc = points[c];
+        let pd = points[d];
+
+        // the new triangles must be valid, so the quad a, d, b, c must be strictly convex
+        if !is_legal(pa, pb, pc, pd) && cross(pa, pd, pc) > 0 && cross(pd, pb, pc) > 0 {
+            triangles[t0] = [a, d, c];
+            triangles[t1] = [d, b, c];
+
+            edges.remove(&(a, b));
+            edges.remove(&(b, a));
+            edges.insert((a, d), t0);
+            edges.insert((d, c), t0);
+            edges.insert((c, a), t0);
+            edges.insert((d, b), t1);
+            edges.insert((b, c), t1);
+            edges.insert((c, d), t1);
+
+            stack.extend_from_slice(&[(a, d), (d, b), (b, c), (c, a)]);
+        }
+    }
+}
+
+#[inline]
+fn opposite(triangle: &[usize; 3], a: usize, b: usize) -> usize {
+    if triangle[0] == a && triangle[1] == b {
+        triangle[2]
+    } else if triangle[1] == a && triangle[2] == b {
+        triangle[0]
+    } else {
+        triangle[1]
+    }
+}
+
+/// Checks the edge `ab` shared by the triangles `abc` and `bad`,
+/// the edge is legal if the sum of the opposite angles at `c` and `d` is not greater than π.
+#[inline]
+fn is_legal(a: IntPoint, b: IntPoint, c: IntPoint, d: IntPoint) -> bool {
+    let sin_c = cross(c, a, b) as i128;
+    let sin_d = cross(d, b, a) as i128;
+    if sin_c <= 0 || sin_d <= 0 {
+        return true;
+    }
+
+    let cos_c = dot(c, a, b) as i128;
+    let cos_d = dot(d, b, a) as i128;
+
+    // sin(c + d) >= 0
+    sin_c * cos_d + cos_c * sin_d >= 0
+}
+
+#[inline(always)]
+fn cross(o: IntPoint, a: IntPoint, b: IntPoint) -> i64 {
+    let ax = a.x as i64 - o.x as i64;
+    let ay = a.y as i64 - o.y as i64;
+    let bx = b.x as i64 - o.x as i64;
+    let by = b.y as i64 - o.y as i64;
+    ax * by - ay * bx
+}
+
+#[inline(always)]
+fn dot(o: IntPoint, a: IntPoint, b: IntPoint) -> i64 {
+    let ax = a.x as i64 - o.x as i64;
+    let ay = a.y as i64 - o.y as i64;
+    let bx = b.x as i64 - o.x as i64;
+    let by = b.y as i64 - o.y as i64;
+    ax * bx + ay * by
+}
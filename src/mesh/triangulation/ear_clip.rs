@@ -0,0 +1,585 @@
+use i_float::int::point::IntPoint;
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    index: usize,
+    x: i64,
+    y: i64,
+    prev: usize,
+    next: usize,
+}
+
+/// Ear clipping triangulator for a set of contours, holes are joined to their outer
+/// contours by bridges before clipping. All predicates are evaluated in integer arithmetic.
+///
+/// Internally the rings are counterclockwise for outer contours and clockwise for holes,
+/// so the produced triangles are counterclockwise.
+pub(crate) struct EarClipper {
+    nodes: Vec<Node>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl EarClipper {
+    /// Triangulates contours oriented as the overlay produces them:
+    /// outer contours are clockwise and holes are counterclockwise.
+    ///
+    /// Vertex indices are assigned sequentially over all points of all contours.
+    /// Returns counterclockwise triangles.
+    pub(crate) fn triangulate<'a, I>(contours: I) -> Vec<[usize; 3]>
+    where
+        I: Iterator<Item = &'a [IntPoint]>,
+    {
+        let mut clipper = Self { nodes: Vec::new(), triangles: Vec::new() };
+
+        let mut rings = Vec::new();
+        let mut holes = Vec::new();
+        let mut offset = 0;
+        for contour in contours {
+            let area = if contour.len() >= 3 { contour_area(contour) } else { 0 };
+            if area != 0 {
+                let start = clipper.linked_list(contour, offset);
+                if area > 0 {
+                    rings.push(start);
+                } else {
+                    holes.push(clipper.leftmost(start));
+                }
+            }
+            offset += contour.len();
+        }
+
+        holes.sort_by(|&a, &b| clipper.compare_x_y_slope(a, b));
+
+        for hole in holes {
+            clipper.eliminate_hole(hole, &mut rings);
+        }
+
+        for ring in rings {
+            clipper.earcut_linked(ring, 0);
+        }
+
+        clipper.triangles
+    }
+
+    /// Links the contour in reversed order.
+    fn linked_list(&mut self, contour: &[IntPoint], offset: usize) -> usize {
+        let mut count = contour.len();
+        if contour[0] == contour[count - 1] {
+            count -= 1;
+        }
+
+        let first = self.nodes.len();
+        for i in (0..count).rev() {
+            let p = contour[i];
+            let id = self.nodes.len();
+            self.nodes.push(Node {
+                index: offset + i,
+                x: p.x as i64,
+                y: p.y as i64,
+                prev: id.wrapping_sub(1),
+                next: id + 1,
+            });
+        }
+
+        let last = self.nodes.len() - 1;
+        self.nodes[first].prev = last;
+        self.nodes[last].next = first;
+
+        first
+    }
+
+    #[inline(always)]
+    fn next(&self, p: usize) -> usize {
+        self.nodes[p].next
+    }
+
+    #[inline(always)]
+    fn prev(&self, p: usize) -> usize {
+        self.nodes[p].prev
+    }
+
+    #[inline(always)]
+    fn equals(&self, a: usize, b: usize) -> bool {
+        let a = &self.nodes[a];
+        let b = &self.nodes[b];
+        a.x == b.x && a.y == b.y
+    }
+
+    /// Signed area of a triangle, negative if it is counterclockwise.
+    #[inline(always)]
+    fn area(&self, p: usize, q: usize, r: usize) -> i64 {
+        let p = &self.nodes[p];
+        let q = &self.nodes[q];
+        let r = &self.nodes[r];
+        (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y)
+    }
+
+    fn remove(&mut self, p: usize) {
+        let Node { prev, next, .. } = self.nodes[p];
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+    }
+
+    fn leftmost(&self, start: usize) -> usize {
+        let mut p = start;
+        let mut leftmost = start;
+        loop {
+            let a = &self.nodes[p];
+            let b = &self.nodes[leftmost];
+            if a.x < b.x || a.x == b.x && a.y < b.y {
+                leftmost = p;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        leftmost
+    }
+
+    /// Holes meeting at the same leftmost point are sorted counterclockwise,
+    /// so the bridge of every next hole is the point where they meet.
+    fn compare_x_y_slope(&self, a: usize, b: usize) -> std::cmp::Ordering {
+        let na = &self.nodes[a];
+        let nb = &self.nodes[b];
+        na.x.cmp(&nb.x).then(na.y.cmp(&nb.y)).then_with(|| {
+            let ra = &self.nodes[na.next];
+            let rb = &self.nodes[nb.next];
+            let (ax, ay) = (ra.x - na.x, ra.y - na.y);
+            let (bx, by) = (rb.x - nb.x, rb.y - nb.y);
+            0.cmp(&(ax * by - ay * bx))
+        })
+    }
+
+    fn eliminate_hole(&mut self, hole: usize, rings: &mut [usize]) {
+        if let Some((ring, bridge)) = self.find_hole_bridge(hole, rings) {
+            let bridge_reverse = self.split_polygon(bridge, hole);
+
+            // filter collinear points around the cuts
+            self.filter_points(bridge_reverse, Some(self.next(bridge_reverse)));
+            rings[ring] = self.filter_points(bridge, Some(self.next(bridge)));
+        }
+    }
+
+    /// Finds a point of the rings visible from the hole's leftmost point.
+    fn find_hole_bridge(&self, hole: usize, rings: &[usize]) -> Option<(usize, usize)> {
+        let hx = self.nodes[hole].x as i128;
+        let hy = self.nodes[hole].y as i128;
+        let hole_next = self.next(hole);
+
+        // find a segment intersected by a ray from the hole's leftmost point to the left,
+        // the intersection x is stored as a fraction qn / qd with a positive denominator;
+        // the segment's endpoint with lesser x will be potential connection point
+        // unless they intersect at a vertex, then choose the vertex
+        let mut best: Option<(usize, usize, i128, i128)> = None;
+        for (ring, &start) in rings.iter().enumerate() {
+            let mut p = start;
+            loop {
+                if self.equals(hole, p) && self.locally_inside(p, hole_next) {
+                    return Some((ring, p));
+                }
+
+                let n = self.next(p);
+                let is_vertex = self.equals(hole, p) || self.equals(hole, n);
+                let (px, py) = (self.nodes[p].x as i128, self.nodes[p].y as i128);
+                let (nx, ny) = (self.nodes[n].x as i128, self.nodes[n].y as i128);
+                if !is_vertex && hy <= py && hy >= ny && ny != py {
+                    let den = py - ny;
+                    let num = px * den - (hy - py) * (nx - px);
+                    let is_closer = match best {
+                        Some((_, _, qn, qd)) => num * qd > qn * den,
+                        None => true,
+                    };
+                    if num <= hx * den && is_closer {
+                        let m = if px < nx { p } else { n };
+                        if num == hx * den {
+                            // hole touches the segment; pick leftmost endpoint
+                            return Some((ring, m));
+                        }
+                        best = Some((ring, m, num, den));
+                    }
+                }
+
+                p = n;
+                if p == start {
+                    break;
+                }
+            }
+        }
+
+        let (ring, mut m, qn, qd) = best?;
+
+        // look for points inside the triangle of hole point, segment intersection and endpoint;
+        // if there are no points found, we have a valid connection;
+        // otherwise choose the point of the minimum angle with the ray as connection point
+        let stop = m;
+        let mx = self.nodes[m].x as i128;
+        let my = self.nodes[m].y as i128;
+
+        // the triangle is scaled by qd to keep the intersection point integer
+        let (ax, bx) = if hy < my { (hx * qd, qn) } else { (qn, hx * qd) };
+        let (ay, by) = (hy * qd, hy * qd);
+        let (cx, cy) = (mx * qd, my * qd);
+
+        let mut tan_min: Option<(i128, i128)> = None;
+        let mut p = m;
+        loop {
+            let px = self.nodes[p].x as i128;
+            let py = self.nodes[p].y as i128;
+            if hx >= px && px >= mx && hx != px
+                && point_in_triangle(ax, ay, cx, cy, bx, by, px * qd, py * qd)
+            {
+                // tangential
+                let tn = (hy - py).abs();
+                let td = hx - px;
+
+                let is_better = match tan_min {
+                    None => true,
+                    Some((mn, md)) => {
+                        let a = tn * md;
+                        let b = mn * td;
+                        a < b || a == b && (px > self.nodes[m].x as i128
+                            || px == self.nodes[m].x as i128 && self.sector_contains_sector(m, p))
+                    }
+                };
+
+                if self.locally_inside(p, hole) && is_better {
+                    m = p;
+                    tan_min = Some((tn, td));
+                }
+            }
+
+            p = self.next(p);
+            if p == stop {
+                break;
+            }
+        }
+
+        Some((ring, m))
+    }
+
+    /// Whether sector in vertex m contains sector in vertex p in the same coordinates.
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        self.area(self.prev(m), m, self.prev(p)) < 0 && self.area(self.next(p), m, self.next(m)) < 0
+    }
+
+    /// Links two vertices with a bridge, if they are in the same ring it is split into two,
+    /// otherwise the rings are merged.
+    ///
+    /// Returns the copy of `b` that starts the second part.
+    fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+        let a2 = self.nodes.len();
+        let b2 = a2 + 1;
+        let an = self.next(a);
+        let bp = self.prev(b);
+
+        self.nodes.push(self.nodes[a]);
+        self.nodes.push(self.nodes[b]);
+
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+
+        self.nodes[a2].next = an;
+        self.nodes[an].prev = a2;
+
+        self.nodes[b2].next = a2;
+        self.nodes[a2].prev = b2;
+
+        self.nodes[bp].next = b2;
+        self.nodes[b2].prev = bp;
+
+        b2
+    }
+
+    /// Removes duplicated and collinear points.
+    fn filter_points(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let mut again = false;
+            if self.equals(p, self.next(p)) || self.area(self.prev(p), p, self.next(p)) == 0 {
+                self.remove(p);
+                p = self.prev(p);
+                end = p;
+                if p == self.next(p) {
+                    break;
+                }
+                again = true;
+            } else {
+                p = self.next(p);
+            }
+
+            if !again && p == end {
+                break;
+            }
+        }
+
+        end
+    }
+
+    fn earcut_linked(&mut self, start: usize, pass: u8) {
+        let mut ear = start;
+        let mut stop = ear;
+
+        while self.prev(ear) != self.next(ear) {
+            let prev = self.prev(ear);
+            let next = self.next(ear);
+
+            if self.is_ear(ear) {
+                self.triangles.push([self.nodes[prev].index, self.nodes[ear].index, self.nodes[next].index]);
+                self.remove(ear);
+
+                ear = self.next(next);
+                stop = ear;
+                continue;
+            }
+
+            ear = next;
+
+            if ear == stop {
+                // no more ears, try to filter points and cure intersections, split the ring at the end
+                match pass {
+                    0 => {
+                        let ear = self.filter_points(ear, None);
+                        self.earcut_linked(ear, 1);
+                    }
+                    1 => {
+                        let ear = self.filter_points(ear, None);
+                        let ear = self.cure_local_intersections(ear);
+                        self.earcut_linked(ear, 2);
+                    }
+                    _ => self.split_earcut(ear),
+                }
+                break;
+            }
+        }
+    }
+
+    fn is_ear(&self, ear: usize) -> bool {
+        let a = self.prev(ear);
+        let c = self.next(ear);
+
+        if self.area(a, ear, c) >= 0 {
+            // reflex, can't be an ear
+            return false;
+        }
+
+        let na = &self.nodes[a];
+        let nb = &self.nodes[ear];
+        let nc = &self.nodes[c];
+
+        let x0 = na.x.min(nb.x).min(nc.x);
+        let y0 = na.y.min(nb.y).min(nc.y);
+        let x1 = na.x.max(nb.x).max(nc.x);
+        let y1 = na.y.max(nb.y).max(nc.y);
+
+        // make sure we don't have other points inside the potential ear
+        let mut p = self.next(c);
+        while p != a {
+            let np = &self.nodes[p];
+            if np.x >= x0 && np.x <= x1 && np.y >= y0 && np.y <= y1
+                && !(np.x == na.x && np.y == na.y)
+                && point_in_triangle(
+                    na.x as i128, na.y as i128,
+                    nb.x as i128, nb.y as i128,
+                    nc.x as i128, nc.y as i128,
+                    np.x as i128, np.y as i128,
+                )
+                && self.area(np.prev, p, np.next) >= 0
+            {
+                return false;
+            }
+            p = np.next;
+        }
+
+        true
+    }
+
+    /// Goes through all polygon nodes and cures small local self-intersections.
+    fn cure_local_intersections(&mut self, start: usize) -> usize {
+        let mut start = start;
+        let mut p = start;
+        loop {
+            let a = self.prev(p);
+            let b = self.next(self.next(p));
+
+            if !self.equals(a, b)
+                && self.intersects(a, p, self.next(p), b)
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                self.triangles.push([self.nodes[a].index, self.nodes[p].index, self.nodes[b].index]);
+
+                let next = self.next(p);
+                self.remove(p);
+                self.remove(next);
+
+                p = b;
+                start = b;
+            }
+
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+
+        self.filter_points(p, None)
+    }
+
+    /// Tries to split the ring into two by a valid diagonal and triangulates them independently.
+    fn split_earcut(&mut self, start: usize) {
+        let mut a = start;
+        loop {
+            let mut b = self.next(self.next(a));
+            while b != self.prev(a) {
+                if self.nodes[a].index != self.nodes[b].index && self.is_valid_diagonal(a, b) {
+                    let c = self.split_polygon(a, b);
+
+                    let a = self.filter_points(a, Some(self.next(a)));
+                    let c = self.filter_points(c, Some(self.next(c)));
+
+                    self.earcut_linked(a, 0);
+                    self.earcut_linked(c, 0);
+                    return;
+                }
+                b = self.next(b);
+            }
+
+            a = self.next(a);
+            if a == start {
+                break;
+            }
+        }
+    }
+
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let ib = self.nodes[b].index;
+        if self.nodes[self.next(a)].index == ib || self.nodes[self.prev(a)].index == ib {
+            return false;
+        }
+
+        if self.intersects_polygon(a, b) {
+            return false;
+        }
+
+        let is_visible = self.locally_inside(a, b)
+            && self.locally_inside(b, a)
+            && self.middle_inside(a, b)
+            // does not create opposite-facing sectors
+            && (self.area(self.prev(a), a, self.prev(b)) != 0 || self.area(a, self.prev(b), b) != 0);
+
+        // special zero-length case
+        let is_zero_length = self.equals(a, b)
+            && self.area(self.prev(a), a, self.next(a)) > 0
+            && self.area(self.prev(b), b, self.next(b)) > 0;
+
+        is_visible || is_zero_length
+    }
+
+    fn intersects(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+        let o1 = self.area(p1, q1, p2).signum();
+        let o2 = self.area(p1, q1, q2).signum();
+        let o3 = self.area(p2, q2, p1).signum();
+        let o4 = self.area(p2, q2, q1).signum();
+
+        if o1 != o2 && o3 != o4 {
+            return true;
+        }
+
+        o1 == 0 && self.on_segment(p1, p2, q1)
+            || o2 == 0 && self.on_segment(p1, q2, q1)
+            || o3 == 0 && self.on_segment(p2, p1, q2)
+            || o4 == 0 && self.on_segment(p2, q1, q2)
+    }
+
+    /// For collinear points p, q, r, checks if point q lies on segment pr.
+    fn on_segment(&self, p: usize, q: usize, r: usize) -> bool {
+        let p = &self.nodes[p];
+        let q = &self.nodes[q];
+        let r = &self.nodes[r];
+        q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+    }
+
+    fn intersects_polygon(&self, a: usize, b: usize) -> bool {
+        let ia = self.nodes[a].index;
+        let ib = self.nodes[b].index;
+        let mut p = a;
+        loop {
+            let n = self.next(p);
+            let ip = self.nodes[p].index;
+            let in_ = self.nodes[n].index;
+            if ip != ia && in_ != ia && ip != ib && in_ != ib && self.intersects(p, n, a, b) {
+                return true;
+            }
+            p = n;
+            if p == a {
+                break;
+            }
+        }
+        false
+    }
+
+    /// Whether a diagonal from `a` to `b` lies locally inside the ring.
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let prev = self.prev(a);
+        let next = self.next(a);
+        if self.area(prev, a, next) < 0 {
+            self.area(a, b, next) >= 0 && self.area(a, prev, b) >= 0
+        } else {
+            self.area(a, b, prev) < 0 || self.area(a, next, b) < 0
+        }
+    }
+
+    /// Whether the middle point of a diagonal is inside the ring.
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        // the middle point is doubled to stay integer
+        let mx = (self.nodes[a].x + self.nodes[b].x) as i128;
+        let my = (self.nodes[a].y + self.nodes[b].y) as i128;
+
+        let mut inside = false;
+        let mut p = a;
+        loop {
+            let n = self.next(p);
+            let x0 = self.nodes[p].x as i128;
+            let y0 = self.nodes[p].y as i128;
+            let x1 = self.nodes[n].x as i128;
+            let y1 = self.nodes[n].y as i128;
+
+            if (2 * y0 > my) != (2 * y1 > my) && y1 != y0 {
+                let lhs = (mx - 2 * x0) * (y1 - y0);
+                let rhs = (x1 - x0) * (my - 2 * y0);
+                let is_left = if y1 > y0 { lhs < rhs } else { lhs > rhs };
+                if is_left {
+                    inside = !inside;
+                }
+            }
+
+            p = n;
+            if p == a {
+                break;
+            }
+        }
+
+        inside
+    }
+}
+
+/// Checks if a point lies inside or on the edges of a triangle.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+fn point_in_triangle(ax: i128, ay: i128, bx: i128, by: i128, cx: i128, cy: i128, px: i128, py: i128) -> bool {
+    (cx - px) * (ay - py) >= (ax - px) * (cy - py)
+        && (ax - px) * (by - py) >= (bx - px) * (ay - py)
+        && (bx - px) * (cy - py) >= (cx - px) * (by - py)
+}
+
+/// Double area of a contour, positive if it is clockwise.
+fn contour_area(contour: &[IntPoint]) -> i64 {
+    let mut area: i64 = 0;
+    let mut p0 = contour[contour.len() - 1];
+    for &p1 in contour.iter() {
+        area += p1.x as i64 * p0.y as i64 - p1.y as i64 * p0.x as i64;
+        p0 = p1;
+    }
+    area
+}
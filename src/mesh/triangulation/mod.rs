@@ -0,0 +1,385 @@
+pub(crate) mod ear_clip;
+mod delaunay;
+
+use i_float::adapter::FloatPointAdapter;
+use i_float::float::compatible::FloatPointCompatible;
+use i_float::float::number::FloatNumber;
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::{IntShape, IntShapes};
+use crate::float::source::resource::OverlayResource;
+use crate::mesh::triangulation::delaunay::delaunay_refine;
+use crate::mesh::triangulation::ear_clip::EarClipper;
+
+/// A triangle mesh of integer shapes.
+///
+/// Every three consecutive `indices` form a triangle, triangles are clockwise like outer contours.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntTriangulation {
+    pub points: Vec<IntPoint>,
+    pub indices: Vec<usize>,
+}
+
+/// A triangle mesh of float shapes.
+///
+/// Every three consecutive `indices` form a triangle, triangles are clockwise like outer contours.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangulation<P> {
+    pub points: Vec<P>,
+    pub indices: Vec<usize>,
+}
+
+/// The `IntTriangulate` trait triangulates integer shapes with holes.
+///
+/// Contours are expected to be oriented as the overlay produces them: outer contours are clockwise
+/// and holes are counterclockwise. Holes may touch each other or their outer contour at a vertex.
+/// The result points are the contour points in the source order.
+pub trait IntTriangulate {
+    /// Triangulates the shapes by ear clipping.
+    fn triangulate(&self) -> IntTriangulation;
+
+    /// Triangulates the shapes and refines the result to a constrained Delaunay triangulation.
+    fn triangulate_with_delaunay(&self) -> IntTriangulation;
+}
+
+impl IntTriangulate for IntShapes {
+    #[inline]
+    fn triangulate(&self) -> IntTriangulation {
+        let mut triangulation = IntTriangulation::default();
+        for shape in self.iter() {
+            triangulation.append_shape(shape, false);
+        }
+        triangulation
+    }
+
+    #[inline]
+    fn triangulate_with_delaunay(&self) -> IntTriangulation {
+        let mut triangulation = IntTriangulation::default();
+        for shape in self.iter() {
+            triangulation.append_shape(shape, true);
+        }
+        triangulation
+    }
+}
+
+impl IntTriangulate for IntShape {
+    #[inline]
+    fn triangulate(&self) -> IntTriangulation {
+        let mut triangulation = IntTriangulation::default();
+        triangulation.append_shape(self, false);
+        triangulation
+    }
+
+    #[inline]
+    fn triangulate_with_delaunay(&self) -> IntTriangulation {
+        let mut triangulation = IntTriangulation::default();
+        triangulation.append_shape(self, true);
+        triangulation
+    }
+}
+
+impl IntTriangulate for [IntPoint] {
+    #[inline]
+    fn triangulate(&self) -> IntTriangulation {
+        let mut triangulation = IntTriangulation::default();
+        triangulation.append_shape(&[self], false);
+        triangulation
+    }
+
+    #[inline]
+    fn triangulate_with_delaunay(&self) -> IntTriangulation {
+        let mut triangulation = IntTriangulation::default();
+        triangulation.append_shape(&[self], true);
+        triangulation
+    }
+}
+
+impl IntTriangulation {
+    fn append_shape<C: AsRef<[IntPoint]>>(&mut self, shape: &[C], delaunay: bool) {
+        let offset = self.points.len();
+        for contour in shape.iter() {
+            self.points.extend_from_slice(contour.as_ref());
+        }
+
+        let mut triangles = EarClipper::triangulate(shape.iter().map(|contour| contour.as_ref()));
+        if delaunay {
+            delaunay_refine(&self.points[offset..], &mut triangles);
+        }
+
+        append_indices(&triangles, offset, &mut self.indices);
+    }
+}
+
+/// The `FloatTriangulate` trait triangulates float shapes with holes.
+///
+/// Contours are expected to be oriented as the overlay produces them: outer contours are clockwise
+/// and holes are counterclockwise. Holes may touch each other or their outer contour at a vertex.
+/// The result points are the source points in the source order, all predicates are evaluated in integer space.
+pub trait FloatTriangulate<P, T>
+where
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    /// Triangulates the contours, or shapes by ear clipping.
+    fn triangulate(&self) -> Triangulation<P>;
+
+    /// Triangulates the contours, or shapes and refines the result to a constrained Delaunay triangulation.
+    fn triangulate_with_delaunay(&self) -> Triangulation<P>;
+}
+
+impl<S, P, T> FloatTriangulate<P, T> for S
+where
+    S: OverlayResource<P, T>,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    #[inline]
+    fn triangulate(&self) -> Triangulation<P> {
+        float_triangulate(self, false)
+    }
+
+    #[inline]
+    fn triangulate_with_delaunay(&self) -> Triangulation<P> {
+        float_triangulate(self, true)
+    }
+}
+
+fn float_triangulate<S, P, T>(source: &S, delaunay: bool) -> Triangulation<P>
+where
+    S: OverlayResource<P, T> + ?Sized,
+    P: FloatPointCompatible<T>,
+    T: FloatNumber,
+{
+    let adapter = FloatPointAdapter::with_iter(source.iter_paths().flatten());
+
+    let contours: Vec<Vec<IntPoint>> = source
+        .iter_paths()
+        .map(|path| path.iter().map(|p| adapter.float_to_int(p)).collect())
+        .collect();
+
+    let mut triangles = EarClipper::triangulate(contours.iter().map(|contour| contour.as_slice()));
+    if delaunay {
+        let int_points: Vec<IntPoint> = contours.into_iter().flatten().collect();
+        delaunay_refine(&int_points, &mut triangles);
+    }
+
+    let mut indices = Vec::with_capacity(3 * triangles.len());
+    append_indices(&triangles, 0, &mut indices);
+
+    Triangulation {
+        points: source.iter_paths().flatten().copied().collect(),
+        indices,
+    }
+}
+
+/// Appends counterclockwise triangles as clockwise ones.
+#[inline]
+fn append_indices(triangles: &[[usize; 3]], offset: usize, indices: &mut Vec<usize>) {
+    indices.reserve(3 * triangles.len());
+    for triangle in triangles.iter() {
+        indices.push(offset + triangle[0]);
+        indices.push(offset + triangle[2]);
+        indices.push(offset + triangle[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay::Overlay;
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::mesh::triangulation::{FloatTriangulate, IntTriangulate, IntTriangulation};
+
+    /// Double area of all triangles, every triangle must be clockwise.
+    fn triangles_area(triangulation: &IntTriangulation) -> i64 {
+        let mut area = 0;
+        for triangle in triangulation.indices.chunks(3) {
+            let a = triangulation.points[triangle[0]];
+            let b = triangulation.points[triangle[1]];
+            let c = triangulation.points[triangle[2]];
+            let cross = (b - a).cross_product(c - a);
+            assert!(cross < 0, "triangle must be clockwise");
+            area -= cross;
+        }
+        area
+    }
+
+    /// Every inner edge must have opposite angles with a sum not greater than 180 degrees.
+    fn assert_delaunay(triangulation: &IntTriangulation) {
+        let triangles: Vec<&[usize]> = triangulation.indices.chunks(3).collect();
+        for t0 in triangles.iter() {
+            for t1 in triangles.iter() {
+                for i in 0..3 {
+                    for j in 0..3 {
+                        let (a, b, c) = (t0[i], t0[(i + 1) % 3], t0[(i + 2) % 3]);
+                        let (b1, a1, d) = (t1[j], t1[(j + 1) % 3], t1[(j + 2) % 3]);
+                        if a != a1 || b != b1 {
+                            continue;
+                        }
+                        let [pa, pb, pc, pd] = [a, b, c, d].map(|i| triangulation.points[i]);
+                        let sin_c = (pb - pc).cross_product(pa - pc) as f64;
+                        let cos_c = (pa - pc).dot_product(pb - pc) as f64;
+                        let sin_d = (pa - pd).cross_product(pb - pd) as f64;
+                        let cos_d = (pa - pd).dot_product(pb - pd) as f64;
+                        assert!(sin_c * cos_d + cos_c * sin_d >= 0.0, "edge is not Delaunay");
+                    }
+                }
+            }
+        }
+    }
+
+    fn shapes_area(shapes: &[Vec<Vec<IntPoint>>]) -> i64 {
+        let mut area = 0;
+        for path in shapes.iter().flatten() {
+            let mut p0 = path[path.len() - 1];
+            for &p1 in path.iter() {
+                area += p1.x as i64 * p0.y as i64 - p1.y as i64 * p0.x as i64;
+                p0 = p1;
+            }
+        }
+        area
+    }
+
+    fn square(x: i32, y: i32, size: i32) -> Vec<IntPoint> {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x, y + size),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x + size, y),
+        ]
+    }
+
+    fn hole(x: i32, y: i32, size: i32) -> Vec<IntPoint> {
+        let mut path = square(x, y, size);
+        path.reverse();
+        path
+    }
+
+    #[test]
+    fn test_square() {
+        let triangulation = square(0, 0, 10).as_slice().triangulate();
+
+        assert_eq!(triangulation.points.len(), 4);
+        assert_eq!(triangulation.indices.len(), 6);
+        assert_eq!(triangles_area(&triangulation), 200);
+    }
+
+    #[test]
+    fn test_counter_clockwise_contour_is_a_hole() {
+        let triangulation = hole(0, 0, 10).as_slice().triangulate();
+
+        assert!(triangulation.indices.is_empty());
+    }
+
+    #[test]
+    fn test_shape_with_hole() {
+        let shape = vec![square(0, 0, 10), hole(3, 3, 4)];
+        let triangulation = shape.triangulate();
+
+        assert_eq!(triangulation.points.len(), 8);
+        assert_eq!(triangulation.indices.len(), 3 * 8);
+        assert_eq!(triangles_area(&triangulation), 2 * (100 - 16));
+    }
+
+    #[test]
+    fn test_touching_holes() {
+        // two holes touch each other and the outer contour
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(0, 5),
+                IntPoint::new(0, 10),
+                IntPoint::new(10, 10),
+                IntPoint::new(10, 0),
+            ],
+            vec![IntPoint::new(0, 5), IntPoint::new(4, 3), IntPoint::new(4, 7)],
+            vec![IntPoint::new(4, 7), IntPoint::new(8, 5), IntPoint::new(8, 9)],
+        ];
+        let triangulation = shape.triangulate();
+
+        assert_eq!(triangles_area(&triangulation), 2 * (100 - 8 - 8));
+    }
+
+    #[test]
+    fn test_overlay_touching_holes() {
+        let subj = vec![square(0, 0, 10)];
+        let clip = vec![
+            vec![IntPoint::new(0, 5), IntPoint::new(4, 3), IntPoint::new(4, 7)],
+            vec![IntPoint::new(4, 7), IntPoint::new(8, 5), IntPoint::new(8, 9)],
+        ];
+
+        let shapes = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Difference, FillRule::NonZero);
+        let triangulation = shapes.triangulate_with_delaunay();
+
+        assert_eq!(triangles_area(&triangulation), 2 * (100 - 8 - 8));
+    }
+
+    #[test]
+    fn test_overlay_result() {
+        let subj = vec![square(0, 0, 20)];
+        let clip = vec![square(2, 2, 4), square(6, 6, 4), square(12, 2, 4), square(6, 12, 4)];
+
+        let shapes = Overlay::with_contours(&subj, &clip).overlay(OverlayRule::Difference, FillRule::NonZero);
+        let triangulation = shapes.triangulate();
+        let delaunay = shapes.triangulate_with_delaunay();
+
+        let area = shapes_area(&shapes);
+        assert_eq!(area, 2 * (400 - 4 * 16));
+        assert_eq!(triangles_area(&triangulation), area);
+        assert_eq!(triangles_area(&delaunay), area);
+        assert_eq!(triangulation.indices.len(), delaunay.indices.len());
+    }
+
+    #[test]
+    fn test_multiple_shapes() {
+        let shapes = vec![vec![square(0, 0, 10)], vec![square(20, 0, 10), hole(23, 3, 4)]];
+        let triangulation = shapes.triangulate();
+
+        assert_eq!(triangulation.points.len(), 12);
+        assert_eq!(triangles_area(&triangulation), 2 * (100 + 100 - 16));
+    }
+
+    #[test]
+    fn test_delaunay() {
+        // a thin fan that ear clipping triangulates from a single vertex
+        let path = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(0, 10),
+            IntPoint::new(1, 11),
+            IntPoint::new(9, 11),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 0),
+        ];
+
+        let triangulation = path.as_slice().triangulate_with_delaunay();
+
+        assert_eq!(triangulation.indices.len(), 12);
+        assert_eq!(triangles_area(&triangulation), 2 * 100 + 2 * 9);
+
+        assert_delaunay(&triangulation);
+    }
+
+    #[test]
+    fn test_float_shape_with_hole() {
+        let shape = vec![
+            vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]],
+            vec![[0.25, 0.25], [0.75, 0.25], [0.75, 0.75], [0.25, 0.75]],
+        ];
+
+        let triangulation = shape.triangulate_with_delaunay();
+
+        assert_eq!(triangulation.points.len(), 8);
+        assert_eq!(triangulation.points[5], [0.75, 0.25]);
+
+        let mut area: f64 = 0.0;
+        for triangle in triangulation.indices.chunks(3) {
+            let a = triangulation.points[triangle[0]];
+            let b = triangulation.points[triangle[1]];
+            let c = triangulation.points[triangle[2]];
+            area -= (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        }
+        assert!((area - 2.0 * 0.75).abs() < 0.000001);
+    }
+}
@@ -0,0 +1,245 @@
+//! Recording of overlay operations for later reproduction.
+//!
+//! A `Recorder` is attached to an `Overlay`, a `StringOverlay` or their float counterparts. Every operation
+//! executed by such an overlay stores its input contours, rules and `Solver` into the recorder as a `Record`.
+//! A record is saved in the same json schema as the test data in `tests/boolean` and `tests/string`,
+//! so a surprising result can be replayed or added as a regression test.
+//!
+//! Contours are recorded in the integer space exactly as the solver receives them: degenerate and
+//! collinear points are already removed, and float overlays store the converted coordinates.
+//!
+//! ```rust
+//! use i_float::int::point::IntPoint;
+//! use i_overlay::core::fill_rule::FillRule;
+//! use i_overlay::core::overlay::Overlay;
+//! use i_overlay::core::overlay_rule::OverlayRule;
+//! use i_overlay::record::{Record, Recorder};
+//!
+//! let subj = [IntPoint::new(0, 0), IntPoint::new(0, 10), IntPoint::new(10, 10), IntPoint::new(10, 0)];
+//! let clip = [IntPoint::new(5, 5), IntPoint::new(5, 15), IntPoint::new(15, 15), IntPoint::new(15, 5)];
+//!
+//! let recorder = Recorder::new();
+//! let mut overlay = Overlay::with_contour(&subj, &clip);
+//! overlay.record_to(&recorder);
+//! let shapes = overlay.overlay(OverlayRule::Union, FillRule::NonZero);
+//!
+//! if let Record::Overlay(record) = &recorder.records()[0] {
+//!     assert_eq!(record.replay(), Some(shapes));
+//! }
+//! ```
+
+pub mod overlay;
+pub mod string;
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use i_float::int::point::IntPoint;
+use i_shape::int::shape::IntContour;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::core::fill_rule::FillRule;
+use crate::record::overlay::OverlayRecord;
+use crate::record::string::StringRecord;
+use crate::segm::segment::Segment;
+
+/// A shared collector of records. Clones of a recorder collect into the same list,
+/// so one recorder can be attached to many overlays, even from different threads.
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    records: Arc<Mutex<Vec<Record>>>,
+}
+
+impl Recorder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all records in the order the operations were executed.
+    pub fn records(&self) -> Vec<Record> {
+        self.lock().clone()
+    }
+
+    /// Removes all collected records.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    #[inline]
+    pub(crate) fn push(&self, record: Record) {
+        self.lock().push(record);
+    }
+
+    #[inline]
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Record>> {
+        // a record is pushed at once, so a poisoned list is still consistent
+        self.records.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+/// A recorded operation of an `Overlay` or a `StringOverlay`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Record {
+    Overlay(OverlayRecord),
+    String(StringRecord),
+}
+
+impl Record {
+    /// Serializes the record into json. Expected results of all rules are computed by replaying the record,
+    /// so the json is a complete test case of `tests/boolean` or `tests/string`.
+    pub fn to_json(&self) -> String {
+        let json = match self {
+            Record::Overlay(record) => serde_json::to_string_pretty(&record.to_test()),
+            Record::String(record) => serde_json::to_string_pretty(&record.to_test()),
+        };
+
+        // records contain only integers, strings and maps with string keys
+        json.expect("record is always serializable")
+    }
+
+    /// Parses a record from json, test cases of `tests/boolean` and `tests/string` are records too.
+    #[inline]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes the record into a json file.
+    pub fn save<Q: AsRef<Path>>(&self, path: Q) -> io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// Reads a record from a json file.
+    pub fn load<Q: AsRef<Path>>(path: Q) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json).map_err(io::Error::other)
+    }
+}
+
+/// Restores closed contours from directed segments.
+/// - `count`: Number of times a segment goes from `a` to `b`, negative for the opposite direction.
+///
+/// Segments of closed contours meet every point as many times as they leave it,
+/// so walking along unused segments always returns to the start point.
+pub(crate) fn contours<C: Send, F: Fn(&C) -> i32>(segments: &[Segment<C>], count: F) -> Vec<IntContour> {
+    let mut starts = Vec::with_capacity(segments.len());
+    let mut outgoing: HashMap<IntPoint, Vec<IntPoint>> = HashMap::with_capacity(segments.len());
+    for segment in segments.iter() {
+        let n = count(&segment.count);
+        let (a, b) = if n > 0 {
+            (segment.x_segment.a, segment.x_segment.b)
+        } else {
+            (segment.x_segment.b, segment.x_segment.a)
+        };
+        for _ in 0..n.unsigned_abs() {
+            starts.push(a);
+            outgoing.entry(a).or_default().push(b);
+        }
+    }
+
+    let mut contours = Vec::new();
+    for start in starts {
+        while outgoing.get(&start).is_some_and(|ends| !ends.is_empty()) {
+            let mut contour = vec![start];
+            let mut p = start;
+            while let Some(next) = outgoing.get_mut(&p).and_then(|ends| ends.pop()) {
+                if next == start {
+                    break;
+                }
+                contour.push(next);
+                p = next;
+            }
+            contours.push(contour);
+        }
+    }
+
+    contours
+}
+
+// Test data stores fill rules as indices.
+pub(crate) mod fill_rule_index {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(fill_rule: &FillRule, serializer: S) -> Result<S::Ok, S::Error> {
+        let index = match fill_rule {
+            FillRule::EvenOdd => 0,
+            FillRule::NonZero => 1,
+            FillRule::Positive => 2,
+            FillRule::Negative => 3,
+        };
+        serializer.serialize_i32(index)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FillRule, D::Error> {
+        match i32::deserialize(deserializer)? {
+            0 => Ok(FillRule::EvenOdd),
+            1 => Ok(FillRule::NonZero),
+            2 => Ok(FillRule::Positive),
+            3 => Ok(FillRule::Negative),
+            _ => Err(serde::de::Error::custom("invalid value for fillRule")),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn even_odd() -> FillRule {
+        FillRule::EvenOdd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::fill_rule::FillRule;
+    use crate::core::overlay::{Overlay, ShapeType};
+    use crate::core::overlay_rule::OverlayRule;
+    use crate::record::{Record, Recorder};
+
+    #[test]
+    fn test_contours() {
+        let square = [IntPoint::new(0, 0), IntPoint::new(0, 10), IntPoint::new(10, 10), IntPoint::new(10, 0)];
+        let mut overlay = Overlay::new(8);
+        overlay.add_contour(&square, ShapeType::Subject);
+        overlay.add_contour(&square, ShapeType::Subject);
+        overlay.add_contour(&square[..3], ShapeType::Clip);
+
+        let recorder = Recorder::new();
+        overlay.record_to(&recorder);
+        let shapes = overlay.clone().overlay(OverlayRule::Xor, FillRule::NonZero);
+
+        let records = recorder.records();
+        assert_eq!(records.len(), 1);
+        let Record::Overlay(record) = &records[0] else { panic!("overlay record is expected") };
+
+        assert_eq!(record.subj_paths.len(), 2);
+        assert!(record.subj_paths.iter().all(|contour| contour.len() == 4));
+        assert_eq!(record.clip_paths.len(), 1);
+        assert_eq!(record.clip_paths[0].len(), 3);
+        assert_eq!(record.replay(), Some(shapes));
+    }
+
+    #[test]
+    fn test_json() {
+        let subj = [IntPoint::new(0, 0), IntPoint::new(0, 10), IntPoint::new(10, 10), IntPoint::new(10, 0)];
+        let clip = [IntPoint::new(5, 5), IntPoint::new(5, 15), IntPoint::new(15, 15), IntPoint::new(15, 5)];
+
+        let recorder = Recorder::new();
+        let mut overlay = Overlay::with_contour(&subj, &clip);
+        overlay.record_to(&recorder);
+        let _ = overlay.into_graph(FillRule::Positive);
+
+        let json = recorder.records()[0].to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["fillRule"], 2);
+        assert_eq!(value["union"].as_array().unwrap().len(), 1);
+        assert!(value.get("overlayRule").is_none());
+
+        let Ok(Record::Overlay(record)) = Record::from_json(&json) else { panic!("overlay record is expected") };
+        assert_eq!(record.fill_rule, FillRule::Positive);
+        assert_eq!(record.replay(), None);
+        assert_eq!(
+            record.replay_with_rule(OverlayRule::Difference),
+            Overlay::with_contour(&subj, &clip).overlay(OverlayRule::Difference, FillRule::Positive)
+        );
+    }
+}
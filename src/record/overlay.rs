@@ -0,0 +1,97 @@
+use i_shape::int::shape::{IntContour, IntShapes};
+use serde::{Deserialize, Serialize};
+use crate::core::fill_rule::FillRule;
+use crate::core::overlay::Overlay;
+use crate::core::overlay_rule::OverlayRule;
+use crate::core::solver::Solver;
+use crate::record::{contours, fill_rule_index, Record, Recorder};
+
+/// A recorded boolean operation, it follows the schema of `tests/boolean`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlayRecord {
+    #[serde(rename = "fillRule", with = "fill_rule_index", default = "fill_rule_index::even_odd")]
+    pub fill_rule: FillRule,
+    #[serde(rename = "subjPaths")]
+    pub subj_paths: Vec<IntContour>,
+    #[serde(rename = "clipPaths")]
+    pub clip_paths: Vec<IntContour>,
+    /// The executed boolean operation, `None` if the overlay was converted into a graph or vectors.
+    #[serde(rename = "overlayRule", default, skip_serializing_if = "Option::is_none")]
+    pub overlay_rule: Option<OverlayRule>,
+    #[serde(rename = "minArea", default)]
+    pub min_area: usize,
+    #[serde(default)]
+    pub solver: Solver,
+}
+
+// A record with expected results of all rules.
+#[derive(Serialize)]
+pub(super) struct OverlayTest<'a> {
+    #[serde(flatten)]
+    record: &'a OverlayRecord,
+    clip: Vec<IntShapes>,
+    subject: Vec<IntShapes>,
+    difference: Vec<IntShapes>,
+    #[serde(rename = "inverseDifference")]
+    inverse_difference: Vec<IntShapes>,
+    intersect: Vec<IntShapes>,
+    union: Vec<IntShapes>,
+    xor: Vec<IntShapes>,
+}
+
+impl OverlayRecord {
+    /// Creates a new `Overlay` with the recorded contours.
+    #[inline]
+    pub fn to_overlay(&self) -> Overlay {
+        Overlay::with_contours(&self.subj_paths, &self.clip_paths)
+    }
+
+    /// Repeats the recorded boolean operation.
+    /// Returns `None` if the overlay was converted into a graph or vectors instead.
+    #[inline]
+    pub fn replay(&self) -> Option<IntShapes> {
+        self.overlay_rule.map(|overlay_rule| self.replay_with_rule(overlay_rule))
+    }
+
+    /// Executes a boolean operation on the recorded contours with the recorded fill rule, min area and solver.
+    /// - `overlay_rule`: The boolean operation rule to apply.
+    #[inline]
+    pub fn replay_with_rule(&self, overlay_rule: OverlayRule) -> IntShapes {
+        self.to_overlay().overlay_with_min_area_and_solver(overlay_rule, self.fill_rule, self.min_area, self.solver)
+    }
+
+    pub(super) fn to_test(&self) -> OverlayTest<'_> {
+        OverlayTest {
+            record: self,
+            clip: vec![self.replay_with_rule(OverlayRule::Clip)],
+            subject: vec![self.replay_with_rule(OverlayRule::Subject)],
+            difference: vec![self.replay_with_rule(OverlayRule::Difference)],
+            inverse_difference: vec![self.replay_with_rule(OverlayRule::InverseDifference)],
+            intersect: vec![self.replay_with_rule(OverlayRule::Intersect)],
+            union: vec![self.replay_with_rule(OverlayRule::Union)],
+            xor: vec![self.replay_with_rule(OverlayRule::Xor)],
+        }
+    }
+}
+
+impl Overlay {
+    /// Attaches a recorder, every following operation of the overlay is stored into it as a `Record`.
+    /// Contours added before the call are recorded as well.
+    #[inline]
+    pub fn record_to(&mut self, recorder: &Recorder) {
+        self.recorder = Some(recorder.clone());
+    }
+
+    pub(crate) fn record(&self, fill_rule: FillRule, overlay_rule: Option<OverlayRule>, min_area: usize, solver: Solver) {
+        let recorder = if let Some(recorder) = &self.recorder { recorder } else { return; };
+
+        recorder.push(Record::Overlay(OverlayRecord {
+            fill_rule,
+            subj_paths: contours(&self.segments, |count| count.subj),
+            clip_paths: contours(&self.segments, |count| count.clip),
+            overlay_rule,
+            min_area,
+            solver,
+        }));
+    }
+}
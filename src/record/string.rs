@@ -0,0 +1,160 @@
+use i_float::int::point::IntPoint;
+use i_shape::int::path::{IntPath, IntPaths};
+use i_shape::int::shape::{IntContour, IntShapes};
+use serde::{Deserialize, Serialize};
+use crate::core::fill_rule::FillRule;
+use crate::core::solver::Solver;
+use crate::record::{contours, fill_rule_index, Record, Recorder};
+use crate::segm::segment::Segment;
+use crate::segm::winding_count::{ShapeCountString, STRING_BACK_CLIP, STRING_FORWARD_CLIP};
+use crate::string::clip::ClipRule;
+use crate::string::overlay::StringOverlay;
+use crate::string::rule::StringRule;
+
+/// A recorded string operation, it follows the schema of `tests/string`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StringRecord {
+    #[serde(rename = "fillRule", with = "fill_rule_index", default = "fill_rule_index::even_odd")]
+    pub fill_rule: FillRule,
+    pub body: Vec<IntContour>,
+    pub string: IntPaths,
+    /// The rule of the executed clip, `None` if the overlay was converted into a graph.
+    #[serde(rename = "clipRule", default, skip_serializing_if = "Option::is_none")]
+    pub clip_rule: Option<ClipRule>,
+    #[serde(default)]
+    pub solver: Solver,
+}
+
+// A record with expected results of slice and clip.
+#[derive(Serialize)]
+pub(super) struct StringTest<'a> {
+    #[serde(flatten)]
+    record: &'a StringRecord,
+    slice: Vec<IntShapes>,
+    clip_direct: Vec<IntPaths>,
+    clip_invert: Vec<IntPaths>,
+}
+
+impl StringRecord {
+    /// Creates a new `StringOverlay` with the recorded shape contours and string lines.
+    pub fn to_overlay(&self) -> StringOverlay {
+        let mut overlay = StringOverlay::with_shape_contours(&self.body);
+        overlay.add_string_paths(&self.string);
+        overlay
+    }
+
+    /// Repeats the recorded clip.
+    /// Returns `None` if the overlay was converted into a graph instead.
+    #[inline]
+    pub fn replay(&self) -> Option<Vec<IntPath>> {
+        self.clip_rule.map(|clip_rule| self.replay_with_rule(clip_rule))
+    }
+
+    /// Clips the recorded string lines with the recorded fill rule and solver.
+    /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
+    #[inline]
+    pub fn replay_with_rule(&self, clip_rule: ClipRule) -> Vec<IntPath> {
+        self.to_overlay().clip_string_lines_with_solver(self.fill_rule, clip_rule, self.solver)
+    }
+
+    /// Slices the recorded shapes by the recorded string lines with the recorded fill rule and solver.
+    #[inline]
+    pub fn replay_slice(&self) -> IntShapes {
+        self.to_overlay()
+            .into_graph_with_solver(self.fill_rule, self.solver)
+            .extract_shapes(StringRule::Slice)
+    }
+
+    pub(super) fn to_test(&self) -> StringTest<'_> {
+        StringTest {
+            record: self,
+            slice: vec![self.replay_slice()],
+            clip_direct: vec![self.replay_with_rule(ClipRule { invert: false, boundary_included: false })],
+            clip_invert: vec![self.replay_with_rule(ClipRule { invert: true, boundary_included: false })],
+        }
+    }
+}
+
+impl StringOverlay {
+    /// Attaches a recorder, every following operation of the overlay is stored into it as a `Record`.
+    /// Contours and lines added before the call are recorded as well.
+    #[inline]
+    pub fn record_to(&mut self, recorder: &Recorder) {
+        self.recorder = Some(recorder.clone());
+    }
+
+    pub(crate) fn record(&self, fill_rule: FillRule, clip_rule: Option<ClipRule>, solver: Solver) {
+        let recorder = if let Some(recorder) = &self.recorder { recorder } else { return; };
+
+        recorder.push(Record::String(StringRecord {
+            fill_rule,
+            body: contours(&self.segments, |count| count.subj),
+            string: string_paths(&self.segments),
+            clip_rule,
+            solver,
+        }));
+    }
+}
+
+// String lines are stored one by one, lines which continue each other are joined back into paths.
+fn string_paths(segments: &[Segment<ShapeCountString>]) -> IntPaths {
+    let mut paths: IntPaths = Vec::new();
+    for segment in segments.iter() {
+        let (a, b) = (segment.x_segment.a, segment.x_segment.b);
+        if segment.count.clip & STRING_BACK_CLIP != 0 {
+            push_line(&mut paths, a, b);
+        }
+        if segment.count.clip & STRING_FORWARD_CLIP != 0 {
+            push_line(&mut paths, b, a);
+        }
+    }
+
+    paths
+}
+
+#[inline]
+fn push_line(paths: &mut IntPaths, a: IntPoint, b: IntPoint) {
+    if let Some(path) = paths.last_mut().filter(|path| path.last() == Some(&a)) {
+        path.push(b);
+    } else {
+        paths.push(vec![a, b]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::fill_rule::FillRule;
+    use crate::record::{Record, Recorder};
+    use crate::string::clip::ClipRule;
+    use crate::string::overlay::StringOverlay;
+    use crate::string::rule::StringRule;
+
+    #[test]
+    fn test_clip() {
+        let square = [IntPoint::new(0, 0), IntPoint::new(0, 10), IntPoint::new(10, 10), IntPoint::new(10, 0)];
+        let path = [IntPoint::new(-5, 5), IntPoint::new(5, 5), IntPoint::new(5, 15)];
+
+        let recorder = Recorder::new();
+        let mut overlay = StringOverlay::with_shape_contour(&square);
+        overlay.record_to(&recorder);
+        overlay.add_string_path(&path);
+
+        let clip_rule = ClipRule { invert: true, boundary_included: false };
+        let paths = overlay.clone().clip_string_lines(FillRule::NonZero, clip_rule);
+        let shapes = overlay.into_graph(FillRule::NonZero).extract_shapes(StringRule::Slice);
+
+        let records = recorder.records();
+        assert_eq!(records.len(), 2);
+
+        let Record::String(record) = &records[0] else { panic!("string record is expected") };
+        assert_eq!(record.body.len(), 1);
+        assert_eq!(record.string, vec![path.to_vec()]);
+        assert_eq!(record.replay(), Some(paths));
+
+        let json = records[1].to_json();
+        let Ok(Record::String(record)) = Record::from_json(&json) else { panic!("string record is expected") };
+        assert!(record.clip_rule.is_none());
+        assert_eq!(record.replay_slice(), shapes);
+    }
+}
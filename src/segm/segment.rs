@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 use i_float::int::point::IntPoint;
 use i_key_sort::index::{BinKey, BinLayout};
 use crate::geom::x_segment::XSegment;
-use crate::segm::winding_count::WindingCount;
+use crate::segm::winding_count::{ShapeCountSource, ShapeCountString, WindingCount};
 
 pub type SegmentFill = u8;
 
@@ -37,6 +37,13 @@ impl<C: WindingCount> Segment<C> {
     }
 }
 
+impl Segment<ShapeCountString> {
+    #[inline(always)]
+    pub(crate) fn with_source(self, shape: u32, line: u32) -> Segment<ShapeCountSource> {
+        Segment { x_segment: self.x_segment, count: ShapeCountSource { count: self.count, shape, line, is_shared_line: false } }
+    }
+}
+
 impl<C: Send> PartialEq<Self> for Segment<C> {
     #[inline(always)]
     fn eq(&self, other: &Self) -> bool {
@@ -13,6 +13,26 @@ pub struct ShapeCountString {
     pub clip: u8,
 }
 
+/// A string count tagged with the indices of the shape and the string line a segment comes from.
+/// Merged segments keep the lowest indices, `is_shared_line` marks a segment merged from different lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ShapeCountSource {
+    pub(crate) count: ShapeCountString,
+    pub(crate) shape: u32,
+    pub(crate) line: u32,
+    pub(crate) is_shared_line: bool,
+}
+
+impl ShapeCountSource {
+    #[inline(always)]
+    fn is_shared_line_with(&self, other: &Self) -> bool {
+        self.is_shared_line || other.is_shared_line
+            || self.line != other.line && self.line != NO_SOURCE && other.line != NO_SOURCE
+    }
+}
+
+pub(crate) const NO_SOURCE: u32 = u32::MAX;
+
 pub(crate) const STRING_FORWARD_CLIP: u8 = 0b10;
 pub(crate) const STRING_BACK_CLIP: u8 = 0b1;
 
@@ -117,4 +137,46 @@ impl WindingCount for ShapeCountString {
 
         Self { subj: -self.subj, clip }
     }
-}
\ No newline at end of file
+}
+
+impl WindingCount for ShapeCountSource {
+    #[inline(always)]
+    fn is_not_empty(&self) -> bool { self.count.is_not_empty() }
+
+    #[inline(always)]
+    fn new(subj: i32, clip: i32) -> Self {
+        Self { count: ShapeCountString::new(subj, clip), shape: NO_SOURCE, line: NO_SOURCE, is_shared_line: false }
+    }
+
+    #[inline(always)]
+    fn with_shape_type(shape_type: ShapeType) -> (Self, Self) {
+        let (direct, invert) = ShapeCountString::with_shape_type(shape_type);
+        (
+            Self { count: direct, shape: NO_SOURCE, line: NO_SOURCE, is_shared_line: false },
+            Self { count: invert, shape: NO_SOURCE, line: NO_SOURCE, is_shared_line: false }
+        )
+    }
+
+    #[inline(always)]
+    fn add(self, count: Self) -> Self {
+        Self {
+            count: self.count.add(count.count),
+            shape: self.shape.min(count.shape),
+            line: self.line.min(count.line),
+            is_shared_line: self.is_shared_line_with(&count),
+        }
+    }
+
+    #[inline(always)]
+    fn apply(&mut self, count: Self) {
+        self.count.apply(count.count);
+        self.is_shared_line = self.is_shared_line_with(&count);
+        self.shape = self.shape.min(count.shape);
+        self.line = self.line.min(count.line);
+    }
+
+    #[inline(always)]
+    fn invert(self) -> Self {
+        Self { count: self.count.invert(), ..self }
+    }
+}
@@ -12,16 +12,36 @@ use crate::util::sort::SmartBinSort;
 
 pub(crate) trait SplitSegments<C: WindingCount> {
     fn split_segments(self, solver: Solver) -> Vec<Segment<C>>;
+    fn split_segments_with_buffer(self, solver: Solver, buffer: &mut SplitBuffer<C>) -> Vec<Segment<C>>;
 }
 
 impl<C: WindingCount> SplitSegments<C> for Vec<Segment<C>> {
     #[inline]
     fn split_segments(self, solver: Solver) -> Vec<Segment<C>> {
+        self.split_segments_with_buffer(solver, &mut SplitBuffer::default())
+    }
+
+    #[inline]
+    fn split_segments_with_buffer(self, solver: Solver, buffer: &mut SplitBuffer<C>) -> Vec<Segment<C>> {
         let mut segments = self;
         segments.smart_bin_sort_by(&solver, |a, b| a.x_segment.cmp(&b.x_segment));
         segments.merge_if_needed();
 
-        SplitSolver::new(solver).split(segments)
+        SplitSolver::new(solver).split(segments, buffer)
+    }
+}
+
+/// The marks and the segment buffer of the split, they can be kept between splits to reuse their memory.
+pub(crate) struct SplitBuffer<C: Send> {
+    pub(super) marks: Vec<LineMark>,
+    /// The segments of the previous iteration, the next iteration is written into them.
+    pub(crate) spare: Vec<Segment<C>>,
+}
+
+impl<C: Send> Default for SplitBuffer<C> {
+    #[inline]
+    fn default() -> Self {
+        Self { marks: Vec::new(), spare: Vec::new() }
     }
 }
 
@@ -36,19 +56,21 @@ impl SplitSolver {
     }
 
     #[inline]
-    pub(crate) fn split<C: WindingCount>(&self, segments: Vec<Segment<C>>) -> Vec<Segment<C>> {
+    pub(crate) fn split<C: WindingCount>(&self, segments: Vec<Segment<C>>, buffer: &mut SplitBuffer<C>) -> Vec<Segment<C>> {
+        buffer.marks.clear();
+
         let is_list = self.solver.is_list_split(&segments);
         let snap_radius = self.snap_radius();
         if is_list {
-            return self.list_split(snap_radius, segments);
+            return self.list_split(snap_radius, segments, buffer);
         }
 
         let is_fragmentation = self.solver.is_fragmentation_required(&segments);
 
         if is_fragmentation {
-            self.fragment_split(snap_radius, segments)
+            self.fragment_split(snap_radius, segments, buffer)
         } else {
-            self.tree_split(snap_radius, segments)
+            self.tree_split(snap_radius, segments, buffer)
         }
     }
 
@@ -95,7 +117,7 @@ impl SplitSolver {
         cross.is_round
     }
 
-    pub(super) fn apply<C: WindingCount>(&self, marks: &mut Vec<LineMark>, segments: Vec<Segment<C>>, need_to_fix: bool) -> Vec<Segment<C>> {
+    pub(super) fn apply<C: WindingCount>(&self, marks: &mut Vec<LineMark>, spare: &mut Vec<Segment<C>>, segments: Vec<Segment<C>>, need_to_fix: bool) -> Vec<Segment<C>> {
         self.sort_and_filter_marks(marks, &segments);
         let min = segments[0].x_segment.a.x;
         let mut max = segments[0].x_segment.b.x;
@@ -126,9 +148,10 @@ impl SplitSolver {
             count: C::new(0, 0),
         };
 
-        let mut buffer = vec![empty; new_len];
+        let mut buffer = std::mem::take(spare);
+        buffer.clear();
+        buffer.resize(new_len, empty);
 
-        // let mut buffer = vec![empty; new_len];
         let slice = buffer.as_mut_slice();
 
         // split segments
@@ -192,6 +215,9 @@ impl SplitSolver {
 
         buffer.merge_if_needed();
 
+        // the old segments are the buffer of the next iteration
+        *spare = segments;
+
         buffer
     }
 
@@ -6,37 +6,38 @@ use crate::split::fragment::Fragment;
 use crate::split::grid_layout::{FragmentBuffer, GridLayout};
 use crate::split::line_mark::LineMark;
 use crate::split::snap_radius::SnapRadius;
-use crate::split::solver::SplitSolver;
+use crate::split::solver::{SplitBuffer, SplitSolver};
 
 impl SplitSolver {
-    pub(super) fn fragment_split<C: WindingCount>(&self, snap_radius: SnapRadius, mut segments: Vec<Segment<C>>) -> Vec<Segment<C>> {
+    pub(super) fn fragment_split<C: WindingCount>(&self, snap_radius: SnapRadius, mut segments: Vec<Segment<C>>, split_buffer: &mut SplitBuffer<C>) -> Vec<Segment<C>> {
         let layout = if let Some(layout) = GridLayout::new(segments.iter().map(|it| it.x_segment), segments.len()) {
             layout
         } else {
-            return self.tree_split(snap_radius, segments)
+            return self.tree_split(snap_radius, segments, split_buffer)
         };
 
         let mut buffer = FragmentBuffer::new(layout);
 
-        let mut marks = Vec::new();
+        let marks = &mut split_buffer.marks;
+        marks.clear();
         let mut need_to_fix = true;
 
         let mut snap_radius = snap_radius;
 
-        while need_to_fix && segments.len() > 2 {
+        while need_to_fix && segments.len() > 1 {
 
             buffer.init_fragment_buffer(segments.iter().map(|it| it.x_segment));
             for (i, segment) in segments.iter().enumerate() {
                 buffer.add_segment(i, segment.x_segment);
             }
 
-            need_to_fix = self.process(snap_radius.radius(), &mut buffer, &mut marks);
+            need_to_fix = self.process(snap_radius.radius(), &mut buffer, marks);
 
             if !buffer.on_border.is_empty() {
                 for (&index, segments) in buffer.on_border.iter_mut() {
                     if let Some(fragments) = buffer.groups.get(index) {
                         let border_x = buffer.layout.pos(index);
-                        Self::on_border_split(border_x, fragments, segments, &mut marks);
+                        Self::on_border_split(border_x, fragments, segments, marks);
                     }
                 }
             }
@@ -47,7 +48,7 @@ impl SplitSolver {
 
             buffer.clear();
 
-            segments = self.apply(&mut marks, segments, need_to_fix);
+            segments = self.apply(marks, &mut split_buffer.spare, segments, need_to_fix);
 
             marks.clear();
 
@@ -1,18 +1,17 @@
 use crate::segm::segment::Segment;
 use crate::segm::winding_count::WindingCount;
 use crate::split::snap_radius::SnapRadius;
-use crate::split::solver::SplitSolver;
+use crate::split::solver::{SplitBuffer, SplitSolver};
 
 impl SplitSolver {
-    pub(super) fn list_split<C: WindingCount>(&self, snap_radius: SnapRadius, mut segments: Vec<Segment<C>>) -> Vec<Segment<C>> {
-        let mut marks = Vec::new();
+    pub(super) fn list_split<C: WindingCount>(&self, snap_radius: SnapRadius, mut segments: Vec<Segment<C>>, buffer: &mut SplitBuffer<C>) -> Vec<Segment<C>> {
         let mut need_to_fix = true;
 
         let mut snap_radius = snap_radius;
 
-        while need_to_fix && segments.len() > 2 {
+        while need_to_fix && segments.len() > 1 {
             need_to_fix = false;
-            marks.clear();
+            buffer.marks.clear();
 
             let radius: i64 = snap_radius.radius();
 
@@ -29,22 +28,22 @@ impl SplitSolver {
                         continue;
                     }
 
-                    let is_round = SplitSolver::cross(i, j, ei, ej, &mut marks, radius);
+                    let is_round = SplitSolver::cross(i, j, ei, ej, &mut buffer.marks, radius);
                     need_to_fix = need_to_fix || is_round
                 }
             }
 
-            if marks.is_empty() {
+            if buffer.marks.is_empty() {
                 return segments;
             }
 
-            segments = self.apply(&mut marks, segments, need_to_fix);
+            segments = self.apply(&mut buffer.marks, &mut buffer.spare, segments, need_to_fix);
 
             snap_radius.increment();
 
             if need_to_fix && !self.solver.is_list_split(&segments) {
                 // finish with tree solver if edges is become large
-                return self.tree_split(snap_radius, segments);
+                return self.tree_split(snap_radius, segments, buffer);
             }
         }
 
@@ -4,35 +4,36 @@ use crate::segm::winding_count::WindingCount;
 use crate::split::fragment::Fragment;
 use crate::split::segment_tree::SegmentTree;
 use crate::split::snap_radius::SnapRadius;
-use crate::split::solver::SplitSolver;
+use crate::split::solver::{SplitBuffer, SplitSolver};
 use crate::split::space_layout::SpaceLayout;
 
 
 impl SplitSolver {
-    pub(super) fn tree_split<C: WindingCount>(&self, snap_radius: SnapRadius, mut segments: Vec<Segment<C>>) -> Vec<Segment<C>> {
+    pub(super) fn tree_split<C: WindingCount>(&self, snap_radius: SnapRadius, mut segments: Vec<Segment<C>>, buffer: &mut SplitBuffer<C>) -> Vec<Segment<C>> {
         let ver_range = segments.ver_range();
         let height = ver_range.width() as usize;
 
         if height < SpaceLayout::MIN_HEIGHT {
-            return self.list_split(snap_radius, segments);
+            return self.list_split(snap_radius, segments, buffer);
         }
 
         let layout = SpaceLayout::new(height, segments.len());
 
         let mut tree = SegmentTree::new(ver_range, layout.power, 0);
-        let mut marks = Vec::new();
+        let marks = &mut buffer.marks;
+        marks.clear();
         let mut need_to_fix = true;
 
         let mut snap_radius = snap_radius;
 
-        while need_to_fix && segments.len() > 2 {
+        while need_to_fix && segments.len() > 1 {
             need_to_fix = false;
 
             tree.radius = snap_radius.radius();
 
             for (i, e) in segments.iter().enumerate() {
                 let fragment = Fragment::with_index_and_segment(i, e.x_segment);
-                let any_round = tree.intersect(&fragment, &mut marks);
+                let any_round = tree.intersect(&fragment, marks);
                 need_to_fix = any_round || need_to_fix;
 
                 tree.insert(fragment);
@@ -44,7 +45,7 @@ impl SplitSolver {
 
             tree.clear();
 
-            segments = self.apply(&mut marks, segments, need_to_fix);
+            segments = self.apply(marks, &mut buffer.spare, segments, need_to_fix);
 
             marks.clear();
 
@@ -1,16 +1,17 @@
 use i_float::int::point::IntPoint;
 use i_shape::int::path::IntPath;
-use i_shape::int::shape::{IntShape, IntShapes};
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
 use crate::core::fill_rule::FillRule;
 use crate::core::link::OverlayLink;
 use crate::geom::id_point::IdPoint;
-use crate::segm::segment::SegmentFill;
+use crate::segm::segment::{SegmentFill, SUBJ_BOTH};
 use crate::segm::winding_count::{STRING_BACK_CLIP, STRING_FORWARD_CLIP};
 use crate::string::graph::StringGraph;
 use crate::string::line::IntLine;
 use crate::string::overlay::StringOverlay;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClipRule {
     /// Configuration for clipping lines with rules to determine inclusion or exclusion based on boundary and inversion.
     /// - `invert`: If true, inverts the clipping area selection, affecting which lines are included in the output.
@@ -58,6 +59,123 @@ impl StringGraph {
         paths
     }
 
+    /// Clips like `into_clip_string_lines`, but first takes out every closed contour which is kept as a whole ring.
+    /// - `contours`: The string contours added to the overlay.
+    pub(super) fn into_clip_string_paths(mut self, contours: &[IntContour]) -> Vec<ClipPath<IntPoint>> {
+        let mut paths = Vec::new();
+        let mut open_contours = Vec::new();
+        if !contours.is_empty() {
+            let node_ids = self.node_ids();
+            let mut ring_links = Vec::new();
+            for contour in contours.iter() {
+                if let Some(path) = self.find_ring(&node_ids, contour, &mut ring_links) {
+                    for &index in ring_links.iter() {
+                        self.links[index].fill = 0;
+                    }
+                    paths.push(ClipPath { path, is_closed: true });
+                } else {
+                    open_contours.push(contour);
+                }
+            }
+        }
+
+        let mut lines = self.into_clip_string_lines();
+        for contour in open_contours {
+            Self::join_ring_pieces(&mut lines, contour);
+        }
+
+        paths.extend(lines.into_iter().map(|path| ClipPath { path, is_closed: false }));
+        paths
+    }
+
+    /// The walk can start in the middle of a ring piece, so such a piece comes in two parts.
+    /// Joins every two pieces where one continues the other along the contour.
+    fn join_ring_pieces(lines: &mut Vec<IntPath>, contour: &[IntPoint]) {
+        let n = contour.len();
+        let mut i = 0;
+        while i < lines.len() {
+            let path = &lines[i];
+            if path.len() < 2 {
+                i += 1;
+                continue;
+            }
+            let v = path[path.len() - 1];
+            let next = lines
+                .iter()
+                .position(|other| other.len() > 1 && other[0] == v)
+                .filter(|&j| j != i);
+
+            let Some(j) = next else {
+                i += 1;
+                continue;
+            };
+
+            let Some(edge) = Self::ring_edge(contour, path[path.len() - 2], v) else {
+                i += 1;
+                continue;
+            };
+
+            let is_continued = match Self::ring_edge(contour, v, lines[j][1]) {
+                Some(next_edge) => next_edge == edge || next_edge == (edge + 1) % n && v == contour[next_edge],
+                None => false,
+            };
+
+            if !is_continued {
+                i += 1;
+                continue;
+            }
+
+            let tail = lines.remove(j);
+            if j < i {
+                i -= 1;
+            }
+            lines[i].extend_from_slice(&tail[1..]);
+        }
+    }
+
+    /// Returns the index of the contour edge which covers the step from `a` to `b` in its direction.
+    fn ring_edge(contour: &[IntPoint], a: IntPoint, b: IntPoint) -> Option<usize> {
+        let n = contour.len();
+        (0..n).find(|&i| {
+            let p0 = contour[i];
+            let p1 = contour[(i + 1) % n];
+            let e = p1 - p0;
+            let len = e.sqr_length();
+            let is_on_edge = |p: IntPoint| {
+                let v = p - p0;
+                let dot = v.dot_product(e);
+                e.cross_product(v) == 0 && 0 <= dot && dot <= len
+            };
+
+            is_on_edge(a) && is_on_edge(b) && (b - a).dot_product(e) > 0
+        })
+    }
+
+    /// Walks the contour along the remaining clip links.
+    /// Returns the points of the ring if every contour edge is covered by them, `ring_links` receives the walked links.
+    fn find_ring(&self, node_ids: &[(IntPoint, usize)], contour: &[IntPoint], ring_links: &mut Vec<usize>) -> Option<IntPath> {
+        ring_links.clear();
+        let mut a = *contour.first()?;
+        let index = node_ids.binary_search_by_key(&a, |&(point, _)| point).ok()?;
+        let mut node_id = node_ids[index].1;
+
+        let mut path = Vec::new();
+        for &b in contour.iter().skip(1).chain(contour.first()) {
+            let mut p = a;
+            while p != b {
+                let index = self.next_source_link(node_id, p, a, b)?;
+                let q = self.link(index).other(node_id);
+                ring_links.push(index);
+                path.push(p);
+                p = q.point;
+                node_id = q.id;
+            }
+            a = b;
+        }
+
+        if path.len() > 2 { Some(path) } else { None }
+    }
+
     #[inline]
     fn find_next_point(nodes: &[Vec<usize>], links: &mut [OverlayLink], a: IdPoint, is_out_node: bool) -> Option<IdPoint> {
         let node = unsafe { nodes.get_unchecked(a.id) };
@@ -74,9 +192,9 @@ impl StringGraph {
     }
 }
 
-const CLIP_BACK: SegmentFill = STRING_BACK_CLIP << 2;
-const CLIP_FORWARD: SegmentFill = STRING_FORWARD_CLIP << 2;
-const CLIP_ALL: SegmentFill = CLIP_BACK | CLIP_FORWARD;
+pub(super) const CLIP_BACK: SegmentFill = STRING_BACK_CLIP << 2;
+pub(super) const CLIP_FORWARD: SegmentFill = STRING_FORWARD_CLIP << 2;
+pub(super) const CLIP_ALL: SegmentFill = CLIP_BACK | CLIP_FORWARD;
 
 impl OverlayLink {
     #[inline]
@@ -108,6 +226,221 @@ impl OverlayLink {
     }
 }
 
+/// A clipped path.
+/// - `path`: The points of the path, a closed path does not repeat its first point at the end.
+/// - `is_closed`: `true` if the path is a contour added with `add_string_contour` which lies fully in the result, such a ring can be filled.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClipPath<P> {
+    pub path: Vec<P>,
+    pub is_closed: bool,
+}
+
+/// A position on a source path.
+/// - `segment`: The index of the path segment, the segment `i` goes from the point `i` to the point `i + 1`.
+/// - `fraction`: The relative position along the segment, from 0.0 at its start to 1.0 at its end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinePosition {
+    pub segment: usize,
+    pub fraction: f64,
+}
+
+/// A clipped piece of a source path.
+/// - `path`: The points of the piece in the direction of the source path.
+/// - `source`: The index of the source path in the input.
+/// - `start`: The position of the first point on the source path.
+/// - `end`: The position of the last point on the source path.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClipPiece<P> {
+    pub path: Vec<P>,
+    pub source: usize,
+    pub start: LinePosition,
+    pub end: LinePosition,
+}
+
+/// The location of a path piece relative to the filled area of the clipping shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PieceLocation {
+    Inside,
+    Outside,
+    OnBoundary,
+}
+
+/// A piece of a source path with its location.
+/// - `location`: The location of the piece relative to the clipping shapes.
+/// - `path`, `source`, `start`, `end`: The same as in `ClipPiece`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassifiedPiece<P> {
+    pub location: PieceLocation,
+    pub path: Vec<P>,
+    pub source: usize,
+    pub start: LinePosition,
+    pub end: LinePosition,
+}
+
+impl PieceLocation {
+    #[inline]
+    fn with_fill(fill: SegmentFill) -> Self {
+        match (fill & SUBJ_BOTH).count_ones() {
+            0 => Self::Outside,
+            1 => Self::OnBoundary,
+            _ => Self::Inside,
+        }
+    }
+}
+
+impl ClipRule {
+    #[inline]
+    fn is_included(&self, location: PieceLocation) -> bool {
+        match location {
+            PieceLocation::Outside => self.invert,
+            PieceLocation::OnBoundary => self.boundary_included,
+            PieceLocation::Inside => !self.invert,
+        }
+    }
+}
+
+impl StringGraph {
+    /// Clips the source paths, the graph must be built from all links of the shapes and exactly these paths.
+    pub(crate) fn clip_sources<'a, I>(&self, paths: I, clip_rule: ClipRule) -> Vec<ClipPiece<IntPoint>>
+    where
+        I: Iterator<Item = &'a [IntPoint]>,
+    {
+        self.split_sources(paths, |location| clip_rule.is_included(location).then_some(()))
+            .into_iter()
+            .map(|(_, piece)| piece)
+            .collect()
+    }
+
+    /// Splits the source paths into pieces with the same location, the graph must be built from all links of the shapes and exactly these paths.
+    pub(crate) fn classify_sources<'a, I>(&self, paths: I) -> Vec<ClassifiedPiece<IntPoint>>
+    where
+        I: Iterator<Item = &'a [IntPoint]>,
+    {
+        self.split_sources(paths, Some)
+            .into_iter()
+            .map(|(location, piece)| ClassifiedPiece {
+                location,
+                path: piece.path,
+                source: piece.source,
+                start: piece.start,
+                end: piece.end,
+            })
+            .collect()
+    }
+
+    /// Walks every source segment along the graph links and joins the consecutive links with the same key into pieces.
+    /// Links without a key break the pieces.
+    fn split_sources<'a, I, K, F>(&self, paths: I, key: F) -> Vec<(K, ClipPiece<IntPoint>)>
+    where
+        I: Iterator<Item = &'a [IntPoint]>,
+        K: Copy + PartialEq,
+        F: Fn(PieceLocation) -> Option<K>,
+    {
+        let node_ids = self.node_ids();
+
+        let mut pieces = Vec::new();
+        for (source, path) in paths.enumerate() {
+            let mut piece: Option<(K, ClipPiece<IntPoint>)> = None;
+            for (segment, w) in path.windows(2).enumerate() {
+                let (a, b) = (w[0], w[1]);
+                if a == b {
+                    continue;
+                }
+
+                let mut p = a;
+                if let Ok(index) = node_ids.binary_search_by_key(&a, |&(point, _)| point) {
+                    let mut node_id = node_ids[index].1;
+                    while p != b {
+                        let Some(index) = self.next_source_link(node_id, p, a, b) else {
+                            break;
+                        };
+                        let link = self.link(index);
+                        let q = link.other(node_id);
+                        let link_key = key(PieceLocation::with_fill(link.fill));
+                        if piece.as_ref().map(|(k, _)| Some(*k)) != Some(link_key) {
+                            pieces.extend(piece.take());
+                        }
+                        if let Some(link_key) = link_key {
+                            let start = LinePosition::new(segment, a, b, p);
+                            let (_, current) = piece.get_or_insert_with(|| (link_key, ClipPiece { path: vec![p], source, start, end: start }));
+                            current.path.push(q.point);
+                            current.end = LinePosition::new(segment, a, b, q.point);
+                        }
+                        p = q.point;
+                        node_id = q.id;
+                    }
+                }
+
+                if p != b {
+                    // the segment is not found in the graph
+                    pieces.extend(piece.take());
+                }
+            }
+
+            pieces.extend(piece.take());
+        }
+
+        pieces
+    }
+
+    /// Returns the node id of every point, sorted by point.
+    fn node_ids(&self) -> Vec<(IntPoint, usize)> {
+        let mut node_ids: Vec<(IntPoint, usize)> = Vec::with_capacity(2 * self.links.len());
+        for link in self.links.iter() {
+            node_ids.push((link.a.point, link.a.id));
+            node_ids.push((link.b.point, link.b.id));
+        }
+        node_ids.sort_unstable_by_key(|&(point, _)| point);
+        node_ids.dedup_by_key(|&mut (point, _)| point);
+        node_ids
+    }
+
+    /// Finds the index of a string link that continues the segment `a` -> `b` from the point `p`.
+    /// Split points can be rounded, so the link closest to the segment line is taken.
+    #[inline]
+    fn next_source_link(&self, node_id: usize, p: IntPoint, a: IntPoint, b: IntPoint) -> Option<usize> {
+        let ab = b - a;
+        let len = ab.sqr_length() as f64;
+        let t_p = (p - a).dot_product(ab) as f64;
+
+        let mut result = None;
+        let mut best = (f64::MAX, f64::MAX);
+        for &index in self.node(node_id).iter() {
+            let link = self.link(index);
+            if link.fill & CLIP_ALL == 0 {
+                continue;
+            }
+            let q = link.other(node_id).point;
+            let aq = q - a;
+            let t_q = aq.dot_product(ab) as f64;
+            if t_q <= t_p || t_q > len {
+                continue;
+            }
+            let distance = (aq.cross_product(ab) as f64).abs();
+            if (distance, t_q) < best {
+                best = (distance, t_q);
+                result = Some(index);
+            }
+        }
+
+        result
+    }
+}
+
+impl LinePosition {
+    #[inline]
+    fn new(segment: usize, a: IntPoint, b: IntPoint, p: IntPoint) -> Self {
+        let ab = b - a;
+        let fraction = (p - a).dot_product(ab) as f64 / ab.sqr_length() as f64;
+        Self { segment, fraction: fraction.clamp(0.0, 1.0) }
+    }
+}
+
 pub trait IntClip {
     /// Clips a single line according to the specified fill and clip rules.
     /// - `line`: The line to be clipped, represented by two points.
@@ -144,6 +477,23 @@ pub trait IntClip {
     /// # Returns
     /// A vector of `IntPath` instances containing the clipped portions of the input paths.
     fn clip_paths(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<IntPath>;
+
+    /// Clips multiple paths and tags every piece with the path it was cut from.
+    /// - `paths`: A slice of `IntPath` instances, each representing a path to be clipped.
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of path segments.
+    /// - `clip_rule`: The rule for clipping, determining how boundary and inversion settings affect the result.
+    ///
+    /// # Returns
+    /// A vector of `ClipPiece` instances ordered by the source path and by the position along it.
+    fn clip_paths_with_source(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPiece<IntPoint>>;
+
+    /// Splits multiple paths at the boundaries of the filled areas and classifies every piece.
+    /// - `paths`: A slice of `IntPath` instances, each representing a path to be split.
+    /// - `fill_rule`: Specifies the rule determining the filled areas.
+    ///
+    /// # Returns
+    /// A vector of `ClassifiedPiece` instances, which covers the input paths in the path order.
+    fn classify_paths(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<ClassifiedPiece<IntPoint>>;
 }
 
 impl IntClip for IntShapes {
@@ -174,6 +524,20 @@ impl IntClip for IntShapes {
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn clip_paths_with_source(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPiece<IntPoint>> {
+        let mut overlay = StringOverlay::with_shapes(self);
+        overlay.add_string_paths(paths);
+        overlay.into_graph(fill_rule).clip_sources(paths.iter().map(|path| path.as_slice()), clip_rule)
+    }
+
+    #[inline]
+    fn classify_paths(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<ClassifiedPiece<IntPoint>> {
+        let mut overlay = StringOverlay::with_shapes(self);
+        overlay.add_string_paths(paths);
+        overlay.into_graph(fill_rule).classify_sources(paths.iter().map(|path| path.as_slice()))
+    }
 }
 
 impl IntClip for IntShape {
@@ -204,6 +568,20 @@ impl IntClip for IntShape {
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn clip_paths_with_source(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPiece<IntPoint>> {
+        let mut overlay = StringOverlay::with_shape(self);
+        overlay.add_string_paths(paths);
+        overlay.into_graph(fill_rule).clip_sources(paths.iter().map(|path| path.as_slice()), clip_rule)
+    }
+
+    #[inline]
+    fn classify_paths(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<ClassifiedPiece<IntPoint>> {
+        let mut overlay = StringOverlay::with_shape(self);
+        overlay.add_string_paths(paths);
+        overlay.into_graph(fill_rule).classify_sources(paths.iter().map(|path| path.as_slice()))
+    }
 }
 
 impl IntClip for [IntPoint] {
@@ -234,6 +612,20 @@ impl IntClip for [IntPoint] {
         overlay.add_string_paths(paths);
         overlay.clip_string_lines(fill_rule, clip_rule)
     }
+
+    #[inline]
+    fn clip_paths_with_source(&self, paths: &[IntPath], fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPiece<IntPoint>> {
+        let mut overlay = StringOverlay::with_shape_contour(self);
+        overlay.add_string_paths(paths);
+        overlay.into_graph(fill_rule).clip_sources(paths.iter().map(|path| path.as_slice()), clip_rule)
+    }
+
+    #[inline]
+    fn classify_paths(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<ClassifiedPiece<IntPoint>> {
+        let mut overlay = StringOverlay::with_shape_contour(self);
+        overlay.add_string_paths(paths);
+        overlay.into_graph(fill_rule).classify_sources(paths.iter().map(|path| path.as_slice()))
+    }
 }
 
 
@@ -242,7 +634,8 @@ mod tests {
     use i_float::int::point::IntPoint;
     use i_shape::int::path::IntPath;
     use crate::core::fill_rule::FillRule;
-    use crate::string::clip::{ClipRule, IntClip};
+    use crate::string::clip::{ClipPath, ClipRule, IntClip, LinePosition, PieceLocation};
+    use crate::string::overlay::StringOverlay;
 
     #[test]
     fn test_empty_path() {
@@ -346,4 +739,207 @@ mod tests {
         assert_eq!(result_0.len(), 3);
         assert_eq!(result_1.len(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_clip_with_source() {
+        let rect = [
+            IntPoint::new(-10, -10),
+            IntPoint::new(-10, 10),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, -10),
+        ];
+
+        let paths = vec![
+            vec![IntPoint::new(-20, 0), IntPoint::new(20, 0)],
+            vec![IntPoint::new(-20, 5), IntPoint::new(0, 5), IntPoint::new(0, 25)],
+        ];
+
+        let result = rect.clip_paths_with_source(&paths, FillRule::NonZero,
+                                                 ClipRule { invert: false, boundary_included: false },
+        );
+
+        assert_eq!(result.len(), 2);
+
+        assert_eq!(result[0].source, 0);
+        assert_eq!(result[0].path, [IntPoint::new(-10, 0), IntPoint::new(10, 0)]);
+        assert_eq!(result[0].start, LinePosition { segment: 0, fraction: 0.25 });
+        assert_eq!(result[0].end, LinePosition { segment: 0, fraction: 0.75 });
+
+        assert_eq!(result[1].source, 1);
+        assert_eq!(result[1].path, [IntPoint::new(-10, 5), IntPoint::new(0, 5), IntPoint::new(0, 10)]);
+        assert_eq!(result[1].start, LinePosition { segment: 0, fraction: 0.5 });
+        assert_eq!(result[1].end, LinePosition { segment: 1, fraction: 0.25 });
+    }
+
+    #[test]
+    fn test_clip_with_source_invert() {
+        let rect = [
+            IntPoint::new(-10, -10),
+            IntPoint::new(-10, 10),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, -10),
+        ];
+
+        let paths = vec![
+            vec![IntPoint::new(20, 0), IntPoint::new(-20, 0)],
+            vec![IntPoint::new(-10, -20), IntPoint::new(-10, 20)],
+        ];
+
+        let outside = rect.clip_paths_with_source(&paths, FillRule::NonZero,
+                                                  ClipRule { invert: true, boundary_included: false },
+        );
+
+        assert_eq!(outside.len(), 4);
+        assert_eq!(outside[0].path, [IntPoint::new(20, 0), IntPoint::new(10, 0)]);
+        assert_eq!(outside[1].path, [IntPoint::new(-10, 0), IntPoint::new(-20, 0)]);
+        assert_eq!(outside[1].end, LinePosition { segment: 0, fraction: 1.0 });
+        assert!(outside[2..].iter().all(|piece| piece.source == 1));
+
+        let with_boundary = rect.clip_paths_with_source(&paths, FillRule::NonZero,
+                                                        ClipRule { invert: true, boundary_included: true },
+        );
+
+        assert_eq!(with_boundary.len(), 3);
+        assert_eq!(with_boundary[2].path, [IntPoint::new(-10, -20), IntPoint::new(-10, -10), IntPoint::new(-10, 0), IntPoint::new(-10, 10), IntPoint::new(-10, 20)]);
+    }
+
+    #[test]
+    fn test_classify_paths() {
+        let rect = [
+            IntPoint::new(-10, -10),
+            IntPoint::new(-10, 10),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, -10),
+        ];
+
+        let paths = vec![
+            vec![IntPoint::new(-20, 0), IntPoint::new(20, 0)],
+            vec![IntPoint::new(-20, 10), IntPoint::new(0, 10), IntPoint::new(0, 0)],
+        ];
+
+        let result = rect.classify_paths(&paths, FillRule::NonZero);
+        let locations: Vec<_> = result.iter().map(|piece| (piece.source, piece.location)).collect();
+
+        assert_eq!(locations, [
+            (0, PieceLocation::Outside),
+            (0, PieceLocation::Inside),
+            (0, PieceLocation::Outside),
+            (1, PieceLocation::Outside),
+            (1, PieceLocation::OnBoundary),
+            (1, PieceLocation::Inside),
+        ]);
+
+        assert_eq!(result[4].path, [IntPoint::new(-10, 10), IntPoint::new(0, 10)]);
+        assert_eq!(result[4].end, LinePosition { segment: 0, fraction: 1.0 });
+        assert_eq!(result[5].path, [IntPoint::new(0, 10), IntPoint::new(0, 0)]);
+        assert_eq!(result[5].start, LinePosition { segment: 1, fraction: 0.0 });
+    }
+
+    fn square(radius: i32) -> [IntPoint; 4] {
+        [
+            IntPoint::new(-radius, -radius),
+            IntPoint::new(-radius, radius),
+            IntPoint::new(radius, radius),
+            IntPoint::new(radius, -radius),
+        ]
+    }
+
+    #[test]
+    fn test_clip_keep_closed() {
+        let inner = square(5);
+        let crossing = [
+            IntPoint::new(7, -5),
+            IntPoint::new(7, 5),
+            IntPoint::new(15, 5),
+            IntPoint::new(15, -5),
+        ];
+
+        let mut overlay = StringOverlay::with_shape_contour(&square(10));
+        overlay.add_string_contour(&inner);
+        overlay.add_string_contour(&crossing);
+        overlay.add_string_path(&[IntPoint::new(-8, -8), IntPoint::new(-8, 8)]);
+
+        let result = overlay.clip_string_lines_keep_closed(FillRule::NonZero, ClipRule { invert: false, boundary_included: false });
+
+        let closed: Vec<_> = result.iter().filter(|path| path.is_closed).collect();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].path, inner);
+        assert!(result.iter().any(|path| !path.is_closed && path.path == [IntPoint::new(-8, -8), IntPoint::new(-8, 8)]));
+        assert!(result.iter().filter(|path| !path.is_closed).all(|path| path.path.iter().all(|p| !inner.contains(p))));
+
+        // the part of the crossing ring inside the shape is one open path
+        let crossing_pieces: Vec<_> = result.iter().filter(|path| path.path.iter().any(|p| p.x >= 7)).collect();
+        assert_eq!(crossing_pieces.len(), 1);
+        assert!(!crossing_pieces[0].is_closed);
+        assert_eq!(crossing_pieces[0].path, [
+            IntPoint::new(10, -5),
+            IntPoint::new(7, -5),
+            IntPoint::new(7, 5),
+            IntPoint::new(10, 5),
+        ]);
+    }
+
+    #[test]
+    fn test_clip_keep_closed_ring_out_of_shape() {
+        let ring = [IntPoint::new(8, 2), IntPoint::new(8, 4), IntPoint::new(12, 4), IntPoint::new(12, 2)];
+        let rect = [IntPoint::new(0, 0), IntPoint::new(0, 10), IntPoint::new(10, 10), IntPoint::new(10, 0)];
+
+        // the piece must be whole wherever the ring starts
+        for start in 0..ring.len() {
+            let mut contour = ring.to_vec();
+            contour.rotate_left(start);
+
+            let mut overlay = StringOverlay::with_shape_contour(&rect);
+            overlay.add_string_contour(&contour);
+
+            let result = overlay.clip_string_lines_keep_closed(FillRule::NonZero, ClipRule { invert: false, boundary_included: false });
+
+            assert_eq!(result, [ClipPath {
+                path: vec![IntPoint::new(10, 2), IntPoint::new(8, 2), IntPoint::new(8, 4), IntPoint::new(10, 4)],
+                is_closed: false,
+            }]);
+        }
+    }
+
+    #[test]
+    fn test_clip_keep_closed_crossed_ring() {
+        let inner = square(5);
+
+        let mut overlay = StringOverlay::with_shape_contour(&square(10));
+        overlay.add_string_contour(&inner);
+        overlay.add_string_path(&[IntPoint::new(0, -8), IntPoint::new(0, 8)]);
+
+        let result = overlay.clip_string_lines_keep_closed(FillRule::NonZero, ClipRule { invert: false, boundary_included: false });
+
+        let closed: Vec<_> = result.iter().filter(|path| path.is_closed).collect();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].path, [
+            IntPoint::new(-5, -5),
+            IntPoint::new(-5, 5),
+            IntPoint::new(0, 5),
+            IntPoint::new(5, 5),
+            IntPoint::new(5, -5),
+            IntPoint::new(0, -5),
+        ]);
+
+        let open_len: i64 = result
+            .iter()
+            .filter(|path| !path.is_closed)
+            .flat_map(|path| path.path.windows(2))
+            .map(|w| (w[1] - w[0]).sqr_length().isqrt())
+            .sum();
+        assert_eq!(open_len, 16);
+    }
+
+    #[test]
+    fn test_clip_keep_closed_joined_paths() {
+        let mut overlay = StringOverlay::with_shape_contour(&square(10));
+        overlay.add_string_path(&[IntPoint::new(-5, -5), IntPoint::new(-5, 5), IntPoint::new(5, 5)]);
+        overlay.add_string_path(&[IntPoint::new(5, 5), IntPoint::new(5, -5), IntPoint::new(-5, -5)]);
+
+        let result = overlay.clip_string_lines_keep_closed(FillRule::NonZero, ClipRule { invert: false, boundary_included: false });
+
+        assert!(!result.is_empty());
+        assert!(result.iter().all(|path| !path.is_closed));
+    }
+}
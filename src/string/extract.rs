@@ -6,7 +6,9 @@ use crate::bind::solver::JoinHoles;
 use crate::core::extract::StartPathData;
 use crate::string::rule::StringRule;
 use crate::string::split::Split;
+use crate::string::clip::{CLIP_ALL, CLIP_FORWARD};
 use crate::string::graph::StringGraph;
+use crate::string::overlay::StringOverlay;
 
 impl StringGraph {
     /// Extracts shapes from the graph based on the specified `StringRule`.
@@ -51,7 +53,12 @@ impl StringGraph {
             if visited.count(left_top_link) == 1 {
                 let is_hole = string_rule.is_hole(link.fill);
                 let start_data = StartPathData::new(is_hole, link, left_top_link);
-                let paths = self.get_path(&start_data, visited).split_loops(min_area);
+                let path = self.get_path(&start_data, visited);
+                let paths = if string_rule == StringRule::CutOpen {
+                    path.cut_open(min_area)
+                } else {
+                    path.split_loops(min_area)
+                };
                 if is_hole {
                     shapes.join_unsorted_holes(&self.solver, paths);
                 } else {
@@ -88,8 +95,34 @@ impl StringGraph {
         shapes
     }
 
+    /// Extracts the string lines kept by the specified `StringRule`.
+    /// - `string_rule`: The rule used to determine which lines are kept.
+    ///   Only `StringRule::SliceKeepLines` keeps lines, it returns the cut pieces inside the shapes.
+    ///
+    /// # Returns
+    /// A vector of `IntPath` polylines in the direction of the source lines, they are joined up to the nodes where the lines cross each other or the boundaries.
+    pub fn extract_lines(&self, string_rule: StringRule) -> Vec<IntPath> {
+        if string_rule != StringRule::SliceKeepLines {
+            return Vec::new();
+        }
+
+        let mut overlay = StringOverlay::new(0);
+        for link in self.links.iter() {
+            if !Self::is_slice_line(link.fill) {
+                continue;
+            }
+            if link.fill & CLIP_ALL == CLIP_FORWARD {
+                overlay.add_string_line([link.b.point, link.a.point]);
+            } else {
+                overlay.add_string_line([link.a.point, link.b.point]);
+            }
+        }
+
+        overlay.merge_string_lines_with_solver(true, self.solver)
+    }
+
     #[inline]
-    fn get_path(&self, start_data: &StartPathData, visited: &mut [u8]) -> IntPath {
+    pub(super) fn get_path(&self, start_data: &StartPathData, visited: &mut [u8]) -> IntPath {
         let mut link_id = start_data.link_id;
         let mut node_id = start_data.node_id;
         let last_node_id = start_data.last_node_id;
@@ -234,7 +267,7 @@ impl StringGraph {
     }
 }
 
-trait Visit {
+pub(super) trait Visit {
     fn count(&self, index: usize) -> u8;
     fn is_visited(&self, index: usize) -> bool;
     fn is_not_visited(&self, index: usize) -> bool;
@@ -1,14 +1,17 @@
 use crate::string::rule::StringRule;
 use crate::string::graph::StringGraph;
-use crate::segm::segment::{CLIP_BOTH, SUBJ_BOTH, SUBJ_BOTTOM, SUBJ_TOP};
+use crate::segm::segment::{SegmentFill, CLIP_BOTH, SUBJ_BOTH, SUBJ_BOTTOM, SUBJ_TOP};
 
 impl StringGraph {
     #[inline(always)]
     pub(super) fn filter(&self, ext_rule: StringRule) -> Vec<u8> {
         match ext_rule {
-            StringRule::Slice => {
+            StringRule::Slice | StringRule::SliceKeepLines | StringRule::CutOpen => {
                 self.filter_slice()
             }
+            StringRule::Imprint => {
+                self.filter_imprint()
+            }
         }
     }
 
@@ -21,7 +24,7 @@ impl StringGraph {
 
             if one_side_subj {
                 1
-            } else if fill & CLIP_BOTH != 0 && subj == SUBJ_BOTH {
+            } else if Self::is_slice_line(fill) {
                 // ony edges inside subj
                 // slice edge, we must visit it twice
                 2
@@ -30,4 +33,17 @@ impl StringGraph {
             }
         }).collect()
     }
-}
\ No newline at end of file
+
+    #[inline]
+    fn filter_imprint(&self) -> Vec<u8> {
+        self.links.iter().map(|link| {
+            let subj = link.fill & SUBJ_BOTH;
+            if subj == SUBJ_TOP || subj == SUBJ_BOTTOM { 1 } else { 0 }
+        }).collect()
+    }
+
+    #[inline]
+    pub(super) fn is_slice_line(fill: SegmentFill) -> bool {
+        fill & CLIP_BOTH != 0 && fill & SUBJ_BOTH == SUBJ_BOTH
+    }
+}
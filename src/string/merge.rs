@@ -0,0 +1,201 @@
+use i_float::int::point::IntPoint;
+use i_shape::int::count::PointsCount;
+use i_shape::int::path::IntPath;
+use crate::core::link::OverlayLink;
+use crate::core::solver::Solver;
+use crate::string::clip::{CLIP_ALL, CLIP_BACK, CLIP_FORWARD};
+use crate::string::graph::StringGraph;
+use crate::string::overlay::StringOverlay;
+
+impl StringGraph {
+    pub(super) fn into_merged_lines(self, respect_direction: bool) -> Vec<IntPath> {
+        let mut visited: Vec<bool> = self.links.iter().map(|link| link.fill & CLIP_ALL == 0).collect();
+        let mut paths = Vec::new();
+
+        // polylines between the end nodes
+        for node_id in 0..self.nodes.len() {
+            if self.is_through_node(node_id, respect_direction) {
+                continue;
+            }
+            for &link_index in self.node(node_id).iter() {
+                if visited[link_index] || !self.link(link_index).is_leaving(node_id, respect_direction) {
+                    continue;
+                }
+                paths.push(self.merge_path(node_id, link_index, respect_direction, &mut visited));
+            }
+        }
+
+        // the rest links form closed loops
+        for link_index in 0..self.links.len() {
+            if visited[link_index] {
+                continue;
+            }
+            let link = self.link(link_index);
+            let node_id = if link.is_leaving(link.a.id, respect_direction) { link.a.id } else { link.b.id };
+            paths.push(self.merge_path(node_id, link_index, respect_direction, &mut visited));
+        }
+
+        paths
+    }
+
+    fn merge_path(&self, start_id: usize, start_link: usize, respect_direction: bool, visited: &mut [bool]) -> IntPath {
+        let mut path = vec![self.node_point(start_id, start_link)];
+        let mut node_id = start_id;
+        let mut link_index = start_link;
+        loop {
+            visited[link_index] = true;
+            let other = self.link(link_index).other(node_id);
+            path.push(other.point);
+            node_id = other.id;
+
+            if node_id == start_id || !self.is_through_node(node_id, respect_direction) {
+                break;
+            }
+
+            let node = self.node(node_id);
+            let next_index = if node[0] == link_index { node[1] } else { node[0] };
+            if visited[next_index] {
+                break;
+            }
+            link_index = next_index;
+        }
+
+        path
+    }
+
+    #[inline]
+    pub(super) fn node_point(&self, node_id: usize, link_index: usize) -> IntPoint {
+        let link = self.link(link_index);
+        if link.a.id == node_id { link.a.point } else { link.b.point }
+    }
+
+    #[inline]
+    fn is_through_node(&self, node_id: usize, respect_direction: bool) -> bool {
+        let node = self.node(node_id);
+        if node.len() != 2 {
+            return false;
+        }
+        if !respect_direction {
+            return true;
+        }
+        let l0 = self.link(node[0]);
+        let l1 = self.link(node[1]);
+        l0.is_leaving(node_id, true) && l1.is_entering(node_id) || l0.is_entering(node_id) && l1.is_leaving(node_id, true)
+    }
+}
+
+impl OverlayLink {
+    /// A line from the lower point to the upper one has the back bit, the opposite line has the forward bit.
+    /// A link covered by lines in both directions is treated as directed from `a` to `b`.
+    #[inline]
+    fn is_leaving(&self, node_id: usize, respect_direction: bool) -> bool {
+        if !respect_direction {
+            return true;
+        }
+        let fill = self.fill & CLIP_ALL;
+        if fill == CLIP_FORWARD { self.b.id == node_id } else { self.a.id == node_id }
+    }
+
+    #[inline]
+    fn is_entering(&self, node_id: usize) -> bool {
+        match self.fill & CLIP_ALL {
+            CLIP_BACK => self.b.id == node_id,
+            CLIP_FORWARD => self.a.id == node_id,
+            _ => false,
+        }
+    }
+}
+
+/// The `IntMerge` trait joins line pieces that share their endpoints into maximal polylines.
+pub trait IntMerge {
+    /// Joins the lines into maximal polylines, which stop at nodes that are not shared by exactly two segments.
+    /// - `respect_direction`: If true, a polyline also stops where the directions of the lines do not follow each other.
+    ///
+    /// # Returns
+    /// A vector of `IntPath` polylines, a closed loop is returned with the same first and last point.
+    fn merge_lines(&self, respect_direction: bool) -> Vec<IntPath>;
+
+    /// Joins the lines into maximal polylines, which stop at nodes that are not shared by exactly two segments.
+    /// - `respect_direction`: If true, a polyline also stops where the directions of the lines do not follow each other.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A vector of `IntPath` polylines, a closed loop is returned with the same first and last point.
+    fn merge_lines_with_solver(&self, respect_direction: bool, solver: Solver) -> Vec<IntPath>;
+}
+
+impl IntMerge for [IntPath] {
+    #[inline]
+    fn merge_lines(&self, respect_direction: bool) -> Vec<IntPath> {
+        self.merge_lines_with_solver(respect_direction, Default::default())
+    }
+
+    #[inline]
+    fn merge_lines_with_solver(&self, respect_direction: bool, solver: Solver) -> Vec<IntPath> {
+        let mut overlay = StringOverlay::new(self.points_count());
+        overlay.add_string_paths(self);
+        overlay.merge_string_lines_with_solver(respect_direction, solver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_shape::int::path::IntPath;
+    use crate::string::merge::IntMerge;
+    use crate::string::node::IntNode;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    #[test]
+    fn test_chain() {
+        let paths = [
+            path(&[[10, 0], [20, 0]]),
+            path(&[[0, 0], [10, 0]]),
+            path(&[[20, 0], [20, 10]]),
+        ];
+
+        let result = paths.merge_lines(true);
+
+        assert_eq!(result, [path(&[[0, 0], [10, 0], [20, 0], [20, 10]])]);
+    }
+
+    #[test]
+    fn test_direction() {
+        let paths = [
+            path(&[[0, 0], [10, 0]]),
+            path(&[[20, 0], [10, 0]]),
+        ];
+
+        assert_eq!(paths.merge_lines(false).len(), 1);
+        assert_eq!(paths.merge_lines(true).len(), 2);
+    }
+
+    #[test]
+    fn test_cross() {
+        let paths = [
+            path(&[[-10, 0], [10, 0]]),
+            path(&[[0, -10], [0, 10]]),
+        ];
+
+        let result = paths.node_lines().merge_lines(true);
+
+        assert_eq!(result.len(), 4);
+    }
+
+    #[test]
+    fn test_loop() {
+        let paths = [
+            path(&[[0, 0], [0, 10], [10, 10]]),
+            path(&[[10, 10], [10, 0], [0, 0]]),
+        ];
+
+        let result = paths.merge_lines(true);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 5);
+        assert_eq!(result[0].first(), result[0].last());
+    }
+}
@@ -6,4 +6,7 @@ pub mod graph;
 pub mod split;
 pub mod clip;
 pub mod extract;
+pub mod node;
+pub mod merge;
+pub mod polygonize;
 mod filter;
\ No newline at end of file
@@ -0,0 +1,112 @@
+use i_float::int::point::IntPoint;
+use i_shape::int::count::PointsCount;
+use i_shape::int::path::IntPath;
+use crate::core::solver::Solver;
+use crate::string::overlay::StringOverlay;
+
+/// The `IntNode` trait splits a line network at all intersections and overlaps.
+pub trait IntNode {
+    /// Splits the lines at all intersections and overlaps.
+    ///
+    /// # Returns
+    /// A vector of two point `IntPath` segments, which share their endpoints at the nodes.
+    fn node_lines(&self) -> Vec<IntPath>;
+
+    /// Splits the lines at all intersections and overlaps.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A vector of two point `IntPath` segments, which share their endpoints at the nodes.
+    fn node_lines_with_solver(&self, solver: Solver) -> Vec<IntPath>;
+}
+
+impl IntNode for [IntPath] {
+    #[inline]
+    fn node_lines(&self) -> Vec<IntPath> {
+        self.node_lines_with_solver(Default::default())
+    }
+
+    #[inline]
+    fn node_lines_with_solver(&self, solver: Solver) -> Vec<IntPath> {
+        let mut overlay = StringOverlay::new(self.points_count());
+        overlay.add_string_paths(self);
+        overlay.node_string_lines_with_solver(solver)
+    }
+}
+
+impl IntNode for [IntPoint] {
+    #[inline]
+    fn node_lines(&self) -> Vec<IntPath> {
+        self.node_lines_with_solver(Default::default())
+    }
+
+    #[inline]
+    fn node_lines_with_solver(&self, solver: Solver) -> Vec<IntPath> {
+        let mut overlay = StringOverlay::new(self.len());
+        overlay.add_string_path(self);
+        overlay.node_string_lines_with_solver(solver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use crate::core::solver::Solver;
+    use crate::string::node::IntNode;
+
+    #[test]
+    fn test_cross() {
+        let paths = [
+            vec![IntPoint::new(-10, 0), IntPoint::new(10, 0)],
+            vec![IntPoint::new(0, 10), IntPoint::new(0, -10)],
+        ];
+
+        let result = paths.node_lines();
+        assert_eq!(result.len(), 4);
+        assert!(result.iter().all(|segment| segment.contains(&IntPoint::new(0, 0))));
+        assert!(result.contains(&vec![IntPoint::new(0, 10), IntPoint::new(0, 0)]));
+    }
+
+    #[test]
+    fn test_cross_every_strategy() {
+        let paths = [
+            vec![IntPoint::new(-10, 0), IntPoint::new(10, 0)],
+            vec![IntPoint::new(0, 10), IntPoint::new(0, -10)],
+        ];
+
+        for solver in [Solver::LIST, Solver::TREE, Solver::FRAG, Solver::AUTO] {
+            let result = paths.node_lines_with_solver(solver);
+            assert_eq!(result.len(), 4, "{:?}", solver.strategy);
+            assert!(result.iter().all(|segment| segment.contains(&IntPoint::new(0, 0))));
+        }
+    }
+
+    #[test]
+    fn test_overlap() {
+        let paths = [
+            vec![IntPoint::new(0, 0), IntPoint::new(20, 0)],
+            vec![IntPoint::new(30, 0), IntPoint::new(10, 0)],
+        ];
+
+        let result = paths.node_lines();
+
+        assert_eq!(result.len(), 3);
+        assert!(result.contains(&vec![IntPoint::new(0, 0), IntPoint::new(10, 0)]));
+        assert!(result.contains(&vec![IntPoint::new(10, 0), IntPoint::new(20, 0)]));
+        assert!(result.contains(&vec![IntPoint::new(30, 0), IntPoint::new(20, 0)]));
+    }
+
+    #[test]
+    fn test_self_intersection() {
+        let path = [
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+        ];
+
+        let result = path.node_lines();
+
+        assert_eq!(result.len(), 5);
+    }
+}
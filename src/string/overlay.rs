@@ -6,17 +6,25 @@ use crate::core::fill_rule::FillRule;
 use crate::core::link::OverlayLinkBuilder;
 use crate::core::overlay::ShapeType;
 use crate::core::solver::Solver;
+#[cfg(feature = "record")]
+use crate::record::Recorder;
 use crate::geom::x_segment::XSegment;
 use crate::segm::build::BuildSegments;
 use crate::segm::segment::Segment;
 use crate::segm::winding_count::{ShapeCountString, STRING_BACK_CLIP, STRING_FORWARD_CLIP};
-use crate::string::clip::ClipRule;
+use crate::split::solver::SplitSegments;
+use crate::string::clip::{ClipPath, ClipRule};
 use crate::string::graph::StringGraph;
 use crate::string::line::IntLine;
+use crate::string::polygonize::Polygonized;
 
 #[derive(Clone)]
 pub struct StringOverlay {
-    pub(super) segments: Vec<Segment<ShapeCountString>>,
+    pub(crate) segments: Vec<Segment<ShapeCountString>>,
+    /// The closed string contours, they are restored as rings by `clip_string_lines_keep_closed`.
+    pub(crate) string_contours: Vec<IntContour>,
+    #[cfg(feature = "record")]
+    pub(crate) recorder: Option<Recorder>,
 }
 
 impl StringOverlay {
@@ -27,6 +35,9 @@ impl StringOverlay {
     pub fn new(capacity: usize) -> Self {
         Self {
             segments: Vec::with_capacity(capacity),
+            string_contours: Vec::new(),
+            #[cfg(feature = "record")]
+            recorder: None,
         }
     }
 
@@ -149,6 +160,7 @@ impl StringOverlay {
             self.add_string_line([a, b]);
             a = b;
         }
+        self.string_contours.push(contour.to_vec());
     }
 
     /// Adds a string line paths to the overlay.
@@ -188,10 +200,118 @@ impl StringOverlay {
     /// A vector of `IntPath` instances representing the clipped sections of the input lines.
     #[inline]
     pub fn clip_string_lines_with_solver(self, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Vec<IntPath> {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, Some(clip_rule), solver);
         let links = OverlayLinkBuilder::build_string_with_clip_rule(self.segments, fill_rule, clip_rule, solver);
         StringGraph::new(solver, links).into_clip_string_lines()
     }
 
+    /// Clips lines like `clip_string_lines`, but returns every contour added with `add_string_contour`
+    /// which lies fully in the result as one closed ring.
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of line segments.
+    /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
+    /// # Returns
+    /// A vector of `ClipPath` instances, the other lines and the pieces of rings crossing the boundary are open paths.
+    #[inline]
+    pub fn clip_string_lines_keep_closed(self, fill_rule: FillRule, clip_rule: ClipRule) -> Vec<ClipPath<IntPoint>> {
+        self.clip_string_lines_keep_closed_with_solver(fill_rule, clip_rule, Default::default())
+    }
+
+    /// Clips lines like `clip_string_lines`, but returns every contour added with `add_string_contour`
+    /// which lies fully in the result as one closed ring.
+    /// - `fill_rule`: Specifies the rule determining the filled areas, influencing the inclusion of line segments.
+    /// - `clip_rule`: The rule for clipping, determining how the boundary and inversion settings affect the result.
+    /// - `solver`: A solver type to be used for advanced control over the graph building process.
+    ///
+    /// # Returns
+    /// A vector of `ClipPath` instances, the other lines and the pieces of rings crossing the boundary are open paths.
+    #[inline]
+    pub fn clip_string_lines_keep_closed_with_solver(self, fill_rule: FillRule, clip_rule: ClipRule, solver: Solver) -> Vec<ClipPath<IntPoint>> {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, Some(clip_rule), solver);
+        let links = OverlayLinkBuilder::build_string_with_clip_rule(self.segments, fill_rule, clip_rule, solver);
+        StringGraph::new(solver, links).into_clip_string_paths(&self.string_contours)
+    }
+
+    /// Splits the string lines at all intersections and overlaps, the shape contours are ignored.
+    /// # Returns
+    /// A vector of two point `IntPath` segments, which share their endpoints at the nodes.
+    /// Overlapping parts are returned once, a segment keeps the direction of its line unless it is covered by lines in both directions.
+    #[inline]
+    pub fn node_string_lines(self) -> Vec<IntPath> {
+        self.node_string_lines_with_solver(Default::default())
+    }
+
+    /// Splits the string lines at all intersections and overlaps, the shape contours are ignored.
+    /// - `solver`: A solver type to be used for advanced control over the splitting process.
+    ///
+    /// # Returns
+    /// A vector of two point `IntPath` segments, which share their endpoints at the nodes.
+    /// Overlapping parts are returned once, a segment keeps the direction of its line unless it is covered by lines in both directions.
+    pub fn node_string_lines_with_solver(self, solver: Solver) -> Vec<IntPath> {
+        let mut segments = self.segments;
+        segments.retain(|segment| segment.count.clip != 0);
+        if segments.is_empty() { return vec![]; }
+
+        segments.split_segments(solver)
+            .into_iter()
+            .map(|segment| {
+                let x_segment = segment.x_segment;
+                if segment.count.clip == STRING_FORWARD_CLIP {
+                    vec![x_segment.b, x_segment.a]
+                } else {
+                    vec![x_segment.a, x_segment.b]
+                }
+            })
+            .collect()
+    }
+
+    /// Joins the string lines into maximal polylines, the shape contours are ignored.
+    /// The lines are split at all intersections, and a polyline continues only through nodes shared by exactly two segments.
+    /// - `respect_direction`: If true, a polyline also stops at nodes where the directions of the lines do not follow each other, and every polyline keeps the direction of its lines.
+    ///
+    /// # Returns
+    /// A vector of `IntPath` polylines, a closed loop is returned with the same first and last point.
+    #[inline]
+    pub fn merge_string_lines(self, respect_direction: bool) -> Vec<IntPath> {
+        self.merge_string_lines_with_solver(respect_direction, Default::default())
+    }
+
+    /// Joins the string lines into maximal polylines, the shape contours are ignored.
+    /// The lines are split at all intersections, and a polyline continues only through nodes shared by exactly two segments.
+    /// - `respect_direction`: If true, a polyline also stops at nodes where the directions of the lines do not follow each other, and every polyline keeps the direction of its lines.
+    /// - `solver`: A solver type to be used for advanced control over the graph building process.
+    ///
+    /// # Returns
+    /// A vector of `IntPath` polylines, a closed loop is returned with the same first and last point.
+    pub fn merge_string_lines_with_solver(self, respect_direction: bool, solver: Solver) -> Vec<IntPath> {
+        let mut segments = self.segments;
+        segments.retain(|segment| segment.count.clip != 0);
+        let links = OverlayLinkBuilder::build_string_all(segments, FillRule::NonZero, solver);
+        StringGraph::new(solver, links).into_merged_lines(respect_direction)
+    }
+
+    /// Splits the string lines at all intersections and extracts all enclosed faces, the shape contours are ignored.
+    /// # Returns
+    /// A `Polygonized` result with the faces, the dangling lines and the cut edges.
+    #[inline]
+    pub fn polygonize(self) -> Polygonized<IntPoint> {
+        self.polygonize_with_min_area_and_solver(0, Default::default())
+    }
+
+    /// Splits the string lines at all intersections and extracts all enclosed faces, the shape contours are ignored.
+    /// - `min_area`: The minimum area of a face contour, smaller contours are dropped.
+    /// - `solver`: A solver type to be used for advanced control over the graph building process.
+    ///
+    /// # Returns
+    /// A `Polygonized` result with the faces, the dangling lines and the cut edges.
+    pub fn polygonize_with_min_area_and_solver(self, min_area: usize, solver: Solver) -> Polygonized<IntPoint> {
+        let mut segments = self.segments;
+        segments.retain(|segment| segment.count.clip != 0);
+        let links = OverlayLinkBuilder::build_string_all(segments, FillRule::NonZero, solver);
+        StringGraph::new(solver, links).polygonize(min_area)
+    }
+
     /// Converts the overlay into a `StringGraph`, using the specified `FillRule`.
     /// This graph is used for string operations, enabling analysis and manipulation of geometric data.
     /// - `fill_rule`: The rule that defines how to fill shapes (e.g., non-zero, even-odd).
@@ -206,6 +326,8 @@ impl StringOverlay {
     /// - `solver`: A solver type to be used for advanced control over the graph building process.
     #[inline]
     pub fn into_graph_with_solver(self, fill_rule: FillRule, solver: Solver) -> StringGraph {
+        #[cfg(feature = "record")]
+        self.record(fill_rule, None, solver);
         let links = OverlayLinkBuilder::build_string_all(self.segments, fill_rule, solver);
         StringGraph::new(solver, links)
     }
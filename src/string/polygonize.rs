@@ -0,0 +1,318 @@
+use i_float::int::point::IntPoint;
+use i_shape::base::data::{Paths, Shapes};
+use i_shape::int::count::PointsCount;
+use i_shape::int::path::IntPath;
+use i_shape::int::shape::IntShapes;
+use crate::bind::segment::IdSegment;
+use crate::bind::solver::{LeftBottomSegment, ShapeBinder, SortByAngle};
+use crate::core::extract::{StartPathData, Validate};
+use crate::core::solver::Solver;
+use crate::string::extract::Visit;
+use crate::string::graph::StringGraph;
+use crate::string::overlay::StringOverlay;
+
+/// The result of polygonization.
+/// - `shapes`: The enclosed faces, the first contour of a shape is its outer boundary, and all subsequent contours are holes.
+/// - `dangles`: The polylines that have a free end and do not enclose anything.
+/// - `cut_edges`: The polylines that connect closed parts of the network, but are not a part of any face boundary.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polygonized<P> {
+    pub shapes: Shapes<P>,
+    pub dangles: Paths<P>,
+    pub cut_edges: Paths<P>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EdgeKind {
+    Face,
+    Dangle,
+    Cut,
+}
+
+impl StringGraph {
+    pub(super) fn polygonize(&self, min_area: usize) -> Polygonized<IntPoint> {
+        let kinds = self.edge_kinds();
+
+        let mut dangles = StringOverlay::new(0);
+        let mut cut_edges = StringOverlay::new(0);
+        for (link, &kind) in self.links.iter().zip(kinds.iter()) {
+            let line = [link.a.point, link.b.point];
+            match kind {
+                EdgeKind::Face => {}
+                EdgeKind::Dangle => dangles.add_string_line(line),
+                EdgeKind::Cut => cut_edges.add_string_line(line),
+            }
+        }
+
+        Polygonized {
+            shapes: self.faces(&kinds, min_area),
+            dangles: dangles.merge_string_lines_with_solver(false, self.solver),
+            cut_edges: cut_edges.merge_string_lines_with_solver(false, self.solver),
+        }
+    }
+
+    /// Walks both sides of every face edge with the same walker as `extract_shapes`.
+    /// Enclosed faces are traced clockwise, and the outer boundary of every connected part is traced counterclockwise,
+    /// so it becomes a hole of the face around it.
+    fn faces(&self, kinds: &[EdgeKind], min_area: usize) -> IntShapes {
+        let mut binding: Vec<u8> = kinds.iter().map(|&kind| if kind == EdgeKind::Face { 2 } else { 0 }).collect();
+        let visited = binding.as_mut_slice();
+        let mut shapes: IntShapes = Vec::new();
+        let mut holes: Vec<IntPath> = Vec::new();
+
+        let mut link_index = 0;
+        while link_index < visited.len() {
+            if visited.is_visited(link_index) {
+                link_index += 1;
+                continue;
+            }
+
+            // the side above the left top link is already traced,
+            // unless it is the outside of a connected part which is not visited yet
+            let left_top_link = self.find_left_top_link(link_index, visited);
+            let is_hole = visited.count(left_top_link) == 2;
+            let start_data = StartPathData::new(is_hole, self.link(left_top_link), left_top_link);
+            let mut path = self.get_path(&start_data, visited);
+
+            if is_hole {
+                if path.validate(0).0 {
+                    holes.push(path);
+                }
+            } else if path.validate(min_area).0 {
+                shapes.push(vec![path]);
+            }
+        }
+
+        if shapes.is_empty() || holes.is_empty() {
+            return shapes;
+        }
+
+        // the frame takes the outer boundaries which are not inside of any face
+        let frame = Self::frame(&holes);
+
+        let mut anchors: Vec<IdSegment> = holes.iter().enumerate()
+            .map(|(id, hole)| IdSegment { id, x_segment: hole.left_bottom_segment() })
+            .collect();
+        anchors.sort_by_a_then_by_angle(&self.solver);
+
+        let solution = ShapeBinder::bind_contours(
+            &self.solver,
+            std::iter::once(frame.as_slice()).chain(shapes.iter().map(|shape| shape[0].as_slice())),
+            holes.iter().map(|hole| hole.as_slice()),
+            &anchors,
+        );
+
+        for (hole, &parent) in holes.into_iter().zip(solution.parent_for_child.iter()) {
+            if parent > 0 {
+                shapes[parent - 1].push(hole);
+            }
+        }
+
+        shapes
+    }
+
+    /// A clockwise rectangle around all the contours.
+    fn frame(contours: &[IntPath]) -> IntPath {
+        let mut min = IntPoint::new(i32::MAX, i32::MAX);
+        let mut max = IntPoint::new(i32::MIN, i32::MIN);
+        for p in contours.iter().flatten() {
+            min = IntPoint::new(min.x.min(p.x), min.y.min(p.y));
+            max = IntPoint::new(max.x.max(p.x), max.y.max(p.y));
+        }
+
+        let min = IntPoint::new(min.x - 1, min.y - 1);
+        let max = IntPoint::new(max.x + 1, max.y + 1);
+
+        vec![min, IntPoint::new(min.x, max.y), max, IntPoint::new(max.x, min.y)]
+    }
+
+    /// Removes the dangling edges layer by layer and marks the bridges among the rest edges as cut edges.
+    fn edge_kinds(&self) -> Vec<EdgeKind> {
+        let mut kinds = vec![EdgeKind::Face; self.links.len()];
+
+        let mut degrees: Vec<usize> = self.nodes.iter().map(|node| node.len()).collect();
+        let mut stack: Vec<usize> = (0..degrees.len()).filter(|&i| degrees[i] == 1).collect();
+        while let Some(node_id) = stack.pop() {
+            let Some(&link_index) = self.node(node_id).iter().find(|&&i| kinds[i] == EdgeKind::Face) else {
+                continue;
+            };
+            kinds[link_index] = EdgeKind::Dangle;
+            degrees[node_id] -= 1;
+            let other_id = self.link(link_index).other(node_id).id;
+            degrees[other_id] -= 1;
+            if degrees[other_id] == 1 {
+                stack.push(other_id);
+            }
+        }
+
+        // iterative Tarjan's bridge search
+        let mut order = vec![usize::MAX; self.nodes.len()];
+        let mut low = vec![0; self.nodes.len()];
+        let mut time = 0;
+        let mut stack: Vec<(usize, usize, usize)> = Vec::new();
+        for root in 0..self.nodes.len() {
+            if order[root] != usize::MAX || degrees[root] == 0 {
+                continue;
+            }
+            order[root] = time;
+            low[root] = time;
+            time += 1;
+            stack.push((root, usize::MAX, 0));
+
+            while let Some(&(node_id, parent_link, pos)) = stack.last() {
+                let node = self.node(node_id);
+                if pos < node.len() {
+                    stack.last_mut().unwrap().2 += 1;
+                    let link_index = node[pos];
+                    if link_index == parent_link || kinds[link_index] != EdgeKind::Face {
+                        continue;
+                    }
+                    let other_id = self.link(link_index).other(node_id).id;
+                    if order[other_id] == usize::MAX {
+                        order[other_id] = time;
+                        low[other_id] = time;
+                        time += 1;
+                        stack.push((other_id, link_index, 0));
+                    } else {
+                        low[node_id] = low[node_id].min(order[other_id]);
+                    }
+                } else {
+                    stack.pop();
+                    if let Some(&(parent_id, _, _)) = stack.last() {
+                        low[parent_id] = low[parent_id].min(low[node_id]);
+                        if low[node_id] > order[parent_id] {
+                            kinds[parent_link] = EdgeKind::Cut;
+                        }
+                    }
+                }
+            }
+        }
+
+        kinds
+    }
+}
+
+/// The `IntPolygonize` trait builds polygons from an unstructured set of lines.
+pub trait IntPolygonize {
+    /// Splits the lines at all intersections and extracts all enclosed faces.
+    ///
+    /// # Returns
+    /// A `Polygonized` result with the faces, the dangling lines and the cut edges.
+    fn polygonize(&self) -> Polygonized<IntPoint>;
+
+    /// Splits the lines at all intersections and extracts all enclosed faces.
+    /// - `min_area`: The minimum area of a face contour, smaller contours are dropped.
+    /// - `solver`: Type of solver to use.
+    ///
+    /// # Returns
+    /// A `Polygonized` result with the faces, the dangling lines and the cut edges.
+    fn polygonize_with_min_area_and_solver(&self, min_area: usize, solver: Solver) -> Polygonized<IntPoint>;
+}
+
+impl IntPolygonize for [IntPath] {
+    #[inline]
+    fn polygonize(&self) -> Polygonized<IntPoint> {
+        self.polygonize_with_min_area_and_solver(0, Default::default())
+    }
+
+    #[inline]
+    fn polygonize_with_min_area_and_solver(&self, min_area: usize, solver: Solver) -> Polygonized<IntPoint> {
+        let mut overlay = StringOverlay::new(self.points_count());
+        overlay.add_string_paths(self);
+        overlay.polygonize_with_min_area_and_solver(min_area, solver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use i_float::int::point::IntPoint;
+    use i_shape::int::path::{IntPath, PointPathExtension};
+    use crate::string::polygonize::IntPolygonize;
+
+    fn path(points: &[[i32; 2]]) -> IntPath {
+        points.iter().map(|p| IntPoint::new(p[0], p[1])).collect()
+    }
+
+    #[test]
+    fn test_two_rooms() {
+        let lines = [
+            path(&[[0, 0], [20, 0]]),
+            path(&[[20, 0], [20, 10]]),
+            path(&[[20, 10], [0, 10]]),
+            path(&[[0, 10], [0, 0]]),
+            path(&[[10, -5], [10, 15]]),
+        ];
+
+        let result = lines.polygonize();
+
+        assert_eq!(result.shapes.len(), 2);
+        assert!(result.shapes.iter().all(|shape| shape.len() == 1 && shape[0].len() == 4));
+        assert_eq!(result.dangles.len(), 2);
+        assert!(result.cut_edges.is_empty());
+    }
+
+    #[test]
+    fn test_cut_edge() {
+        let lines = [
+            path(&[[0, 0], [0, 10], [10, 10], [10, 0], [0, 0]]),
+            path(&[[20, 0], [20, 10], [30, 10], [30, 0], [20, 0]]),
+            path(&[[10, 5], [20, 5]]),
+            path(&[[30, 5], [40, 5]]),
+        ];
+
+        let result = lines.polygonize();
+
+        assert_eq!(result.shapes.len(), 2);
+        assert_eq!(result.dangles, [path(&[[30, 5], [40, 5]])]);
+        assert_eq!(result.cut_edges, [path(&[[10, 5], [20, 5]])]);
+    }
+
+    #[test]
+    fn test_hole() {
+        let lines = [
+            path(&[[0, 0], [0, 30], [30, 30], [30, 0], [0, 0]]),
+            path(&[[10, 10], [10, 20], [20, 20], [20, 10], [10, 10]]),
+        ];
+
+        let result = lines.polygonize();
+
+        assert_eq!(result.shapes.len(), 2);
+        let ring = result.shapes.iter().find(|shape| shape.len() == 2).unwrap();
+        assert!(ring[0].unsafe_area() > 0);
+        assert!(ring[1].unsafe_area() < 0);
+    }
+
+    #[test]
+    fn test_open_lines() {
+        let lines = [path(&[[0, 0], [10, 0], [10, 10]])];
+
+        let result = lines.polygonize();
+
+        assert!(result.shapes.is_empty());
+        assert_eq!(result.dangles.len(), 1);
+    }
+
+    #[test]
+    fn test_nested_rings() {
+        let lines = [
+            path(&[[0, 0], [0, 60], [60, 60], [60, 0], [0, 0]]),
+            path(&[[10, 10], [10, 50], [50, 50], [50, 10], [10, 10]]),
+            path(&[[20, 20], [20, 40], [40, 40], [40, 20], [20, 20]]),
+            path(&[[30, 20], [30, 40]]),
+            path(&[[100, 0], [100, 10], [110, 10], [110, 0], [100, 0]]),
+        ];
+
+        let result = lines.polygonize();
+
+        let mut sizes: Vec<(usize, i64)> = result.shapes.iter().map(|shape| (shape.len(), shape[0].unsafe_area())).collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [(1, 2 * 100), (1, 2 * 200), (1, 2 * 200), (2, 2 * 1600), (2, 2 * 3600)]);
+        for shape in result.shapes.iter() {
+            assert!(shape[0].unsafe_area() > 0);
+            assert!(shape.iter().skip(1).all(|hole| hole.unsafe_area() < 0));
+        }
+        let middle = result.shapes.iter().find(|shape| shape[0].unsafe_area() == 2 * 1600).unwrap();
+        assert_eq!(middle[1].unsafe_area(), -2 * 400);
+    }
+}
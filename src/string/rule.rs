@@ -1,17 +1,22 @@
 use crate::segm::segment::{SegmentFill, SUBJ_BOTTOM};
 
+/// The rule that defines how string lines modify the shapes during `StringGraph` extraction.
+/// - `Slice`: The lines split the shapes into separate faces.
+/// - `SliceKeepLines`: The same faces as `Slice`, and `extract_lines` returns the cut pieces of the lines inside the shapes.
+/// - `CutOpen`: The lines are cuts of zero width, a cut from the outer boundary to a hole opens the hole to the outside, so the shape gets a single contour that passes both sides of the cut.
+/// - `Imprint`: The shapes are not split, only the points where the lines touch or cross the boundaries are added to the contours.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StringRule {
-    Slice
+    Slice,
+    SliceKeepLines,
+    CutOpen,
+    Imprint,
 }
 
 impl StringRule {
     #[inline(always)]
     pub(crate) fn is_hole(&self, fill: SegmentFill) -> bool {
-        match self {
-            StringRule::Slice => {
-                fill & SUBJ_BOTTOM == 0
-            }
-        }
+        fill & SUBJ_BOTTOM == 0
     }
-}
\ No newline at end of file
+}
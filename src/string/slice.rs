@@ -1,16 +1,44 @@
+use std::collections::HashMap;
 use i_float::int::point::IntPoint;
-use i_shape::int::path::IntPath;
-use i_shape::int::shape::{IntShape, IntShapes};
+use i_shape::int::count::PointsCount;
+use i_shape::int::path::{IntPath, PointPathExtension};
+use i_shape::base::data::Shape;
+use i_shape::int::shape::{IntContour, IntShape, IntShapes};
 use crate::core::fill_rule::FillRule;
+use crate::core::link::OverlayLinkBuilder;
+use crate::core::solver::Solver;
+use crate::segm::winding_count::NO_SOURCE;
+use crate::string::graph::StringGraph;
 use crate::string::line::IntLine;
 use crate::string::overlay::StringOverlay;
 use crate::string::rule::StringRule;
 
+/// A piece of a sliced shape.
+/// - `shape`: The piece, the first contour is its outer boundary, and all subsequent contours are holes.
+/// - `source`: The index of the input shape the piece was cut from.
+/// - `lines`: The sorted indices of the input lines that bound the piece.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlicePiece<P> {
+    pub shape: Shape<P>,
+    pub source: usize,
+    pub lines: Vec<usize>,
+}
+
 pub trait IntSlice {
     fn slice_by_line(&self, line: IntLine, fill_rule: FillRule) -> IntShapes;
     fn slice_by_lines(&self, lines: &[IntLine], fill_rule: FillRule) -> IntShapes;
     fn slice_by_path(&self, path: &IntPath, fill_rule: FillRule) -> IntShapes;
     fn slice_by_paths(&self, paths: &[IntPath], fill_rule: FillRule) -> IntShapes;
+
+    /// Slices by paths like `slice_by_paths` and tags every piece with its source shape and the paths that bound it.
+    /// Overlapping shapes are joined by the fill rule, a piece gets the lowest index of the shapes it comes from.
+    /// - `paths`: The slicing paths.
+    /// - `fill_rule`: Fill rule to determine filled areas of every shape.
+    ///
+    /// # Returns
+    /// A vector of `SlicePiece` in the same order as `slice_by_paths`.
+    fn slice_by_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<SlicePiece<IntPoint>>;
 }
 
 impl IntSlice for IntShapes {
@@ -41,6 +69,11 @@ impl IntSlice for IntShapes {
         overlay.add_string_paths(paths);
         overlay.into_graph(fill_rule).extract_shapes(StringRule::Slice)
     }
+
+    #[inline]
+    fn slice_by_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<SlicePiece<IntPoint>> {
+        slice_with_sources(self, paths, fill_rule, 0, Default::default())
+    }
 }
 
 impl IntSlice for IntShape {
@@ -71,6 +104,11 @@ impl IntSlice for IntShape {
         overlay.add_string_paths(paths);
         overlay.into_graph(fill_rule).extract_shapes(StringRule::Slice)
     }
+
+    #[inline]
+    fn slice_by_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<SlicePiece<IntPoint>> {
+        slice_with_sources(std::slice::from_ref(self), paths, fill_rule, 0, Default::default())
+    }
 }
 
 impl IntSlice for [IntPoint] {
@@ -101,4 +139,138 @@ impl IntSlice for [IntPoint] {
         overlay.add_string_paths(paths);
         overlay.into_graph(fill_rule).extract_shapes(StringRule::Slice)
     }
-}
\ No newline at end of file
+
+    #[inline]
+    fn slice_by_paths_with_sources(&self, paths: &[IntPath], fill_rule: FillRule) -> Vec<SlicePiece<IntPoint>> {
+        slice_with_sources(&[vec![self.to_vec()]], paths, fill_rule, 0, Default::default())
+    }
+}
+
+/// Slices the shapes by the paths like `slice_by_paths` and tags every piece with its sources.
+/// The segments carry the indices of their shape and path through the split, so every contour edge
+/// is matched to exactly one link. A piece bounded only by paths takes the source of a neighbouring piece.
+pub(crate) fn slice_with_sources(shapes: &[IntShape], paths: &[IntPath], fill_rule: FillRule, min_area: usize, solver: Solver) -> Vec<SlicePiece<IntPoint>> {
+    let mut overlay = StringOverlay::new(0);
+    let mut segments = Vec::with_capacity(shapes.points_count() + paths.points_count());
+    for (index, shape) in shapes.iter().enumerate() {
+        overlay.add_shape_contours(shape);
+        segments.extend(overlay.segments.drain(..).map(|segment| segment.with_source(index as u32, NO_SOURCE)));
+    }
+    for (index, path) in paths.iter().enumerate() {
+        overlay.add_string_path(path);
+        segments.extend(overlay.segments.drain(..).map(|segment| segment.with_source(NO_SOURCE, index as u32)));
+    }
+
+    let (links, sources) = OverlayLinkBuilder::build_string_all_with_sources(segments, fill_rule, solver);
+    let graph = StringGraph::new(solver, links);
+
+    // the links are unique, a contour edge is found by its end points
+    let link_map: HashMap<(IntPoint, IntPoint), usize> = graph.links.iter().enumerate()
+        .map(|(index, link)| ((link.a.point, link.b.point), index))
+        .collect();
+
+    let shapes = graph.extract_shapes_min_area(StringRule::Slice, 0);
+
+    let mut link_pieces = vec![[usize::MAX; 2]; graph.links.len()];
+    let mut piece_links = Vec::with_capacity(shapes.len());
+    let mut pieces = Vec::with_capacity(shapes.len());
+    for (piece_index, shape) in shapes.into_iter().enumerate() {
+        let mut indices = Vec::new();
+        for contour in shape.iter() {
+            let mut p0 = if let Some(&p) = contour.last() { p } else { continue; };
+            for &p1 in contour.iter() {
+                let key = if p0 < p1 { (p0, p1) } else { (p1, p0) };
+                p0 = p1;
+                let Some(&link_index) = link_map.get(&key) else {
+                    debug_assert!(false, "a contour edge must be a link");
+                    continue;
+                };
+                indices.push(link_index);
+                let sides = &mut link_pieces[link_index];
+                if sides[0] == usize::MAX {
+                    sides[0] = piece_index;
+                } else if sides[0] != piece_index {
+                    sides[1] = piece_index;
+                }
+            }
+        }
+
+        let source = indices.iter().map(|&index| sources[index].shape).min().unwrap_or(NO_SOURCE);
+        let mut lines = Vec::new();
+        for &index in indices.iter() {
+            let source = &sources[index];
+            if source.is_shared_line {
+                // the link was merged from overlapping lines and keeps only the lowest one
+                let link = &graph.links[index];
+                lines.extend(lines_along(paths, link.a.point, link.b.point));
+            } else if source.line != NO_SOURCE {
+                lines.push(source.line as usize);
+            }
+        }
+        lines.sort_unstable();
+        lines.dedup();
+
+        pieces.push(SlicePiece { shape, source: source as usize, lines });
+        piece_links.push(indices);
+    }
+
+    // pass the source over the lines to the pieces which do not touch a shape boundary
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for piece_index in 0..pieces.len() {
+            if pieces[piece_index].source != NO_SOURCE as usize {
+                continue;
+            }
+            let neighbour = piece_links[piece_index].iter()
+                .flat_map(|&index| link_pieces[index])
+                .filter(|&other| other != usize::MAX && other != piece_index)
+                .map(|other| pieces[other].source)
+                .min();
+            if let Some(source) = neighbour.filter(|&source| source != NO_SOURCE as usize) {
+                pieces[piece_index].source = source;
+                is_changed = true;
+            }
+        }
+    }
+
+    if min_area > 0 {
+        let is_valid = |contour: &IntContour| contour.unsafe_area().unsigned_abs() as usize >> 1 >= min_area;
+        pieces.retain_mut(|piece| {
+            if !piece.shape.first().is_some_and(is_valid) {
+                return false;
+            }
+            let mut is_outer = true;
+            piece.shape.retain(|contour| std::mem::replace(&mut is_outer, false) || is_valid(contour));
+            true
+        });
+    }
+
+    pieces
+}
+
+/// Returns the indices of the paths which have an edge going along the segment `a`-`b`.
+fn lines_along(paths: &[IntPath], a: IntPoint, b: IntPoint) -> impl Iterator<Item = usize> + '_ {
+    paths.iter().enumerate()
+        .filter(move |(_, path)| path.windows(2).any(|edge| is_on_edge(edge[0], edge[1], a) && is_on_edge(edge[0], edge[1], b)))
+        .map(|(index, _)| index)
+}
+
+/// Checks if `p` is on the edge `e0`-`e1`, the split may round a point by a unit off the edge.
+fn is_on_edge(e0: IntPoint, e1: IntPoint, p: IntPoint) -> bool {
+    let in_range = |a: i32, b: i32, v: i32| (a.min(b) as i64 - 1..=a.max(b) as i64 + 1).contains(&(v as i64));
+    if !in_range(e0.x, e1.x, p.x) || !in_range(e0.y, e1.y, p.y) {
+        return false;
+    }
+
+    let ex = e1.x as i64 - e0.x as i64;
+    let ey = e1.y as i64 - e0.y as i64;
+    let px = p.x as i64 - e0.x as i64;
+    let py = p.y as i64 - e0.y as i64;
+
+    // the distance to the edge is not more than a unit: cross^2 <= |e|^2
+    let cross = (ex * py - ey * px) as i128;
+    let sqr_len = (ex * ex + ey * ey) as i128;
+
+    cross * cross <= sqr_len
+}
@@ -6,6 +6,10 @@ pub(super) trait Split {
     fn split_loops(self, min_area: usize) -> Vec<Self>
     where
         Self: Sized;
+
+    fn cut_open(self, min_area: usize) -> Vec<Self>
+    where
+        Self: Sized;
 }
 
 impl Split for IntPath {
@@ -40,6 +44,40 @@ impl Split for IntPath {
 
         result
     }
+    /// Keeps the path as a single contour, only the dead ends (a, b, a) of the cuts are removed.
+    fn cut_open(self, min_area: usize) -> Vec<Self> {
+        let mut path: IntPath = Vec::with_capacity(self.len());
+        for point in self {
+            let n = path.len();
+            if n >= 2 && path[n - 2] == point {
+                path.pop();
+            } else if path.last() != Some(&point) {
+                path.push(point);
+            }
+        }
+
+        // the same for the joint of the last and the first points
+        loop {
+            let n = path.len();
+            if n < 3 {
+                return Vec::new();
+            }
+            if path[0] == path[n - 1] || path[n - 2] == path[0] {
+                path.pop();
+            } else if path[n - 1] == path[1] {
+                path.remove(0);
+            } else {
+                break;
+            }
+        }
+
+        let abs_area = path.unsafe_area().unsigned_abs() as usize >> 1;
+        if abs_area == 0 || abs_area < min_area {
+            return Vec::new();
+        }
+
+        vec![path]
+    }
 }
 
 trait ValidateArea {
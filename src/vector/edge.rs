@@ -26,6 +26,7 @@ impl Reverse for SideFill {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VectorEdge {
     pub a: IntPoint,
     pub b: IntPoint,
@@ -165,7 +165,7 @@ impl JoinHoles for Vec<VectorShape> {
 
         segments.smart_bin_sort_by(solver, |a, b| a.x_segment.a.x.cmp(&b.x_segment.a.x));
 
-        let solution = ShapeBinder::bind(self.len(), hole_segments, segments);
+        let solution = ShapeBinder::bind(self.len(), &hole_segments, segments);
 
         for (shape_index, &capacity) in solution.children_count_for_parent.iter().enumerate() {
             self[shape_index].reserve_exact(capacity);
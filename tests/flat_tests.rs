@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use i_overlay::core::context::OverlayContext;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::flat::FlatShapes;
+    use i_overlay::core::overlay::{Overlay, ShapeType};
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_shape::int::path::IntPaths;
+
+    const RULES: [OverlayRule; 7] = [
+        OverlayRule::Subject,
+        OverlayRule::Clip,
+        OverlayRule::Intersect,
+        OverlayRule::Union,
+        OverlayRule::Difference,
+        OverlayRule::InverseDifference,
+        OverlayRule::Xor,
+    ];
+
+    // some tests have no expected results, so only the input is read
+    fn load_input(index: usize) -> (IntPaths, IntPaths, FillRule) {
+        let path = format!("./tests/boolean/test_{}.json", index);
+        let data: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let subj: IntPaths = serde_json::from_value(data["subjPaths"].clone()).unwrap();
+        let clip: IntPaths = serde_json::from_value(data["clipPaths"].clone()).unwrap();
+        let fill_rule = match data["fillRule"].as_i64() {
+            Some(1) => FillRule::NonZero,
+            _ => FillRule::EvenOdd,
+        };
+        (subj, clip, fill_rule)
+    }
+
+    fn tests_count() -> usize {
+        std::fs::read_dir("./tests/boolean").unwrap().count()
+    }
+
+    #[test]
+    fn test_extract_into_matches_extract() {
+        let mut flat = FlatShapes::new();
+        for index in 0..tests_count() {
+            let (subj, clip, fill_rule) = load_input(index);
+            let graph = Overlay::with_contours(&subj, &clip).into_graph(fill_rule);
+
+            for rule in RULES {
+                graph.extract_shapes_into(rule, 0, &mut flat);
+                let shapes = graph.extract_shapes(rule);
+                assert_eq!(flat.to_shapes(), shapes, "test {} {:?}", index, rule);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlay_with_context_matches_overlay() {
+        let mut context = OverlayContext::new();
+        let mut overlay = Overlay::new(0);
+        let mut flat = FlatShapes::new();
+        for index in 0..tests_count() {
+            let (subj, clip, fill_rule) = load_input(index);
+            for rule in RULES {
+                overlay.add_contours(&subj, ShapeType::Subject);
+                overlay.add_contours(&clip, ShapeType::Clip);
+                overlay.overlay_with_context(rule, fill_rule, 0, Default::default(), &mut context, &mut flat);
+
+                let shapes = Overlay::with_contours(&subj, &clip).overlay(rule, fill_rule);
+                assert_eq!(flat.to_shapes(), shapes, "test {} {:?}", index, rule);
+            }
+        }
+    }
+}
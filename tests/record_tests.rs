@@ -0,0 +1,69 @@
+#[allow(dead_code)]
+mod util;
+
+#[cfg(all(test, feature = "record"))]
+mod tests {
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::float::string_overlay::FloatStringOverlay;
+    use i_overlay::record::{Record, Recorder};
+    use i_overlay::string::clip::ClipRule;
+    use i_shape::int::shape::IntShapes;
+    use crate::util::overlay;
+
+    #[test]
+    fn test_replay_boolean_data() {
+        for index in [0, 5, 42] {
+            let path = format!("./tests/boolean/test_{}.json", index);
+            let Record::Overlay(record) = Record::load(&path).unwrap() else { panic!("overlay record is expected") };
+
+            let data: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            let union: Vec<IntShapes> = serde_json::from_value(data["union"].clone()).unwrap();
+            let replay = record.replay_with_rule(OverlayRule::Union);
+            assert!(overlay::is_group_of_shapes_one_of(&replay, &union));
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let subj = [[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]];
+        let clip = [[1.0, 1.0], [1.0, 3.0], [3.0, 3.0], [3.0, 1.0]];
+
+        let recorder = Recorder::new();
+        let _ = FloatOverlay::with_subj_and_clip(&subj, &clip)
+            .record_to(&recorder)
+            .overlay(OverlayRule::Intersect, FillRule::EvenOdd);
+
+        let line = [[-1.0, 1.0], [4.0, 1.0]];
+        let clip_rule = ClipRule { invert: false, boundary_included: true };
+        let _ = FloatStringOverlay::with_shape_and_string(&subj, &line)
+            .record_to(&recorder)
+            .clip_string_lines_with_solver(FillRule::NonZero, clip_rule, Default::default());
+
+        let records = recorder.records();
+        assert_eq!(records.len(), 2);
+
+        let path = std::env::temp_dir().join(format!("i_overlay_record_{}.json", std::process::id()));
+        for record in records.iter() {
+            record.save(&path).unwrap();
+            let loaded = Record::load(&path).unwrap();
+            match (record, &loaded) {
+                (Record::Overlay(a), Record::Overlay(b)) => {
+                    assert_eq!(a.subj_paths, b.subj_paths);
+                    assert_eq!(a.clip_paths, b.clip_paths);
+                    assert_eq!(b.overlay_rule, Some(OverlayRule::Intersect));
+                    assert_eq!(a.replay(), b.replay());
+                }
+                (Record::String(a), Record::String(b)) => {
+                    assert_eq!(a.body, b.body);
+                    assert_eq!(a.string, b.string);
+                    assert!(b.clip_rule.is_some_and(|rule| rule.boundary_included));
+                    assert_eq!(a.replay(), b.replay());
+                }
+                _ => panic!("record kind is changed"),
+            }
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}
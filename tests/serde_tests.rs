@@ -0,0 +1,73 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay_rule::OverlayRule;
+    use i_overlay::core::solver::{Precision, Solver, Strategy};
+    use i_overlay::float::overlay::FloatOverlay;
+    use i_overlay::mesh::stroke::offset::StrokeOffset;
+    use i_overlay::mesh::style::{LineCap, LineJoin, StrokeStyle};
+    use i_overlay::string::clip::ClipRule;
+    use i_overlay::string::rule::StringRule;
+
+    #[derive(Serialize, Deserialize)]
+    struct Job {
+        subj: Vec<Vec<[f64; 2]>>,
+        clip: Vec<Vec<[f64; 2]>>,
+        overlay_rule: OverlayRule,
+        fill_rule: FillRule,
+        solver: Solver,
+    }
+
+    #[test]
+    fn test_job_round_trip() {
+        let job = Job {
+            subj: vec![vec![[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]]],
+            clip: vec![vec![[1.0, 1.0], [1.0, 3.0], [3.0, 3.0], [3.0, 1.0]]],
+            overlay_rule: OverlayRule::Difference,
+            fill_rule: FillRule::NonZero,
+            solver: Solver { strategy: Strategy::List, precision: Precision::MEDIUM, multithreading: None },
+        };
+
+        let json = serde_json::to_string(&job).unwrap();
+        let replay: Job = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(replay.overlay_rule, job.overlay_rule);
+        assert_eq!(replay.fill_rule, job.fill_rule);
+        assert_eq!(replay.solver.strategy, Strategy::List);
+        assert_eq!(replay.solver.precision, Precision::MEDIUM);
+        assert!(replay.solver.multithreading.is_none());
+
+        let run = |job: &Job| {
+            FloatOverlay::with_subj_and_clip(&job.subj, &job.clip)
+                .overlay_with_filter_and_solver(job.overlay_rule, job.fill_rule, Default::default(), job.solver)
+        };
+
+        assert_eq!(run(&replay), run(&job));
+    }
+
+    #[test]
+    fn test_rules() {
+        let json = serde_json::to_string(&(StringRule::Slice, ClipRule { invert: true, boundary_included: false })).unwrap();
+        let (string_rule, clip_rule): (StringRule, ClipRule) = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(string_rule, StringRule::Slice);
+        assert!(clip_rule.invert);
+        assert!(!clip_rule.boundary_included);
+    }
+
+    #[test]
+    fn test_stroke_style() {
+        let style = StrokeStyle::new(2.0)
+            .start_cap(LineCap::Round(0.1))
+            .end_cap(LineCap::Custom(vec![[0.0, 0.0], [1.0, 0.5]]))
+            .line_join(LineJoin::Miter(0.5));
+
+        let json = serde_json::to_string(&style).unwrap();
+        let replay: StrokeStyle<[f64; 2], f64> = serde_json::from_str(&json).unwrap();
+
+        let path = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]];
+        let expected = path.stroke(style, false);
+        assert_eq!(path.stroke(replay, false), expected);
+    }
+}
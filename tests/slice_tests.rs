@@ -4,7 +4,10 @@ mod tests {
     use i_shape::int::path::IntPath;
     use rand::Rng;
     use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::float::string_overlay::FloatStringOverlay;
     use i_overlay::string::line::IntLine;
+    use i_overlay::string::overlay::StringOverlay;
+    use i_overlay::string::rule::StringRule;
     use i_overlay::string::slice::IntSlice;
 
     #[test]
@@ -491,6 +494,162 @@ mod tests {
         }
     }
 
+    fn square(radius: i32) -> IntPath {
+        [
+            IntPoint::new(-radius, -radius),
+            IntPoint::new(-radius, radius),
+            IntPoint::new(radius, radius),
+            IntPoint::new(radius, -radius)
+        ].to_vec()
+    }
+
+    #[test]
+    fn test_slice_keep_lines() {
+        let mut overlay = StringOverlay::with_shape_contour(&square(10));
+        overlay.add_string_line([IntPoint::new(0, 15), IntPoint::new(0, -15)]);
+        let graph = overlay.into_graph(FillRule::NonZero);
+
+        let shapes = graph.extract_shapes(StringRule::SliceKeepLines);
+        let lines = graph.extract_lines(StringRule::SliceKeepLines);
+
+        assert_eq!(shapes, graph.extract_shapes(StringRule::Slice));
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(lines, [vec![IntPoint::new(0, 10), IntPoint::new(0, -10)]]);
+        assert!(graph.extract_lines(StringRule::Slice).is_empty());
+    }
+
+    #[test]
+    fn test_cut_open() {
+        let mut hole = square(10);
+        hole.reverse();
+        let mut overlay = StringOverlay::with_shape_contours(&[square(20), hole]);
+        overlay.add_string_line([IntPoint::new(0, -25), IntPoint::new(0, -5)]);
+        let graph = overlay.into_graph(FillRule::NonZero);
+
+        let opened = graph.extract_shapes(StringRule::CutOpen);
+
+        assert_eq!(opened.len(), 1);
+        assert_eq!(opened[0].len(), 1);
+        let contour = &opened[0][0];
+        assert_eq!(contour.len(), 12);
+        assert_eq!(contour.iter().filter(|&&p| p == IntPoint::new(0, -20)).count(), 2);
+        assert_eq!(contour.iter().filter(|&&p| p == IntPoint::new(0, -10)).count(), 2);
+    }
+
+    #[test]
+    fn test_cut_open_dead_end() {
+        let mut overlay = StringOverlay::with_shape_contour(&square(10));
+        overlay.add_string_line([IntPoint::new(0, -15), IntPoint::new(0, 0)]);
+
+        let result = overlay.into_graph(FillRule::NonZero).extract_shapes(StringRule::CutOpen);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        assert_eq!(result[0][0].len(), 5);
+    }
+
+    #[test]
+    fn test_imprint() {
+        let mut overlay = StringOverlay::with_shape_contour(&square(10));
+        overlay.add_string_line([IntPoint::new(0, -15), IntPoint::new(0, 15)]);
+        overlay.add_string_line([IntPoint::new(-15, 5), IntPoint::new(-5, 5)]);
+
+        let result = overlay.into_graph(FillRule::NonZero).extract_shapes(StringRule::Imprint);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        let contour = &result[0][0];
+        assert_eq!(contour.len(), 7);
+        assert!(contour.contains(&IntPoint::new(0, -10)));
+        assert!(contour.contains(&IntPoint::new(0, 10)));
+        assert!(contour.contains(&IntPoint::new(-10, 5)));
+    }
+
+    #[test]
+    fn test_imprint_float() {
+        let rect = [[0.0f32, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let line = [[5.0f32, -5.0], [5.0, 15.0]];
+
+        // the default filter simplifies f32 contours, it must keep the imprinted points
+        let result = FloatStringOverlay::with_shape_and_string(&rect, &line)
+            .into_graph(FillRule::NonZero)
+            .extract_shapes(StringRule::Imprint);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 1);
+        let contour = &result[0][0];
+        assert_eq!(contour.len(), 6);
+        assert!(contour.contains(&[5.0, 0.0]));
+        assert!(contour.contains(&[5.0, 10.0]));
+
+        let rect = [[0.0f64, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        let line = [[5.0f64, -5.0], [5.0, 15.0]];
+
+        let result = FloatStringOverlay::with_shape_and_string(&rect, &line)
+            .into_graph(FillRule::NonZero)
+            .extract_shapes(StringRule::Imprint);
+
+        assert_eq!(result[0][0].len(), 6);
+    }
+
+    #[test]
+    fn test_slice_with_sources() {
+        let shapes = vec![
+            vec![square(10)],
+            vec![square(10).iter().map(|p| IntPoint::new(p.x + 30, p.y)).collect()],
+        ];
+        let lines = vec![
+            vec![IntPoint::new(30, -15), IntPoint::new(30, 15)],
+            vec![IntPoint::new(-15, 0), IntPoint::new(0, 0)],
+            vec![IntPoint::new(-15, 5), IntPoint::new(15, 5)],
+        ];
+
+        let pieces = shapes.slice_by_paths_with_sources(&lines, FillRule::NonZero);
+
+        assert_eq!(pieces.len(), 4);
+        assert!(pieces.iter().filter(|piece| piece.source == 0).all(|piece| piece.lines == [2]));
+        assert!(pieces.iter().filter(|piece| piece.source == 1).all(|piece| piece.lines == [0]));
+        assert_eq!(pieces.iter().filter(|piece| piece.source == 1).count(), 2);
+    }
+
+    #[test]
+    fn test_slice_with_sources_inner_ring() {
+        let shapes = vec![
+            vec![square(10)],
+            vec![square(10).iter().map(|p| IntPoint::new(p.x + 30, p.y)).collect()],
+        ];
+        let ring: IntPath = square(5).iter().map(|p| IntPoint::new(p.x + 30, p.y)).collect();
+        let lines = vec![
+            // runs along the top edge of the first square, but outside of it
+            vec![IntPoint::new(-10, 11), IntPoint::new(10, 11)],
+            [ring.as_slice(), &ring[..1]].concat(),
+        ];
+
+        let pieces = shapes.slice_by_paths_with_sources(&lines, FillRule::NonZero);
+        let expected = shapes.slice_by_paths(&lines, FillRule::NonZero);
+
+        assert_eq!(pieces.iter().map(|piece| piece.shape.clone()).collect::<Vec<_>>(), expected);
+        assert_eq!(pieces.len(), 3);
+        assert!(pieces.iter().filter(|piece| piece.source == 0).all(|piece| piece.lines.is_empty()));
+        assert!(pieces.iter().filter(|piece| piece.source == 1).all(|piece| piece.lines == [1]));
+        assert_eq!(pieces.iter().filter(|piece| piece.source == 1).count(), 2);
+    }
+
+    #[test]
+    fn test_slice_with_sources_overlapping_lines() {
+        let shapes = vec![vec![square(10)]];
+        let lines = vec![
+            vec![IntPoint::new(-15, 0), IntPoint::new(15, 0)],
+            vec![IntPoint::new(0, 0), IntPoint::new(15, 0)],
+            vec![IntPoint::new(15, 0), IntPoint::new(-5, 0)],
+        ];
+
+        let pieces = shapes.slice_by_paths_with_sources(&lines, FillRule::NonZero);
+
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(|piece| piece.source == 0 && piece.lines == [0, 1, 2]));
+    }
+
     fn random_polygon(radius: i32, n: usize) -> IntPath {
         let a = radius / 2;
         let range = -a..=a;